@@ -9,13 +9,23 @@ use std::rc::Rc;
 use gtk4::glib;
 use gtk4::prelude::*;
 
+use crate::daemon::PanelState;
 use crate::dbus::bluetooth_device::BluetoothDevice;
 use crate::dbus::bluetooth_manager::BluetoothManager;
+use crate::dbus::polkit;
 use crate::ui::device_list;
+use crate::ui::device_row::DeviceRowCallbacks;
 use crate::ui::window::PanelWidgets;
 
 use super::AppState;
-use super::bt_helpers::{get_bt, no_op_menu_active, no_op_remove, refresh_bt_list};
+use super::bt_adapters;
+use super::bt_details;
+use super::bt_discoverable;
+use super::bt_pairing;
+use super::bt_reconnect;
+use super::bt_rename;
+use super::bt_transfer;
+use super::bt_helpers::{get_bt, pair_with_timeout, refresh_bt_list, render_bt_list};
 use super::bt_scanning::{
     run_bt_scan_burst, start_bt_background_tasks, ManualBtScanUi,
 };
@@ -30,7 +40,11 @@ const BT_MANUAL_SCAN_WINDOW_MS: u64 = 5000;
 /// Set up all Bluetooth UI event handlers.
 ///
 /// Hides the BT tab entirely when no Bluetooth adapter is available.
-pub(super) fn setup_bluetooth(widgets: &PanelWidgets, state: Rc<RefCell<AppState>>) {
+pub(super) fn setup_bluetooth(
+    widgets: &PanelWidgets,
+    state: Rc<RefCell<AppState>>,
+    panel_state: PanelState,
+) {
     let bt_tab = widgets.bt_tab.clone();
     let bt_spinner = widgets.bt_spinner.clone();
     let bt_scroll = widgets.bt_scroll.clone();
@@ -39,6 +53,13 @@ pub(super) fn setup_bluetooth(widgets: &PanelWidgets, state: Rc<RefCell<AppState
     let switch = widgets.wifi_switch.clone();
     let scan_btn = widgets.scan_button.clone();
     let title = widgets.title_label.clone();
+    let pairing = widgets.bt_pairing.clone();
+    let transfer = widgets.bt_transfer.clone();
+    let rename = widgets.bt_rename.clone();
+    let details = widgets.bt_details.clone();
+    let adapter_dropdown = widgets.bt_adapter_dropdown.clone();
+    let discoverable_button = widgets.bt_discoverable_button.clone();
+    setup_device_filter(widgets, Rc::clone(&state));
 
     glib::spawn_future_local(async move {
         let bt = match BluetoothManager::new().await {
@@ -53,6 +74,26 @@ pub(super) fn setup_bluetooth(widgets: &PanelWidgets, state: Rc<RefCell<AppState
 
         log::info!("Bluetooth adapter available — BT tab enabled");
         state.borrow_mut().bluetooth = Some(bt.clone());
+        panel_state.set_bluetooth(bt.clone());
+        bt_pairing::setup_pairing_agent(&pairing, Rc::clone(&state), bt.clone());
+        state.borrow_mut().bt_file_sender = Some(bt_transfer::FileSender::new(&transfer, &status));
+        bt_transfer::setup_file_receiver(Rc::clone(&state));
+        let renamer = bt_rename::DeviceRenamer::new(&rename, &state, &bt_list_box, &status);
+        state.borrow_mut().bt_renamer = Some(renamer);
+        bt_details::setup_device_details(&details);
+        state.borrow_mut().bt_details = Some(details);
+        bt_adapters::setup_adapter_selector(
+            &adapter_dropdown,
+            &bt_tab,
+            Rc::clone(&state),
+            panel_state,
+        );
+        bt_discoverable::setup_discoverable_toggle(
+            &discoverable_button,
+            &bt_tab,
+            &status,
+            Rc::clone(&state),
+        );
 
         // ── BT tab activation ──────────────────────────────────────────────
         {
@@ -81,6 +122,11 @@ pub(super) fn setup_bluetooth(widgets: &PanelWidgets, state: Rc<RefCell<AppState
                 }
 
                 title.set_text("Bluetooth");
+                glib::spawn_future_local(bt_adapters::update_adapter_tooltip(
+                    title.clone(),
+                    btn.clone(),
+                    Rc::clone(&state),
+                ));
                 scan_btn.set_tooltip_text(Some("Scan for devices"));
                 switch.set_tooltip_text(Some("Enable/Disable Bluetooth"));
 
@@ -99,13 +145,22 @@ pub(super) fn setup_bluetooth(widgets: &PanelWidgets, state: Rc<RefCell<AppState
                         None => return,
                     };
 
-                    let powered = match bt.is_powered().await {
+                    let mut powered = match bt.is_powered().await {
                         Ok(p) => p,
                         Err(e) => {
                             log::error!("Failed to get BT power state: {e}");
                             true
                         }
                     };
+                    if !powered && state.borrow().config.bt_auto_power_on {
+                        match bt.set_powered(true).await {
+                            Ok(()) => {
+                                powered = true;
+                                bt_reconnect::reconnect_trusted_audio(&state);
+                            }
+                            Err(e) => log::error!("Failed to power on Bluetooth: {e}"),
+                        }
+                    }
                     switch.set_active(powered);
 
                     if !powered {
@@ -118,8 +173,8 @@ pub(super) fn setup_bluetooth(widgets: &PanelWidgets, state: Rc<RefCell<AppState
                             &bt_list_box,
                             &[],
                             &empty,
-                            no_op_remove(),
-                            no_op_menu_active(),
+                            &empty,
+                            &DeviceRowCallbacks::default(),
                         );
                         state.borrow_mut().bt_row_paths = row_paths;
                         stop_bt_background_tasks(&state);
@@ -165,10 +220,16 @@ pub(super) fn setup_bluetooth(widgets: &PanelWidgets, state: Rc<RefCell<AppState
                         None => return,
                     };
 
+                    // The switch is also set when the tab opens; only a real
+                    // power-on should reconnect devices
+                    let was_powered = bt.is_powered().await.unwrap_or(false);
                     match bt.set_powered(enabled).await {
                         Ok(_) => {
                             if enabled {
                                 status.set_text("Bluetooth enabled");
+                                if !was_powered {
+                                    bt_reconnect::reconnect_trusted_audio(&state);
+                                }
                                 scan_btn.set_sensitive(false);
                                 run_bt_scan_burst(
                                     Rc::clone(&state),
@@ -190,8 +251,8 @@ pub(super) fn setup_bluetooth(widgets: &PanelWidgets, state: Rc<RefCell<AppState
                                     &bt_list_box,
                                     &[],
                                     &empty,
-                                    no_op_remove(),
-                                    no_op_menu_active(),
+                                    &empty,
+                                    &DeviceRowCallbacks::default(),
                                 );
                                 state.borrow_mut().bt_row_paths = row_paths;
                                 stop_bt_background_tasks(&state);
@@ -200,7 +261,11 @@ pub(super) fn setup_bluetooth(widgets: &PanelWidgets, state: Rc<RefCell<AppState
                         }
                         Err(e) => {
                             log::error!("BT power toggle failed: {e}");
-                            status.set_text("Toggle failed");
+                            status.set_text(if polkit::is_not_authorized(&e) {
+                                polkit::NOT_AUTHORIZED
+                            } else {
+                                "Toggle failed"
+                            });
                         }
                     }
                 });
@@ -238,6 +303,10 @@ pub(super) fn setup_bluetooth(widgets: &PanelWidgets, state: Rc<RefCell<AppState
                     let (Some(device), Some(bt)) = (device, bt) else {
                         return;
                     };
+                    // Already busy (e.g. pairing); the row's own Cancel handles that
+                    if state.borrow().bt_pending.contains_key(&device.device_path) {
+                        return;
+                    }
 
                     handle_device_row_click(state, status, bt_list_box, device, bt).await;
                 });
@@ -246,6 +315,59 @@ pub(super) fn setup_bluetooth(widgets: &PanelWidgets, state: Rc<RefCell<AppState
     });
 }
 
+/// Filter the device list by the search query and, while the header filter
+/// button is active, hide devices that have no name.
+///
+/// Paired and connected devices are always listed, named or not. Section
+/// headers are hidden while a query is active, like on the Wi-Fi list.
+fn setup_device_filter(widgets: &PanelWidgets, state: Rc<RefCell<AppState>>) {
+    let list_box = widgets.bt_list_box.clone();
+    let filter_button = widgets.bt_filter_button.clone();
+    let search_entry = widgets.bt_search_entry.clone();
+    let search_bar = widgets.bt_search_bar.clone();
+    filter_button.set_active(state.borrow().config.bt_hide_unnamed);
+
+    {
+        let filter_button = filter_button.clone();
+        let search_entry = search_entry.clone();
+        list_box.set_filter_func(move |row| {
+            let query = search_entry.text().to_lowercase();
+            let hide_unnamed = filter_button.is_active();
+            if query.is_empty() && !hide_unnamed {
+                return true;
+            }
+            // The list is being rebuilt; re-filtered once the paths are stored
+            let Ok(st) = state.try_borrow() else {
+                return true;
+            };
+            let path = match st.bt_row_paths.get(row.index() as usize) {
+                Some(Some(path)) => path,
+                Some(None) => return query.is_empty(),
+                None => return true,
+            };
+            st.bt_devices
+                .iter()
+                .find(|d| d.device_path == *path)
+                .is_none_or(|d| {
+                    d.display_name.to_lowercase().contains(&query)
+                        && (!hide_unnamed || d.named || d.paired || d.connected)
+                })
+        });
+    }
+    {
+        let list_box = list_box.clone();
+        filter_button.connect_toggled(move |_| list_box.invalidate_filter());
+    }
+    search_entry.connect_search_changed(move |_| list_box.invalidate_filter());
+
+    // Clear the query when the bar is closed so the full list comes back
+    search_bar.connect_search_mode_enabled_notify(move |bar| {
+        if !bar.is_search_mode() {
+            search_entry.set_text("");
+        }
+    });
+}
+
 /// Execute the connect / disconnect / pair flow for a tapped device row.
 async fn handle_device_row_click(
     state: Rc<RefCell<AppState>>,
@@ -266,15 +388,9 @@ async fn handle_device_row_click(
             st.bt_pending
                 .insert(device.device_path.clone(), pending_label.to_string());
         }
+        // Redraw from the cached list so the row's spinner shows at once
+        render_bt_list(state, bt_list_box, status);
         status.set_text(&format!("{} {}...", status_prefix, device.display_name));
-        glib::spawn_future_local({
-            let state = Rc::clone(state);
-            let bt_list_box = bt_list_box.clone();
-            let status = status.clone();
-            async move {
-                refresh_bt_list(&state, &bt_list_box, &status).await;
-            }
-        });
     };
 
     // Clear a pending entry and refresh the list.
@@ -323,9 +439,19 @@ async fn handle_device_row_click(
             }
         }
     } else {
-        // "Just Works" pair then connect
+        // Pair (the agent prompts for a PIN if needed), then connect
         set_pending(&state, &status, &bt_list_box, &device, "Pairing", "Pairing with");
-        match bt.pair_device(&device.device_path).await {
+        let Some(result) = pair_with_timeout(&state, &bt, &device.device_path).await else {
+            log::warn!("BT pairing with {} timed out", device.address);
+            status.set_text(&format!(
+                "Pairing with {} timed out after {} s",
+                device.display_name,
+                state.borrow().config.bt_pairing_timeout
+            ));
+            clear_pending(&state, &bt_list_box, &status, &device);
+            return;
+        };
+        match result {
             Ok(_) => {
                 let _ = bt.trust_device(&device.device_path, true).await;
                 set_pending(&state, &status, &bt_list_box, &device, "Connecting", "Connecting to");
@@ -334,9 +460,16 @@ async fn handle_device_row_click(
                 clear_pending(&state, &bt_list_box, &status, &device);
                 refresh_bt_list(&state, &bt_list_box, &status).await;
             }
+            Err(zbus::Error::MethodError(name, _, _))
+                if name.as_str() == "org.bluez.Error.AuthenticationCanceled" =>
+            {
+                log::info!("BT pairing with {} cancelled", device.address);
+                status.set_text("Pairing cancelled");
+                clear_pending(&state, &bt_list_box, &status, &device);
+            }
             Err(e) => {
                 log::error!("BT pairing failed: {e}");
-                status.set_text("Pairing failed — try bluetoothctl");
+                status.set_text("Pairing failed");
                 clear_pending(&state, &bt_list_box, &status, &device);
             }
         }
@@ -0,0 +1,178 @@
+//! Adapter selector — picks which Bluetooth adapter the BT page manages.
+//!
+//! Only shown with two or more adapters (e.g. internal + USB dongle). The
+//! choice is remembered in state.toml by adapter address, since object paths
+//! (hci0, hci1) can swap between boots.
+//!
+//! The header title's tooltip names the adapter in use while the BT tab is
+//! open.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+
+use crate::daemon::PanelState;
+use crate::dbus::bluetooth_manager::AdapterInfo;
+use crate::state::AppStateStore;
+
+use super::AppState;
+use super::bt_helpers::get_bt;
+use super::bt_scanning::stop_bt_background_tasks;
+
+/// Settle the adapter to manage, apply the config's adapter defaults to it,
+/// then fill the adapter dropdown and switch adapters when it changes.
+pub(super) fn setup_adapter_selector(
+    dropdown: &gtk4::DropDown,
+    bt_tab: &gtk4::ToggleButton,
+    state: Rc<RefCell<AppState>>,
+    panel_state: PanelState,
+) {
+    let dropdown = dropdown.clone();
+    let bt_tab = bt_tab.clone();
+
+    glib::spawn_future_local(async move {
+        let Some(bt) = get_bt(&state) else {
+            return;
+        };
+        let adapters = match bt.list_adapters().await {
+            Ok(adapters) => adapters,
+            Err(e) => {
+                log::warn!("Failed to list Bluetooth adapters: {e}");
+                Vec::new()
+            }
+        };
+
+        let mut current = adapters
+            .iter()
+            .position(|a| a.path == bt.adapter_path())
+            .unwrap_or(0);
+        if adapters.len() > 1
+            && let Some(address) = AppStateStore::load().bt_adapter
+            && let Some(index) = adapters.iter().position(|a| a.address == address)
+            && index != current
+            && switch_adapter(&state, &panel_state, &adapters[index], &bt_tab).await
+        {
+            current = index;
+        }
+
+        // Config defaults go to the adapter actually managed
+        if let Some(bt) = get_bt(&state) {
+            bt.apply_startup_defaults().await;
+        }
+        if adapters.len() < 2 {
+            return;
+        }
+
+        let labels: Vec<String> = adapters
+            .iter()
+            .map(|a| format!("{} ({})", a.alias, a.address))
+            .collect();
+        let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+        dropdown.set_model(Some(&gtk4::StringList::new(&labels)));
+        dropdown.set_selected(current as u32);
+        dropdown.set_visible(true);
+
+        dropdown.connect_selected_notify(move |dropdown| {
+            let Some(adapter) = adapters.get(dropdown.selected() as usize).cloned() else {
+                return;
+            };
+            let state = Rc::clone(&state);
+            let panel_state = panel_state.clone();
+            let bt_tab = bt_tab.clone();
+            glib::spawn_future_local(async move {
+                if switch_adapter(&state, &panel_state, &adapter, &bt_tab).await {
+                    AppStateStore::update(|s| s.bt_adapter = Some(adapter.address.clone()));
+                }
+            });
+        });
+    });
+}
+
+/// Show the managed adapter's name and address as the header title tooltip.
+///
+/// With several adapters the tooltip also says which one is selected.
+pub(super) async fn update_adapter_tooltip(
+    title: gtk4::Label,
+    bt_tab: gtk4::ToggleButton,
+    state: Rc<RefCell<AppState>>,
+) {
+    let Some(bt) = get_bt(&state) else {
+        return;
+    };
+    let adapters = match bt.list_adapters().await {
+        Ok(adapters) => adapters,
+        Err(e) => {
+            log::warn!("Failed to list Bluetooth adapters: {e}");
+            return;
+        }
+    };
+    // The Wi-Fi tab clears the tooltip; don't put it back
+    if !bt_tab.is_active() {
+        return;
+    }
+    let Some(index) = adapters.iter().position(|a| a.path == bt.adapter_path()) else {
+        title.set_tooltip_text(None);
+        return;
+    };
+
+    let adapter = &adapters[index];
+    let mut text = format!("{} ({})", adapter.alias, adapter.address);
+    if adapters.len() > 1 {
+        let name = adapter.path.rsplit('/').next().unwrap_or_default();
+        text.push_str(&format!(
+            "\nAdapter {} of {} — {name}",
+            index + 1,
+            adapters.len()
+        ));
+    }
+    title.set_tooltip_text(Some(&text));
+}
+
+/// Make `adapter` the one the BT page manages.
+///
+/// Discovery on the previous adapter is stopped; if the BT tab is open it is
+/// re-activated so power state, list and scanning follow the new adapter.
+async fn switch_adapter(
+    state: &Rc<RefCell<AppState>>,
+    panel_state: &PanelState,
+    adapter: &AdapterInfo,
+    bt_tab: &gtk4::ToggleButton,
+) -> bool {
+    let Some(old) = get_bt(state) else {
+        return false;
+    };
+    if old.adapter_path() == adapter.path {
+        return false;
+    }
+    let new = match old.with_adapter(&adapter.path) {
+        Ok(new) => new,
+        Err(e) => {
+            log::error!("Failed to switch Bluetooth adapter: {e}");
+            return false;
+        }
+    };
+
+    stop_bt_background_tasks(state);
+    let _ = old.stop_discovery().await;
+    log::info!(
+        "Bluetooth adapter switched to {} ({})",
+        adapter.alias,
+        adapter.address
+    );
+    {
+        let mut st = state.borrow_mut();
+        st.bluetooth = Some(new.clone());
+        st.bt_pending.clear();
+    }
+    panel_state.set_bluetooth(new.clone());
+    let switched = state.borrow().bt_adapter_switched.clone();
+    if let Some(switched) = switched {
+        let _ = switched.send(new).await;
+    }
+    if bt_tab.is_active() {
+        bt_tab.emit_by_name::<()>("toggled", &[]);
+    }
+    true
+}
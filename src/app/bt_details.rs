@@ -0,0 +1,113 @@
+//! Device details — what the row menu's "Details" shows for a Bluetooth device.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Instant;
+
+use gtk4::prelude::*;
+
+use crate::dbus::bluetooth_device::BluetoothDevice;
+use crate::ui::network_details::{self, NetworkDetails};
+
+use super::AppState;
+
+/// Wire the Close button of the details section.
+pub(super) fn setup_device_details(details: &NetworkDetails) {
+    let revealer = details.revealer.clone();
+    details
+        .close_button
+        .connect_clicked(move |_| revealer.set_reveal_child(false));
+}
+
+/// Build the callback behind the row menu's "Details".
+pub(super) fn build_details_callback(state: &Rc<RefCell<AppState>>) -> Rc<dyn Fn(String)> {
+    let state = Rc::clone(state);
+    Rc::new(move |device_path| {
+        let st = state.borrow();
+        let Some(details) = st.bt_details.clone() else {
+            return;
+        };
+        let Some(device) = st
+            .bt_devices
+            .iter()
+            .find(|d| d.device_path == device_path)
+            .cloned()
+        else {
+            return;
+        };
+        drop(st);
+        show_details(&details, &device);
+    })
+}
+
+/// Fill the details section for `device` and reveal it.
+///
+/// Everything comes from the cached device; fields BlueZ didn't report
+/// (no services, no battery) are left out rather than shown as blank.
+fn show_details(details: &NetworkDetails, device: &BluetoothDevice) {
+    let mut rows = vec![("Address", device.address.clone())];
+    if !device.address_type.is_empty() {
+        let kind = match device.address_type.as_str() {
+            "random" => "Random (LE)",
+            "public" => "Public",
+            other => other,
+        };
+        rows.push(("Address type", kind.to_string()));
+    }
+
+    let mut flags = Vec::new();
+    if device.connected {
+        flags.push("Connected");
+    }
+    if device.paired {
+        flags.push("Paired");
+    }
+    if device.trusted {
+        flags.push("Trusted");
+    }
+    if device.blocked {
+        flags.push("Blocked");
+    }
+    let flags = if flags.is_empty() {
+        "Not paired".to_string()
+    } else {
+        flags.join(" · ")
+    };
+    rows.push(("Status", flags));
+
+    if device.is_in_range() {
+        rows.push(("Signal", format!("{} dBm", device.rssi)));
+    }
+    if let Some(battery) = device.battery {
+        rows.push(("Battery", format!("{battery}%")));
+    }
+    let services = device.services();
+    if !services.is_empty() {
+        rows.push(("Services", services.join(", ")));
+    }
+    let last_seen = if device.connected || device.is_in_range() {
+        "Now".to_string()
+    } else {
+        match device.last_seen {
+            Some(seen) => format_last_seen(seen),
+            None => "Not this session".to_string(),
+        }
+    };
+    rows.push(("Last seen", last_seen));
+
+    details.title.set_text(&device.display_name);
+    network_details::set_detail_rows(details, &rows);
+    details.revealer.set_reveal_child(true);
+}
+
+/// "Just now" / "5 min ago" / "2 h ago".
+fn format_last_seen(seen: Instant) -> String {
+    let secs = seen.elapsed().as_secs();
+    if secs < 60 {
+        "Just now".to_string()
+    } else if secs < 3600 {
+        format!("{} min ago", secs / 60)
+    } else {
+        format!("{} h ago", secs / 3600)
+    }
+}
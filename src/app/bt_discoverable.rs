@@ -0,0 +1,134 @@
+//! Discoverable mode — makes this machine visible so phones can pair from
+//! their side.
+//!
+//! BlueZ turns discoverable mode off by itself after `bt_discoverable_timeout`
+//! seconds; the header button is re-synced from the adapter when that happens
+//! and whenever the BT tab is opened.
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+
+use super::AppState;
+use super::bt_helpers::get_bt;
+
+/// Wire the header's "Make discoverable" toggle.
+pub(super) fn setup_discoverable_toggle(
+    button: &gtk4::ToggleButton,
+    bt_tab: &gtk4::ToggleButton,
+    status: &gtk4::Label,
+    state: Rc<RefCell<AppState>>,
+) {
+    // Set while the button is updated from the adapter, so that doesn't
+    // write the same value straight back
+    let syncing = Rc::new(Cell::new(false));
+    let expiry: Rc<RefCell<Option<glib::SourceId>>> = Rc::new(RefCell::new(None));
+
+    {
+        let button = button.clone();
+        let state = Rc::clone(&state);
+        let syncing = Rc::clone(&syncing);
+        bt_tab.connect_toggled(move |tab| {
+            if tab.is_active() {
+                glib::spawn_future_local(sync_button(
+                    button.clone(),
+                    Rc::clone(&state),
+                    Rc::clone(&syncing),
+                ));
+            }
+        });
+    }
+
+    let status = status.clone();
+    button.connect_toggled(move |button| {
+        if syncing.get() {
+            return;
+        }
+        let Some(bt) = get_bt(&state) else {
+            return;
+        };
+        let discoverable = button.is_active();
+        let timeout = state.borrow().config.bt_discoverable_timeout;
+
+        if let Some(id) = expiry.borrow_mut().take() {
+            id.remove();
+        }
+
+        let button = button.clone();
+        let status = status.clone();
+        let state = Rc::clone(&state);
+        let syncing = Rc::clone(&syncing);
+        let expiry = Rc::clone(&expiry);
+        glib::spawn_future_local(async move {
+            if let Err(e) = bt.set_discoverable(discoverable, timeout).await {
+                log::error!("Failed to change Bluetooth visibility: {e}");
+                status.set_text("Couldn't change visibility — is Bluetooth on?");
+                sync_button(button, state, syncing).await;
+                return;
+            }
+            if !discoverable {
+                status.set_text("Hidden from other devices");
+                return;
+            }
+
+            let name = bt.adapter_alias().await.unwrap_or_default();
+            let name = if name.is_empty() {
+                "this device".to_string()
+            } else {
+                name
+            };
+            if timeout == 0 {
+                status.set_text(&format!("Visible as {name}"));
+                return;
+            }
+            status.set_text(&format!(
+                "Visible as {name} for {}",
+                format_timeout(timeout)
+            ));
+
+            // BlueZ switches it off on its own; follow it a moment later
+            let delay = Duration::from_secs(u64::from(timeout) + 1);
+            let id = {
+                let expiry = Rc::clone(&expiry);
+                glib::timeout_add_local_once(delay, move || {
+                    expiry.borrow_mut().take();
+                    glib::spawn_future_local(sync_button(button, state, syncing));
+                })
+            };
+            *expiry.borrow_mut() = Some(id);
+        });
+    });
+}
+
+/// Set the button from the adapter's current Discoverable state.
+async fn sync_button(
+    button: gtk4::ToggleButton,
+    state: Rc<RefCell<AppState>>,
+    syncing: Rc<Cell<bool>>,
+) {
+    let Some(bt) = get_bt(&state) else {
+        return;
+    };
+    let discoverable = match bt.is_discoverable().await {
+        Ok(d) => d,
+        Err(e) => {
+            log::warn!("Failed to read Bluetooth visibility: {e}");
+            false
+        }
+    };
+    syncing.set(true);
+    button.set_active(discoverable);
+    syncing.set(false);
+}
+
+/// "3 min" / "45 s" for the status line.
+fn format_timeout(secs: u32) -> String {
+    if secs >= 60 && secs % 60 == 0 {
+        format!("{} min", secs / 60)
+    } else {
+        format!("{secs} s")
+    }
+}
@@ -5,29 +5,29 @@
 
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::time::Duration;
 
+use futures_util::future::{self, Either};
 use gtk4::glib;
 
+use crate::config::BtSortOrder;
+use crate::controls::audio_profile::{AudioMode, AudioProfiles};
+use crate::dbus::bluetooth_device::BluetoothDevice;
 use crate::dbus::bluetooth_manager::BluetoothManager;
-use crate::ui::device_list;
+use crate::dbus::bt_tether::TetherManager;
+use crate::ui::device_row::DeviceRowCallbacks;
+use crate::ui::{device_list, window};
 
 use super::AppState;
 
+/// How long the one-off GATT battery read may take before it's abandoned.
+const GATT_BATTERY_READ_TIMEOUT_SECS: u64 = 5;
+
 /// Extract BluetoothManager from AppState.
 pub(super) fn get_bt(state: &Rc<RefCell<AppState>>) -> Option<BluetoothManager> {
     state.borrow().bluetooth.clone()
 }
 
-/// No-op remove callback (used when BT is off / list is empty).
-pub(super) fn no_op_remove() -> std::rc::Rc<dyn Fn(String)> {
-    std::rc::Rc::new(|_path| {})
-}
-
-/// No-op menu-active callback (used when BT is off / list is empty).
-pub(super) fn no_op_menu_active() -> std::rc::Rc<dyn Fn(bool)> {
-    std::rc::Rc::new(|_active| {})
-}
-
 /// Build the callback that handles "Unpair device" from the row context menu.
 pub(super) fn build_remove_callback(
     state: &Rc<RefCell<AppState>>,
@@ -74,6 +74,157 @@ pub(super) fn build_remove_callback(
     })
 }
 
+/// Build the callback that switches a headset between A2DP and HFP.
+///
+/// Goes through the sound server's card profiles; BlueZ itself has no say.
+pub(super) fn build_audio_mode_callback(
+    status: &gtk4::Label,
+) -> std::rc::Rc<dyn Fn(String, AudioMode)> {
+    let status = status.clone();
+    std::rc::Rc::new(move |address, mode| {
+        let status = status.clone();
+        glib::spawn_future_local(async move {
+            status.set_text(&format!("Switching to {mode}..."));
+            let result = match AudioProfiles::connect().await {
+                Ok(profiles) => profiles.set_mode(&address, mode).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(()) => status.set_text(&format!("Switched to {mode}")),
+                Err(e) => {
+                    log::error!("Audio profile switch for {address} failed: {e}");
+                    status.set_text(&e);
+                }
+            }
+        });
+    })
+}
+
+/// Build the callback behind a connected audio device's volume slider.
+pub(super) fn build_volume_callback(bt: &BluetoothManager) -> std::rc::Rc<dyn Fn(String, u16)> {
+    let bt = bt.clone();
+    std::rc::Rc::new(move |transport_path, volume| {
+        let bt = bt.clone();
+        glib::spawn_future_local(async move {
+            if let Err(e) = bt.set_transport_volume(&transport_path, volume).await {
+                log::error!("Setting Bluetooth volume failed: {e}");
+            }
+        });
+    })
+}
+
+/// Build the callback behind the row menu's "Connect only" entries.
+///
+/// Brings up just the chosen profile, e.g. audio without the headset's HID
+/// remote control.
+pub(super) fn build_connect_profile_callback(
+    state: &Rc<RefCell<AppState>>,
+    list_box: &gtk4::ListBox,
+    status: &gtk4::Label,
+    bt: &BluetoothManager,
+) -> std::rc::Rc<dyn Fn(String, String)> {
+    let state = Rc::clone(state);
+    let list_box = list_box.clone();
+    let status = status.clone();
+    let bt = bt.clone();
+    std::rc::Rc::new(move |device_path, uuid| {
+        let state = Rc::clone(&state);
+        let list_box = list_box.clone();
+        let status = status.clone();
+        let bt = bt.clone();
+        glib::spawn_future_local(async move {
+            let label = state
+                .borrow()
+                .bt_devices
+                .iter()
+                .find(|d| d.device_path == device_path)
+                .and_then(|d| {
+                    d.connectable_profiles()
+                        .into_iter()
+                        .find(|(u, _)| *u == uuid)
+                        .map(|(_, label)| label)
+                })
+                .unwrap_or("profile");
+            status.set_text(&format!("Connecting {label}..."));
+            state
+                .borrow_mut()
+                .bt_pending
+                .insert(device_path.clone(), "Connecting".to_string());
+            refresh_bt_list(&state, &list_box, &status).await;
+
+            let result = bt.connect_profile(&device_path, &uuid).await;
+            state.borrow_mut().bt_pending.remove(&device_path);
+            refresh_bt_list(&state, &list_box, &status).await;
+            match result {
+                Ok(()) => status.set_text(&format!("Connected {label}")),
+                Err(e) => {
+                    log::error!("Connect profile {uuid} failed: {e}");
+                    status.set_text(&format!("Failed to connect {label}"));
+                }
+            }
+        });
+    })
+}
+
+/// Build the callback behind a pairing row's Cancel button.
+///
+/// The pairing flow itself reports the outcome once BlueZ aborts it.
+pub(super) fn build_cancel_pairing_callback(
+    status: &gtk4::Label,
+    bt: &BluetoothManager,
+) -> std::rc::Rc<dyn Fn(String)> {
+    let status = status.clone();
+    let bt = bt.clone();
+    std::rc::Rc::new(move |device_path| {
+        let status = status.clone();
+        let bt = bt.clone();
+        glib::spawn_future_local(async move {
+            status.set_text("Cancelling pairing...");
+            if let Err(e) = bt.cancel_pairing(&device_path).await {
+                log::error!("Cancel pairing failed: {e}");
+            }
+        });
+    })
+}
+
+/// Build the callback behind the row menu's "Use phone's internet".
+///
+/// NetworkManager brings up the PAN link; the profile is created on first use
+/// and reused after that.
+pub(super) fn build_tether_callback(
+    state: &Rc<RefCell<AppState>>,
+    status: &gtk4::Label,
+    bt: &BluetoothManager,
+) -> std::rc::Rc<dyn Fn(String)> {
+    let state = Rc::clone(state);
+    let status = status.clone();
+    let bt = bt.clone();
+    std::rc::Rc::new(move |device_path| {
+        let Some(device) = state
+            .borrow()
+            .bt_devices
+            .iter()
+            .find(|d| d.device_path == device_path)
+            .cloned()
+        else {
+            return;
+        };
+        let status = status.clone();
+        let tether = TetherManager::new(bt.connection());
+        glib::spawn_future_local(async move {
+            let name = device.display_name;
+            status.set_text(&format!("Connecting to {name}'s internet..."));
+            match tether.connect(&device.address, &name).await {
+                Ok(_) => status.set_text(&format!("Using {name}'s internet")),
+                Err(e) => {
+                    log::error!("Bluetooth tethering via {name} failed: {e}");
+                    status.set_text("Failed to connect — is tethering on?");
+                }
+            }
+        });
+    })
+}
+
 /// Build the callback that tracks whether a row context menu is open.
 ///
 /// While open, list refreshes are suppressed to avoid the popover closing.
@@ -84,6 +235,113 @@ pub(super) fn build_menu_active_callback(state: &Rc<RefCell<AppState>>) -> std::
     })
 }
 
+/// Pair with `bt_pairing_timeout` applied.
+///
+/// Returns `None` if it timed out; the pairing has been cancelled by then.
+pub(super) async fn pair_with_timeout(
+    state: &Rc<RefCell<AppState>>,
+    bt: &BluetoothManager,
+    device_path: &str,
+) -> Option<zbus::Result<()>> {
+    let timeout = state.borrow().config.bt_pairing_timeout;
+    if timeout == 0 {
+        return Some(bt.pair_device(device_path).await);
+    }
+    let pair = bt.pair_device(device_path);
+    let timer = glib::timeout_future(std::time::Duration::from_secs(timeout));
+    futures_util::pin_mut!(pair, timer);
+    match futures_util::future::select(pair, timer).await {
+        futures_util::future::Either::Left((result, _)) => Some(result),
+        futures_util::future::Either::Right(_) => {
+            let _ = bt.cancel_pairing(device_path).await;
+            None
+        }
+    }
+}
+
+/// Sort devices for display per `bt_sort_order`.
+pub(super) fn sort_devices(devices: &mut [BluetoothDevice], order: BtSortOrder) {
+    match order {
+        BtSortOrder::Name => devices.sort_by_cached_key(|d| d.sort_key()),
+        BtSortOrder::Proximity => devices.sort_by_cached_key(|d| d.proximity_sort_key()),
+    }
+}
+
+/// Fill in battery levels of connected LE devices from their GATT service.
+///
+/// Levels come from a cache, so a slow device can't hold up the list; the
+/// first time a device is seen connected its level is read in the background
+/// and the list re-rendered once it arrives. Disconnecting forgets the level.
+fn apply_gatt_batteries(
+    state: &Rc<RefCell<AppState>>,
+    list_box: &gtk4::ListBox,
+    status: &gtk4::Label,
+    bt: &BluetoothManager,
+    devices: &mut [BluetoothDevice],
+) {
+    let mut st = state.borrow_mut();
+    st.bt_gatt_batteries.retain(|path, _| {
+        devices
+            .iter()
+            .any(|d| d.connected && d.device_path == *path)
+    });
+
+    for device in devices
+        .iter_mut()
+        .filter(|d| d.connected && d.battery.is_none())
+    {
+        let Some(characteristic) = device.gatt_battery_path.clone() else {
+            continue;
+        };
+        if let Some(cached) = st.bt_gatt_batteries.get(&device.device_path) {
+            device.battery = *cached;
+            continue;
+        }
+        st.bt_gatt_batteries
+            .insert(device.device_path.clone(), None);
+
+        let state = Rc::clone(state);
+        let list_box = list_box.clone();
+        let status = status.clone();
+        let bt = bt.clone();
+        let device_path = device.device_path.clone();
+        let address = device.address.clone();
+        glib::spawn_future_local(async move {
+            let read = std::pin::pin!(bt.read_gatt_battery(&characteristic));
+            let timer = std::pin::pin!(glib::timeout_future(Duration::from_secs(
+                GATT_BATTERY_READ_TIMEOUT_SECS
+            )));
+            let level = match future::select(read, timer).await {
+                Either::Left((Ok(level), _)) => level,
+                Either::Left((Err(e), _)) => {
+                    log::debug!("GATT battery read for {address} failed: {e}");
+                    return;
+                }
+                Either::Right(_) => {
+                    log::debug!("GATT battery read for {address} timed out");
+                    return;
+                }
+            };
+            {
+                let mut st = state.borrow_mut();
+                // Disconnected (and forgotten) while the read was out
+                let Some(cached) = st.bt_gatt_batteries.get_mut(&device_path) else {
+                    return;
+                };
+                *cached = Some(level);
+                if let Some(device) = st
+                    .bt_devices
+                    .iter_mut()
+                    .find(|d| d.device_path == device_path)
+                {
+                    device.battery = Some(level);
+                }
+            }
+            render_bt_list(&state, &list_box, &status);
+        });
+    }
+}
+
 /// Refresh the Bluetooth device list from D-Bus and update the UI.
 ///
 /// Skips the refresh if a context menu is currently open.
@@ -102,25 +360,25 @@ pub(super) async fn refresh_bt_list(
     };
 
     match bt.get_devices().await {
-        Ok(devices) => {
-            let connected = devices.iter().find(|d| d.connected);
-            match connected {
-                Some(d) => status.set_text(&format!("Connected to {}", d.display_name)),
-                None => status.set_text("Not connected"),
+        Ok(mut devices) => {
+            if state.borrow().config.ble_gatt_battery {
+                apply_gatt_batteries(state, list_box, status, &bt, &mut devices);
             }
-
-            let on_remove = build_remove_callback(state, list_box, status, &bt);
-            let on_menu_active = build_menu_active_callback(state);
-            let row_paths = device_list::populate_device_list(
-                list_box,
-                &devices,
-                &state.borrow().bt_pending,
-                on_remove,
-                on_menu_active,
-            );
-            state.borrow_mut().bt_row_paths = row_paths;
+            // Keep when out-of-range devices were last heard
+            {
+                let st = state.borrow();
+                for device in devices.iter_mut().filter(|d| d.last_seen.is_none()) {
+                    device.last_seen = st
+                        .bt_devices
+                        .iter()
+                        .find(|old| old.device_path == device.device_path)
+                        .and_then(|old| old.last_seen);
+                }
+            }
+            sort_devices(&mut devices, state.borrow().config.bt_sort_order);
             log::info!("BT device list refreshed: {} devices", devices.len());
             state.borrow_mut().bt_devices = devices;
+            render_bt_list(state, list_box, status);
         }
         Err(e) => {
             log::error!("Failed to get BT devices: {e}");
@@ -128,3 +386,64 @@ pub(super) async fn refresh_bt_list(
         }
     }
 }
+
+/// Rebuild the Bluetooth rows from the devices already in `AppState`.
+///
+/// Used directly when a signal carried the change, so no D-Bus round trip
+/// is needed. Skipped while a context menu is open.
+pub(super) fn render_bt_list(
+    state: &Rc<RefCell<AppState>>,
+    list_box: &gtk4::ListBox,
+    status: &gtk4::Label,
+) {
+    if state.borrow().bt_menu_open {
+        return;
+    }
+    let Some(bt) = get_bt(state) else {
+        return;
+    };
+    let devices = state.borrow().bt_devices.clone();
+
+    let connected = devices.iter().find(|d| d.connected);
+    match connected {
+        Some(d) => status.set_text(&format!("Connected to {}", d.display_name)),
+        None => status.set_text("Not connected"),
+    }
+    window::set_panel_class(list_box, "bt-connected", connected.is_some());
+
+    let callbacks = DeviceRowCallbacks {
+        on_remove: super::guest::guard(
+            list_box,
+            status,
+            "unpair this device",
+            build_remove_callback(state, list_box, status, &bt),
+        ),
+        on_repair: super::guest::guard(
+            list_box,
+            status,
+            "re-pair this device",
+            super::bt_repair::build_repair_callback(state, list_box, status, &bt),
+        ),
+        on_audio_mode: build_audio_mode_callback(status),
+        on_volume: build_volume_callback(&bt),
+        on_connect_profile: build_connect_profile_callback(state, list_box, status, &bt),
+        on_cancel_pairing: build_cancel_pairing_callback(status, &bt),
+        on_send_file: super::bt_transfer::build_send_file_callback(state),
+        on_tether: build_tether_callback(state, status, &bt),
+        on_rename: super::guest::guard(
+            list_box,
+            status,
+            "rename this device",
+            super::bt_rename::build_rename_callback(state),
+        ),
+        on_details: super::bt_details::build_details_callback(state),
+        on_menu_active: build_menu_active_callback(state),
+    };
+    let pending = state.borrow().bt_pending.clone();
+    let bt_icons = state.borrow().config.bt_icons.clone();
+    let row_paths =
+        device_list::populate_device_list(list_box, &devices, &pending, &bt_icons, &callbacks);
+    state.borrow_mut().bt_row_paths = row_paths;
+    // Rows were filtered before the paths above were stored
+    list_box.invalidate_filter();
+}
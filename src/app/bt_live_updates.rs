@@ -8,16 +8,21 @@ use std::rc::Rc;
 use gtk4::glib;
 use gtk4::prelude::*;
 
+use crate::config::BtSortOrder;
+use crate::dbus::bluetooth_manager::BluetoothManager;
 use crate::dbus::bluez_proxies::BluezObjectManagerProxy;
 use crate::ui::window::PanelWidgets;
 
-use super::bt_helpers::refresh_bt_list;
 use super::AppState;
+use super::bt_helpers::{refresh_bt_list, render_bt_list, sort_devices};
 
 /// Subscribe to BlueZ ObjectManager signals for live BT updates.
 ///
 /// Watches `InterfacesAdded` — fires when a new device is discovered or
-/// a device's interface changes (e.g. Connected property change).
+/// a device gains an interface — and Device1 `PropertiesChanged`, which
+/// carries Connected / Paired / Trusted / Alias changes made elsewhere.
+/// Property changes are applied to the cached devices without re-reading
+/// everything from BlueZ.
 ///
 /// This refreshes the BT device list automatically, but only when the
 /// Bluetooth tab is active.
@@ -73,6 +78,24 @@ pub(super) fn setup_bt_live_updates(widgets: &PanelWidgets, state: Rc<RefCell<Ap
             }
         };
 
+        // Battery1.Percentage — subtitle shows the charge
+        let battery_stream = match bt.receive_battery_changes().await {
+            Ok(s) => Some(s),
+            Err(e) => {
+                log::warn!("Failed to subscribe to battery changes: {e}");
+                None
+            }
+        };
+
+        // Device1 properties — connect/pair/rename done from another tool
+        let device_stream = match bt.receive_device_changes().await {
+            Ok(s) => Some(s),
+            Err(e) => {
+                log::warn!("Failed to subscribe to device property changes: {e}");
+                None
+            }
+        };
+
         if added_stream.is_none() && removed_stream.is_none() {
             log::error!("BT live updates: failed to subscribe to InterfacesAdded/Removed");
             return;
@@ -107,16 +130,78 @@ pub(super) fn setup_bt_live_updates(widgets: &PanelWidgets, state: Rc<RefCell<Ap
         let state_removed = Rc::clone(&state);
         if let Some(mut removed_stream) = removed_stream {
             glib::spawn_future_local(async move {
-                while (removed_stream.next().await).is_some() {
-                    if !bt_tab_removed.is_active() {
+                while let Some(signal) = removed_stream.next().await {
+                    let Ok(args) = signal.args() else {
+                        continue;
+                    };
+                    if !args.interfaces.iter().any(|i| i == "org.bluez.Device1") {
                         continue;
                     }
-                    log::debug!("BT InterfacesRemoved — refreshing device list");
-                    glib::timeout_future(std::time::Duration::from_millis(300)).await;
-                    if !bt_tab_removed.is_active() {
+                    // Drop it even while the tab is hidden, so nothing acts
+                    // on a device BlueZ no longer knows
+                    let path = args.object_path.as_str();
+                    let removed = {
+                        let mut st = state_removed.borrow_mut();
+                        st.bt_pending.remove(path);
+                        let before = st.bt_devices.len();
+                        st.bt_devices.retain(|d| d.device_path != path);
+                        st.bt_devices.len() != before
+                    };
+                    if removed && bt_tab_removed.is_active() {
+                        log::debug!("BT device {path} removed — dropping its row");
+                        render_bt_list(&state_removed, &bt_list_box_removed, &status_removed);
+                    }
+                }
+            });
+        }
+
+        let bt_tab_changed = bt_tab.clone();
+        let bt_list_box_changed = bt_list_box.clone();
+        let status_changed = status.clone();
+        let state_changed = Rc::clone(&state);
+        if let Some(mut device_stream) = device_stream {
+            glib::spawn_future_local(async move {
+                while let Some(msg) = device_stream.next().await {
+                    if !bt_tab_changed.is_active() {
+                        continue;
+                    }
+                    let Some((path, changed)) = msg
+                        .ok()
+                        .and_then(|msg| BluetoothManager::parse_properties_changed(&msg))
+                    else {
+                        continue;
+                    };
+                    let updated = {
+                        let mut st = state_changed.borrow_mut();
+                        let order = st.config.bt_sort_order;
+                        let updated = st
+                            .bt_devices
+                            .iter_mut()
+                            .find(|d| d.device_path == path)
+                            .is_some_and(|d| d.apply_changes(&changed));
+                        // A new RSSI can change who's nearest
+                        let moved = order == BtSortOrder::Proximity && changed.contains_key("RSSI");
+                        if updated || moved {
+                            sort_devices(&mut st.bt_devices, order);
+                        }
+                        updated || moved
+                    };
+                    if updated {
+                        log::debug!("BT device {path} changed — updating its row");
+                        render_bt_list(&state_changed, &bt_list_box_changed, &status_changed);
+                    }
+                }
+            });
+        }
+
+        if let Some(mut battery_stream) = battery_stream {
+            glib::spawn_future_local(async move {
+                while (battery_stream.next().await).is_some() {
+                    if !bt_tab.is_active() {
                         continue;
                     }
-                    refresh_bt_list(&state_removed, &bt_list_box_removed, &status_removed).await;
+                    log::debug!("BT battery level changed — refreshing device list");
+                    refresh_bt_list(&state, &bt_list_box, &status).await;
                 }
             });
         }
@@ -0,0 +1,169 @@
+//! Bluetooth pairing prompts — the panel side of the BlueZ agent.
+//!
+//! Requests arrive from `dbus::bluez_agent` one at a time; the inline prompt
+//! on the Bluetooth page answers them — typed PIN / passkey, or Confirm /
+//! Reject for numeric comparison. A newer request (or a cancel from BlueZ)
+//! replaces whatever was still on screen.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+
+use crate::dbus::bluetooth_manager::BluetoothManager;
+use crate::dbus::bluez_agent::{AgentEvent, PairingPrompt, PairingRequest};
+use crate::ui::pairing_prompt;
+
+use super::AppState;
+
+/// Register the agent and route its prompts to the pairing section.
+pub(super) fn setup_pairing_agent(
+    prompt: &pairing_prompt::PairingPrompt,
+    state: Rc<RefCell<AppState>>,
+    bt: BluetoothManager,
+) {
+    let pending: Rc<RefCell<Option<PairingRequest>>> = Rc::new(RefCell::new(None));
+
+    {
+        let pending = Rc::clone(&pending);
+        let revealer = prompt.revealer.clone();
+        prompt.cancel_button.connect_clicked(move |_| {
+            revealer.set_reveal_child(false);
+            if let Some(request) = pending.borrow_mut().take() {
+                request.respond(None);
+            }
+        });
+    }
+    {
+        let pending = Rc::clone(&pending);
+        let revealer = prompt.revealer.clone();
+        let entry = prompt.entry.clone();
+        prompt.confirm_button.connect_clicked(move |_| {
+            let comparing = matches!(
+                pending.borrow().as_ref().map(|r| r.prompt),
+                Some(PairingPrompt::Confirmation(_) | PairingPrompt::Authorization)
+            );
+            // Yes/no prompts have nothing to type; any answer accepts
+            let answer = entry.text().trim().to_string();
+            if answer.is_empty() && !comparing {
+                return;
+            }
+            revealer.set_reveal_child(false);
+            if let Some(request) = pending.borrow_mut().take() {
+                request.respond(Some(answer));
+            }
+        });
+    }
+    {
+        let confirm_button = prompt.confirm_button.clone();
+        prompt
+            .entry
+            .connect_activate(move |_| confirm_button.emit_clicked());
+    }
+
+    let prompt = prompt.clone();
+    glib::spawn_future_local(async move {
+        let events = match bt.register_agent().await {
+            Ok(events) => events,
+            Err(e) => {
+                log::warn!("Bluetooth agent unavailable, only Just Works pairing: {e}");
+                return;
+            }
+        };
+
+        while let Ok(event) = events.recv().await {
+            // Whatever was on screen is stale now
+            if let Some(previous) = pending.borrow_mut().take() {
+                previous.respond(None);
+            }
+            match event {
+                AgentEvent::Request(request) => {
+                    show_request(&prompt, &state, &request);
+                    *pending.borrow_mut() = Some(request);
+                }
+                AgentEvent::Display { device_path, code } => {
+                    let name = device_name(&state, &device_path);
+                    prompt
+                        .title
+                        .set_text(&format!("Type this code on {name}, then press Enter"));
+                    prompt.code.set_text(&code);
+                    prompt.code.set_visible(true);
+                    prompt.entry.set_visible(false);
+                    prompt.pin_presets.set_visible(false);
+                    prompt.confirm_button.set_visible(false);
+                    prompt.cancel_button.set_label("Close");
+                    prompt.revealer.set_reveal_child(true);
+                }
+                AgentEvent::Cancel => prompt.revealer.set_reveal_child(false),
+            }
+        }
+    });
+}
+
+/// Fill the pairing section for `request` and reveal it.
+fn show_request(
+    prompt: &pairing_prompt::PairingPrompt,
+    state: &Rc<RefCell<AppState>>,
+    request: &PairingRequest,
+) {
+    let name = device_name(state, &request.device_path);
+    prompt.entry.set_text("");
+    prompt.confirm_button.set_visible(true);
+
+    let (title, input) = match request.prompt {
+        PairingPrompt::PinCode => (
+            format!("Enter the PIN for {name} — older devices usually use 0000 or 1234"),
+            Some(("PIN", gtk4::InputPurpose::Pin)),
+        ),
+        PairingPrompt::Passkey => (
+            format!("Type the passkey shown on {name}"),
+            Some(("6-digit passkey", gtk4::InputPurpose::Digits)),
+        ),
+        PairingPrompt::Confirmation(passkey) => {
+            prompt.code.set_text(&format!("{passkey:06}"));
+            (format!("Does {name} show this code?"), None)
+        }
+        PairingPrompt::Authorization => (format!("Allow {name} to pair?"), None),
+    };
+    prompt.title.set_text(&title);
+    prompt
+        .code
+        .set_visible(matches!(request.prompt, PairingPrompt::Confirmation(_)));
+    prompt.entry.set_visible(input.is_some());
+    // Legacy devices have a fixed PIN; most take the first preset
+    let legacy_pin = request.prompt == PairingPrompt::PinCode;
+    prompt.pin_presets.set_visible(legacy_pin);
+    if legacy_pin {
+        prompt.entry.set_text(pairing_prompt::LEGACY_PINS[0]);
+    }
+    match input {
+        Some((placeholder, purpose)) => {
+            prompt.entry.set_placeholder_text(Some(placeholder));
+            prompt.entry.set_input_purpose(purpose);
+            prompt.confirm_button.set_label("Pair");
+            prompt.cancel_button.set_label("Cancel");
+        }
+        None => {
+            prompt.confirm_button.set_label("Confirm");
+            prompt.cancel_button.set_label("Reject");
+        }
+    }
+    prompt.revealer.set_reveal_child(true);
+    if input.is_some() {
+        prompt.entry.grab_focus();
+        // Typing replaces the prefilled PIN
+        prompt.entry.select_region(0, -1);
+    }
+}
+
+/// Display name of the device at `device_path`, as listed on the BT page.
+fn device_name(state: &Rc<RefCell<AppState>>, device_path: &str) -> String {
+    state
+        .borrow()
+        .bt_devices
+        .iter()
+        .find(|d| d.device_path == device_path)
+        .map(|d| d.display_name.clone())
+        .unwrap_or_else(|| "the device".to_string())
+}
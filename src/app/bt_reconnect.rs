@@ -0,0 +1,57 @@
+//! Reconnects trusted audio devices after Bluetooth is switched on.
+//!
+//! Most headphones wait for the host to reconnect rather than doing it
+//! themselves, so without this they stay disconnected until clicked.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use gtk4::glib;
+
+use crate::dbus::bluetooth_device::DeviceCategory;
+
+use super::AppState;
+use super::bt_helpers::get_bt;
+
+/// Give the adapter a moment after powering on before connecting.
+const RECONNECT_DELAY_MS: u64 = 1000;
+
+/// Connect trusted audio devices that aren't connected yet.
+///
+/// Called right after the adapter is powered on from the panel; does
+/// nothing when `bt_reconnect_audio` is off. Devices are tried one at a
+/// time, and the row updates arrive through live updates.
+pub(super) fn reconnect_trusted_audio(state: &Rc<RefCell<AppState>>) {
+    if !state.borrow().config.bt_reconnect_audio {
+        return;
+    }
+    let Some(bt) = get_bt(state) else {
+        return;
+    };
+
+    glib::spawn_future_local(async move {
+        glib::timeout_future(Duration::from_millis(RECONNECT_DELAY_MS)).await;
+        let devices = match bt.get_devices().await {
+            Ok(devices) => devices,
+            Err(e) => {
+                log::warn!("Auto-reconnect: failed to list devices: {e}");
+                return;
+            }
+        };
+
+        for device in devices.iter().filter(|d| {
+            d.paired
+                && d.trusted
+                && !d.blocked
+                && !d.connected
+                && d.category == DeviceCategory::Audio
+        }) {
+            log::info!("Auto-reconnecting {}", device.display_name);
+            if let Err(e) = bt.connect_device(&device.device_path).await {
+                // Usually just switched off or out of range
+                log::debug!("Auto-reconnect of {} failed: {e}", device.display_name);
+            }
+        }
+    });
+}
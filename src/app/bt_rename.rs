@@ -0,0 +1,121 @@
+//! Device rename — sets the BlueZ alias from the inline entry on the BT page.
+//!
+//! An empty name clears the alias, so BlueZ falls back to the device's own name.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+
+use crate::ui::device_rename::DeviceRename;
+
+use super::AppState;
+use super::bt_helpers::{get_bt, refresh_bt_list};
+
+/// Opens the rename section for a device and applies the new alias.
+#[derive(Clone)]
+pub(super) struct DeviceRenamer {
+    prompt: DeviceRename,
+    /// Device path being renamed while the section is open
+    target: Rc<RefCell<Option<String>>>,
+}
+
+impl DeviceRenamer {
+    /// Wire the rename section's buttons.
+    pub(super) fn new(
+        prompt: &DeviceRename,
+        state: &Rc<RefCell<AppState>>,
+        list_box: &gtk4::ListBox,
+        status: &gtk4::Label,
+    ) -> Self {
+        let renamer = Self {
+            prompt: prompt.clone(),
+            target: Rc::new(RefCell::new(None)),
+        };
+
+        {
+            let target = Rc::clone(&renamer.target);
+            let revealer = prompt.revealer.clone();
+            prompt.cancel_button.connect_clicked(move |_| {
+                revealer.set_reveal_child(false);
+                target.borrow_mut().take();
+            });
+        }
+        {
+            let target = Rc::clone(&renamer.target);
+            let revealer = prompt.revealer.clone();
+            let entry = prompt.entry.clone();
+            let state = Rc::clone(state);
+            let list_box = list_box.clone();
+            let status = status.clone();
+            prompt.save_button.connect_clicked(move |_| {
+                let Some(device_path) = target.borrow_mut().take() else {
+                    return;
+                };
+                let alias = entry.text().trim().to_string();
+                revealer.set_reveal_child(false);
+
+                let Some(bt) = get_bt(&state) else {
+                    return;
+                };
+                let state = Rc::clone(&state);
+                let list_box = list_box.clone();
+                let status = status.clone();
+                glib::spawn_future_local(async move {
+                    match bt.set_alias(&device_path, &alias).await {
+                        Ok(()) if alias.is_empty() => status.set_text("Device name reset"),
+                        Ok(()) => status.set_text(&format!("Renamed to {alias}")),
+                        Err(e) => {
+                            log::error!("BT rename failed: {e}");
+                            status.set_text("Rename failed");
+                        }
+                    }
+                    refresh_bt_list(&state, &list_box, &status).await;
+                });
+            });
+        }
+        {
+            let save_button = prompt.save_button.clone();
+            prompt
+                .entry
+                .connect_activate(move |_| save_button.emit_clicked());
+        }
+
+        renamer
+    }
+
+    /// Open the section for the device at `device_path`.
+    fn open(&self, device_path: String, current_name: &str) {
+        self.prompt
+            .title
+            .set_text(&format!("Rename {current_name}"));
+        self.prompt.entry.set_text(current_name);
+        self.prompt
+            .entry
+            .set_placeholder_text(Some("Leave empty to use the device's name"));
+        *self.target.borrow_mut() = Some(device_path);
+        self.prompt.revealer.set_reveal_child(true);
+        self.prompt.entry.grab_focus();
+        self.prompt.entry.select_region(0, -1);
+    }
+}
+
+/// Build the callback behind the row menu's "Rename…".
+pub(super) fn build_rename_callback(state: &Rc<RefCell<AppState>>) -> Rc<dyn Fn(String)> {
+    let state = Rc::clone(state);
+    Rc::new(move |device_path| {
+        let st = state.borrow();
+        let Some(renamer) = st.bt_renamer.clone() else {
+            return;
+        };
+        let current_name = st
+            .bt_devices
+            .iter()
+            .find(|d| d.device_path == device_path)
+            .map(|d| d.display_name.clone())
+            .unwrap_or_default();
+        drop(st);
+        renamer.open(device_path, &current_name);
+    })
+}
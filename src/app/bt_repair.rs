@@ -0,0 +1,150 @@
+//! "Remove and re-pair" — one-click recovery for a broken pairing.
+//!
+//! A device that was reset or paired with another host keeps failing with
+//! the old keys. The fix is always the same sequence: remove it, find it
+//! again, pair, trust and connect; this runs it with status updates.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use gtk4::glib;
+
+use crate::dbus::bluetooth_device::BluetoothDevice;
+use crate::dbus::bluetooth_manager::BluetoothManager;
+
+use super::AppState;
+use super::bt_helpers::{pair_with_timeout, refresh_bt_list};
+
+/// How long to wait for the removed device to show up again.
+const REDISCOVER_TIMEOUT_SECS: u64 = 30;
+/// How often the device list is checked while waiting.
+const REDISCOVER_POLL_MS: u64 = 1000;
+
+/// Build the callback behind the row menu's "Remove and re-pair".
+pub(super) fn build_repair_callback(
+    state: &Rc<RefCell<AppState>>,
+    list_box: &gtk4::ListBox,
+    status: &gtk4::Label,
+    bt: &BluetoothManager,
+) -> Rc<dyn Fn(String)> {
+    let state = Rc::clone(state);
+    let list_box = list_box.clone();
+    let status = status.clone();
+    let bt = bt.clone();
+    Rc::new(move |device_path| {
+        let Some(device) = state
+            .borrow()
+            .bt_devices
+            .iter()
+            .find(|d| d.device_path == device_path)
+            .cloned()
+        else {
+            return;
+        };
+        let state = Rc::clone(&state);
+        let list_box = list_box.clone();
+        let status = status.clone();
+        let bt = bt.clone();
+        glib::spawn_future_local(async move {
+            let result = repair(&state, &list_box, &status, &bt, &device).await;
+            if let Some(path) = &result {
+                state.borrow_mut().bt_pending.remove(path);
+            }
+            refresh_bt_list(&state, &list_box, &status).await;
+        });
+    })
+}
+
+/// Run the remove → rediscover → pair → trust → connect sequence.
+///
+/// Reports progress and failures on `status`. Returns the device path the
+/// last pending label was set on, if any.
+async fn repair(
+    state: &Rc<RefCell<AppState>>,
+    list_box: &gtk4::ListBox,
+    status: &gtk4::Label,
+    bt: &BluetoothManager,
+    device: &BluetoothDevice,
+) -> Option<String> {
+    let address = device.address.as_str();
+    let name = device.display_name.as_str();
+    status.set_text(&format!("Removing {name}..."));
+    if let Err(e) = bt.remove_device(&device.device_path).await {
+        log::error!("Re-pair: removing {address} failed: {e}");
+        status.set_text(&format!("Failed to remove {name}"));
+        return None;
+    }
+
+    status.set_text(&format!("Searching for {name} — put it in pairing mode"));
+    let Some(path) = wait_for_device(bt, address).await else {
+        log::warn!("Re-pair: {address} did not reappear");
+        status.set_text(&format!("{name} not found — is it in pairing mode?"));
+        return None;
+    };
+
+    status.set_text(&format!("Pairing with {name}..."));
+    state
+        .borrow_mut()
+        .bt_pending
+        .insert(path.clone(), "Pairing".to_string());
+    refresh_bt_list(state, list_box, status).await;
+    match pair_with_timeout(state, bt, &path).await {
+        Some(Ok(())) => {}
+        Some(Err(e)) => {
+            log::error!("Re-pair: pairing with {address} failed: {e}");
+            status.set_text("Pairing failed");
+            return Some(path);
+        }
+        None => {
+            log::warn!("Re-pair: pairing with {address} timed out");
+            status.set_text(&format!("Pairing with {name} timed out"));
+            return Some(path);
+        }
+    }
+
+    let _ = bt.trust_device(&path, true).await;
+    status.set_text(&format!("Connecting to {name}..."));
+    state
+        .borrow_mut()
+        .bt_pending
+        .insert(path.clone(), "Connecting".to_string());
+    refresh_bt_list(state, list_box, status).await;
+    match bt.connect_device(&path).await {
+        Ok(()) => status.set_text(&format!("Re-paired with {name}")),
+        Err(e) => {
+            log::error!("Re-pair: connecting to {address} failed: {e}");
+            status.set_text(&format!("Paired with {name}, but connecting failed"));
+        }
+    }
+    Some(path)
+}
+
+/// Scan until `address` shows up again and return its device path.
+///
+/// Discovery is restarted whenever it stopped, since a background scan
+/// burst ending stops it for this client too.
+async fn wait_for_device(bt: &BluetoothManager, address: &str) -> Option<String> {
+    let deadline = Instant::now() + Duration::from_secs(REDISCOVER_TIMEOUT_SECS);
+    let mut started_discovery = false;
+    let mut found = None;
+    while found.is_none() && Instant::now() < deadline {
+        if !bt.is_discovering().await.unwrap_or(false) {
+            match bt.start_discovery().await {
+                Ok(()) => started_discovery = true,
+                Err(e) => log::warn!("Re-pair: BT discovery failed: {e}"),
+            }
+        }
+        glib::timeout_future(Duration::from_millis(REDISCOVER_POLL_MS)).await;
+        found = bt
+            .get_devices()
+            .await
+            .ok()
+            .and_then(|devices| devices.into_iter().find(|d| d.address == address))
+            .map(|d| d.device_path);
+    }
+    if started_discovery && let Err(e) = bt.stop_discovery().await {
+        log::warn!("BT discovery stop failed: {e}");
+    }
+    found
+}
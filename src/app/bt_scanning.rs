@@ -167,7 +167,12 @@ pub(super) async fn resume_bt_background_tasks(
 }
 
 /// Tell BlueZ to stop discovery (best-effort, logs a warning on failure).
+///
+/// Also ends a running scan window, so the next show can start a fresh one
+/// instead of waiting for it to time out.
 pub(super) async fn stop_bt_discovery(state: Rc<RefCell<AppState>>) {
+    // Dropping the sender wakes the scan burst
+    state.borrow_mut().bt_scan_abort.take();
     if let Some(bt) = get_bt(&state) {
         if let Err(e) = bt.stop_discovery().await {
             log::warn!("BT discovery stop failed: {e}");
@@ -238,7 +243,9 @@ pub(super) async fn run_bt_scan_burst(
     struct ScanGuard(Rc<RefCell<AppState>>);
     impl Drop for ScanGuard {
         fn drop(&mut self) {
-            self.0.borrow_mut().bt_scan_in_progress = false;
+            let mut st = self.0.borrow_mut();
+            st.bt_scan_in_progress = false;
+            st.bt_scan_abort = None;
         }
     }
 
@@ -261,6 +268,10 @@ pub(super) async fn run_bt_scan_burst(
         st.bt_scan_in_progress = true;
     }
     let _guard = ScanGuard(Rc::clone(&state));
+    // Created before discovery starts so a hide during start_discovery()
+    // still ends the window straight away
+    let (abort_tx, abort_rx) = async_channel::bounded::<()>(1);
+    state.borrow_mut().bt_scan_abort = Some(abort_tx);
 
     let bt = match get_bt(&state) {
         Some(bt) => bt,
@@ -307,9 +318,14 @@ pub(super) async fn run_bt_scan_burst(
         }
     }
 
-    glib::timeout_future(std::time::Duration::from_millis(scan_window_ms)).await;
+    let window = glib::timeout_future(std::time::Duration::from_millis(scan_window_ms));
+    let aborted = std::pin::pin!(abort_rx.recv());
+    let aborted = matches!(
+        futures_util::future::select(window, aborted).await,
+        futures_util::future::Either::Right(_)
+    );
 
-    if bt_tab.is_active() {
+    if bt_tab.is_active() && !aborted {
         refresh_bt_list(&state, &list_box, &status).await;
     }
 
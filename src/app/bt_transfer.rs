@@ -0,0 +1,356 @@
+//! Bluetooth file transfer — sending and receiving over OBEX.
+//!
+//! Sending: one transfer at a time; the progress row on the Bluetooth page
+//! shows it and its Cancel button aborts it.
+//!
+//! Receiving: incoming pushes are offered as a notification (Accept / Decline)
+//! since they usually arrive while the panel is hidden; accepted files are
+//! moved from obexd's folder to ~/Downloads once complete.
+
+use std::cell::{Cell, RefCell};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::Duration;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+
+use crate::dbus::obex::{ObexManager, TransferStatus};
+use crate::dbus::obex_agent::PushRequest;
+use crate::ui::transfer_row::TransferRow;
+
+use super::{AppState, get_notifier};
+
+/// How often the progress row is updated while a file is sent.
+const TRANSFER_POLL_MS: u64 = 500;
+
+/// Sends files to devices and drives the transfer progress row.
+#[derive(Clone)]
+pub(super) struct FileSender {
+    row: TransferRow,
+    status: gtk4::Label,
+    busy: Rc<Cell<bool>>,
+    cancel_requested: Rc<Cell<bool>>,
+}
+
+impl FileSender {
+    /// Wire the progress row's Cancel button.
+    pub(super) fn new(row: &TransferRow, status: &gtk4::Label) -> Self {
+        let sender = Self {
+            row: row.clone(),
+            status: status.clone(),
+            busy: Rc::new(Cell::new(false)),
+            cancel_requested: Rc::new(Cell::new(false)),
+        };
+        {
+            let busy = Rc::clone(&sender.busy);
+            let cancel_requested = Rc::clone(&sender.cancel_requested);
+            let revealer = row.revealer.clone();
+            row.cancel_button.connect_clicked(move |_| {
+                if busy.get() {
+                    cancel_requested.set(true);
+                } else {
+                    revealer.set_reveal_child(false);
+                }
+            });
+        }
+        sender
+    }
+
+    /// Ask for a file and push it to the device at `address`.
+    pub(super) fn choose_and_send(&self, address: String, device_name: String) {
+        if self.busy.get() {
+            self.status.set_text("A file transfer is already running");
+            return;
+        }
+
+        let chooser = gtk4::FileDialog::builder()
+            .title(format!("Send File to {device_name}"))
+            .accept_label("Send")
+            .build();
+
+        let sender = self.clone();
+        glib::spawn_future_local(async move {
+            match chooser.open_future(None::<&gtk4::Window>).await {
+                Ok(file) => match file.path() {
+                    Some(path) => sender.send(&address, &device_name, path).await,
+                    None => sender
+                        .status
+                        .set_text("Send failed: selected file path is unavailable"),
+                },
+                Err(e) => {
+                    // User cancel should be quiet.
+                    if !e.matches(gtk4::DialogError::Dismissed) {
+                        sender.status.set_text(&format!("Send failed: {e}"));
+                    }
+                }
+            }
+        });
+    }
+
+    /// Push `path` and follow the transfer until it ends.
+    async fn send(&self, address: &str, device_name: &str, path: PathBuf) {
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+
+        self.busy.set(true);
+        self.cancel_requested.set(false);
+        self.row.cancel_button.set_label("Cancel");
+        self.row
+            .title
+            .set_text(&format!("Waiting for {device_name} to accept {file_name}"));
+        self.row.progress.set_fraction(0.0);
+        self.row.revealer.set_reveal_child(true);
+
+        let transfer = match ObexManager::new().await {
+            Ok(obex) => obex.send_file(address, &path).await,
+            Err(e) => Err(e),
+        };
+        let transfer = match transfer {
+            Ok(transfer) => transfer,
+            Err(e) => {
+                log::error!("OBEX send to {address} failed: {e}");
+                self.finish(&format!("Couldn't send {file_name}"));
+                return;
+            }
+        };
+
+        let outcome = loop {
+            if self.cancel_requested.get() {
+                if let Err(e) = transfer.cancel().await {
+                    log::warn!("OBEX cancel failed: {e}");
+                }
+                break None;
+            }
+            match transfer.progress().await {
+                Ok(progress) => {
+                    if progress.status == TransferStatus::Active {
+                        self.row
+                            .title
+                            .set_text(&format!("Sending {file_name} to {device_name}"));
+                    }
+                    self.row.progress.set_fraction(progress.fraction());
+                    if progress.status.is_finished() {
+                        break Some(progress.status);
+                    }
+                }
+                Err(e) => {
+                    log::error!("OBEX transfer to {address} lost: {e}");
+                    break Some(TransferStatus::Error);
+                }
+            }
+            glib::timeout_future(Duration::from_millis(TRANSFER_POLL_MS)).await;
+        };
+        transfer.close().await;
+
+        match outcome {
+            Some(TransferStatus::Complete) => {
+                self.finish(&format!("Sent {file_name} to {device_name}"));
+            }
+            Some(_) => self.finish(&format!("Couldn't send {file_name} to {device_name}")),
+            None => self.finish("File transfer cancelled"),
+        }
+    }
+
+    /// Report the outcome and hide the progress row.
+    fn finish(&self, message: &str) {
+        self.busy.set(false);
+        self.cancel_requested.set(false);
+        self.status.set_text(message);
+        self.row.revealer.set_reveal_child(false);
+    }
+}
+
+/// Build the callback behind the row menu's "Send file…".
+pub(super) fn build_send_file_callback(state: &Rc<RefCell<AppState>>) -> Rc<dyn Fn(String)> {
+    let state = Rc::clone(state);
+    Rc::new(move |address| {
+        let st = state.borrow();
+        let Some(sender) = st.bt_file_sender.clone() else {
+            return;
+        };
+        let device_name = st
+            .bt_devices
+            .iter()
+            .find(|d| d.address == address)
+            .map(|d| d.display_name.clone())
+            .unwrap_or_else(|| address.clone());
+        drop(st);
+        sender.choose_and_send(address, device_name);
+    })
+}
+
+/// Register the Object Push agent and offer incoming files to the user.
+pub(super) fn setup_file_receiver(state: Rc<RefCell<AppState>>) {
+    glib::spawn_future_local(async move {
+        let registered = match ObexManager::new().await {
+            Ok(obex) => obex.register_agent().await.map(|requests| (obex, requests)),
+            Err(e) => Err(e),
+        };
+        let (obex, requests) = match registered {
+            Ok(registered) => registered,
+            Err(e) => {
+                log::warn!("Receiving files over Bluetooth unavailable (is obexd running?): {e}");
+                return;
+            }
+        };
+
+        while let Ok(request) = requests.recv().await {
+            let device_name = request
+                .address
+                .as_deref()
+                .and_then(|address| {
+                    state
+                        .borrow()
+                        .bt_devices
+                        .iter()
+                        .find(|d| d.address == address)
+                        .map(|d| d.display_name.clone())
+                })
+                .unwrap_or_else(|| "A Bluetooth device".to_string());
+            glib::spawn_future_local(receive_file(
+                Rc::clone(&state),
+                obex.clone(),
+                device_name,
+                request,
+            ));
+        }
+    });
+}
+
+/// Ask about one incoming file and, if accepted, follow it to ~/Downloads.
+async fn receive_file(
+    state: Rc<RefCell<AppState>>,
+    obex: ObexManager,
+    device_name: String,
+    request: PushRequest,
+) {
+    let notifier = match get_notifier(&state).await {
+        Ok(notifier) => notifier,
+        Err(e) => {
+            log::warn!("Declining incoming file, notifications unavailable: {e}");
+            request.respond(false);
+            return;
+        }
+    };
+
+    let file_name = request.file_name.clone();
+    let mut body = format!("{device_name} wants to send {file_name}");
+    if request.size > 0 {
+        body.push_str(&format!(" ({})", format_size(request.size)));
+    }
+    let answer = notifier
+        .notify_with_actions(
+            "bluetooth-active-symbolic",
+            "Incoming file",
+            &body,
+            &[("accept", "Accept"), ("decline", "Decline")],
+        )
+        .await;
+    if !matches!(&answer, Ok(Some(key)) if key == "accept") {
+        log::info!("Declined incoming file {file_name} from {device_name}");
+        request.respond(false);
+        return;
+    }
+
+    // Watch before accepting: obexd drops the object soon after it finishes
+    let transfer = match obex.incoming_transfer(&request.transfer_path).await {
+        Ok(transfer) => transfer,
+        Err(e) => {
+            log::error!("Can't follow incoming transfer: {e}");
+            request.respond(false);
+            return;
+        }
+    };
+    request.respond(true);
+
+    let status = loop {
+        glib::timeout_future(Duration::from_millis(TRANSFER_POLL_MS)).await;
+        match transfer.progress().await {
+            Ok(progress) if progress.status.is_finished() => break progress.status,
+            Ok(_) => {}
+            Err(e) => {
+                log::error!("Incoming transfer of {file_name} lost: {e}");
+                break TransferStatus::Error;
+            }
+        }
+    };
+
+    let saved = match (status, transfer.filename().await) {
+        (TransferStatus::Complete, Ok(source)) => move_to_downloads(Path::new(&source)),
+        (TransferStatus::Complete, Err(e)) => Err(e.to_string()),
+        _ => Err("transfer did not complete".to_string()),
+    };
+    let (summary, body) = match saved {
+        Ok(dest) => {
+            log::info!(
+                "Received {file_name} from {device_name} into {}",
+                dest.display()
+            );
+            ("File received", format!("Saved {}", dest.display()))
+        }
+        Err(e) => {
+            log::error!("Receiving {file_name} from {device_name} failed: {e}");
+            (
+                "File transfer failed",
+                format!("{file_name} from {device_name} was not received"),
+            )
+        }
+    };
+    if let Err(e) = notifier
+        .notify("bluetooth-active-symbolic", summary, &body)
+        .await
+    {
+        log::warn!("Failed to notify about received file: {e}");
+    }
+}
+
+/// Move a received file into the Downloads folder without overwriting.
+fn move_to_downloads(source: &Path) -> Result<PathBuf, String> {
+    let dir = glib::user_special_dir(glib::UserDirectory::Downloads)
+        .or_else(|| {
+            std::env::var("HOME")
+                .ok()
+                .map(|h| PathBuf::from(h).join("Downloads"))
+        })
+        .ok_or("No Downloads folder")?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let name = source.file_name().ok_or("Received file has no name")?;
+    let mut dest = dir.join(name);
+    let stem = Path::new(name)
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = Path::new(name)
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    let mut n = 1;
+    while dest.exists() {
+        dest = dir.join(format!("{stem} ({n}){extension}"));
+        n += 1;
+    }
+
+    // obexd's folder may sit on another filesystem; fall back to copying
+    if std::fs::rename(source, &dest).is_err() {
+        std::fs::copy(source, &dest).map_err(|e| e.to_string())?;
+        let _ = std::fs::remove_file(source);
+    }
+    Ok(dest)
+}
+
+/// Human-readable file size, e.g. "3.4 MB".
+fn format_size(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    let bytes = bytes as f64;
+    if bytes >= KB * KB {
+        format!("{:.1} MB", bytes / (KB * KB))
+    } else if bytes >= KB {
+        format!("{:.0} KB", bytes / KB)
+    } else {
+        format!("{bytes} B")
+    }
+}
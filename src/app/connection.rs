@@ -2,15 +2,25 @@
 
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::time::Instant;
 
 use gtk4::glib;
 use gtk4::prelude::*;
 
-use crate::dbus::access_point::SecurityType;
-use crate::ui::network_list;
+use crate::dbus::access_point::{Network, SecurityType};
+use crate::dbus::network_manager::ActivationOutcome;
+use crate::dbus::polkit;
+use crate::ui::{hidden_dialog, network_list};
+use crate::state::AppStateStore;
 use crate::ui::window::PanelWidgets;
 
-use super::{AppState, get_wifi, refresh_list};
+use super::{AppState, PasswordDraft, get_wifi, refresh_list};
+
+/// How long to follow an activation before leaving it to live updates.
+const WIFI_ACTIVATION_TIMEOUT_SECS: u64 = 30;
+
+/// How long a password typed before hiding the panel is kept for the next show.
+const PASSWORD_DRAFT_TTL_SECS: u64 = 60;
 
 /// Wire the WiFi toggle switch (only when WiFi tab is active).
 pub(super) fn setup_wifi_toggle(widgets: &PanelWidgets, state: Rc<RefCell<AppState>>) {
@@ -38,16 +48,16 @@ pub(super) fn setup_wifi_toggle(widgets: &PanelWidgets, state: Rc<RefCell<AppSta
                     Ok(_) => {
                         if enabled {
                             status.set_text("WiFi enabled");
+                            // Give the radio a moment to come up before scanning
                             glib::timeout_future(std::time::Duration::from_millis(2000)).await;
-                            let _ = wifi.request_scan().await;
-                            glib::timeout_future(std::time::Duration::from_millis(1500)).await;
+                            let _ = wifi.request_scan_and_wait(super::scanning::scan_timeout()).await;
                             refresh_list(&state, &list_box, &status).await;
                         } else {
                             status.set_text("WiFi disabled");
-                            let config = crate::config::Config::load();
+                            let config = state.borrow().config.clone();
                             let wifi = get_wifi(&state);
                             let empty_pending = std::collections::HashMap::new();
-                            let on_forget = std::rc::Rc::new(|_ssid: String| {});
+                            let on_forget = Rc::new(|_ssid: String| {});
                             let row_ssids = network_list::populate_network_list(
                                 &list_box,
                                 &[],
@@ -55,14 +65,23 @@ pub(super) fn setup_wifi_toggle(widgets: &PanelWidgets, state: Rc<RefCell<AppSta
                                 &wifi,
                                 &status,
                                 &empty_pending,
+                                &empty_pending,
                                 on_forget,
+                                Rc::new(|_ssid: String, _bssid: String| {}),
+                                Rc::new(|_ssid: String| {}),
+                                Rc::new(|_ssid: String| {}),
+                                Rc::new(|_ssid: String| {}),
                             );
                             state.borrow_mut().wifi_row_ssids = row_ssids;
                         }
                     }
                     Err(e) => {
                         log::error!("WiFi toggle failed: {e}");
-                        status.set_text("Toggle failed");
+                        status.set_text(if polkit::is_not_authorized(&e) {
+                            polkit::NOT_AUTHORIZED
+                        } else {
+                            "Toggle failed"
+                        });
                     }
                 }
             });
@@ -71,19 +90,23 @@ pub(super) fn setup_wifi_toggle(widgets: &PanelWidgets, state: Rc<RefCell<AppSta
         });
 }
 
-/// Wire network row clicks to connect or show password dialog.
+/// Wire network row clicks to connect, show details, or show password dialog.
 pub(super) fn setup_network_click(widgets: &PanelWidgets, state: Rc<RefCell<AppState>>) {
     let revealer = widgets.password_revealer.clone();
     let entry = widgets.password_entry.clone();
     let error_label = widgets.error_label.clone();
     let list_box = widgets.network_list_box.clone();
     let status = widgets.status_label.clone();
+    let open_warning = widgets.open_warning.clone();
+    let details = widgets.network_details.clone();
 
     widgets
         .network_list_box
         .connect_row_activated(move |_list, row| {
             let index = row.index() as usize;
             let state = Rc::clone(&state);
+            let open_warning = open_warning.clone();
+            let details = details.clone();
             let revealer = revealer.clone();
             let entry = entry.clone();
             let error_label = error_label.clone();
@@ -106,95 +129,41 @@ pub(super) fn setup_network_click(widgets: &PanelWidgets, state: Rc<RefCell<AppS
                     return;
                 };
 
-                let wifi = get_wifi(&state);
-
-                let set_pending = |state: &Rc<RefCell<AppState>>,
-                                   status: &gtk4::Label,
-                                   list_box: &gtk4::ListBox,
-                                   ssid: &str,
-                                   pending_label: &str,
-                                   status_prefix: &str| {
-                    {
-                        let mut st = state.borrow_mut();
-                        st.wifi_pending
-                            .insert(ssid.to_string(), pending_label.to_string());
-                    }
-                    status.set_text(&format!("{} {}...", status_prefix, ssid));
-                    glib::spawn_future_local({
-                        let state = Rc::clone(state);
-                        let list_box = list_box.clone();
-                        let status = status.clone();
-                        async move {
-                            refresh_list(&state, &list_box, &status).await;
-                        }
-                    });
-                };
-
-                let clear_pending = |state: &Rc<RefCell<AppState>>,
-                                     list_box: &gtk4::ListBox,
-                                     status: &gtk4::Label,
-                                     ssid: &str| {
-                    let mut st = state.borrow_mut();
-                    st.wifi_pending.remove(ssid);
-                    glib::spawn_future_local({
-                        let state = Rc::clone(state);
-                        let list_box = list_box.clone();
-                        let status = status.clone();
-                        async move {
-                            refresh_list(&state, &list_box, &status).await;
-                        }
-                    });
-                };
-
-                if network.is_connected {
-                    // Disconnect
-                    set_pending(
-                        &state,
-                        &status,
-                        &list_box,
-                        &network.ssid,
-                        "Disconnecting",
-                        "Disconnecting from",
-                    );
-                    match wifi.disconnect().await {
-                        Ok(_) => {
-                            glib::timeout_future(std::time::Duration::from_millis(500)).await;
-                            clear_pending(&state, &list_box, &status, &network.ssid);
-                            refresh_list(&state, &list_box, &status).await;
-                        }
-                        Err(e) => {
-                            log::error!("Disconnect failed: {e}");
-                            status.set_text("Disconnect failed");
-                            clear_pending(&state, &list_box, &status, &network.ssid);
-                        }
-                    }
+                if network.is_hidden && !network.is_connected {
+                    // The name has to be typed in; NM probes for it directly
+                    prompt_hidden_network(&state, &list_box, &status);
+                } else if network.is_connected {
+                    // Details only; disconnecting lives in the row menu
+                    revealer.set_reveal_child(false);
+                    open_warning.revealer.set_reveal_child(false);
+                    super::network_details::show_details(&state, &details, &network).await;
+                } else if needs_open_warning(&state, &network) {
+                    // Unencrypted and never joined before: ask first
+                    state.borrow_mut().selected_ssid = Some(network.ssid.clone());
+                    revealer.set_reveal_child(false);
+                    details.revealer.set_reveal_child(false);
+                    open_warning
+                        .title
+                        .set_text(&format!("{} is an open network", network.ssid));
+                    open_warning.dont_ask.set_active(false);
+                    open_warning.revealer.set_reveal_child(true);
                 } else if network.is_saved || network.security == SecurityType::Open {
                     // Connect directly (no password needed)
-                    set_pending(
-                        &state,
-                        &status,
-                        &list_box,
-                        &network.ssid,
-                        "Connecting",
-                        "Connecting to",
-                    );
-                    match wifi.connect_to_network(&network, None).await {
-                        Ok(_) => {
-                            glib::timeout_future(std::time::Duration::from_millis(2000)).await;
-                            clear_pending(&state, &list_box, &status, &network.ssid);
-                            refresh_list(&state, &list_box, &status).await;
-                        }
-                        Err(e) => {
-                            log::error!("Connect failed: {e}");
-                            status.set_text(&format!("Failed: {}", e));
-                            clear_pending(&state, &list_box, &status, &network.ssid);
-                        }
-                    }
+                    details.revealer.set_reveal_child(false);
+                    connect_without_password(&state, &list_box, &status, &network).await;
                 } else {
                     // Show password dialog
                     state.borrow_mut().selected_ssid = Some(network.ssid.clone());
+                    open_warning.revealer.set_reveal_child(false);
+                    details.revealer.set_reveal_child(false);
                     error_label.set_visible(false);
                     entry.set_text("");
+                    let placeholder = match network.security {
+                        SecurityType::WEP => "WEP key (legacy, insecure)",
+                        _ => "Enter password",
+                    };
+                    entry.set_placeholder_text(Some(placeholder));
+                    super::guest::apply_password_reveal(&entry);
                     revealer.set_reveal_child(true);
                     entry.grab_focus();
                 }
@@ -202,6 +171,45 @@ pub(super) fn setup_network_click(widgets: &PanelWidgets, state: Rc<RefCell<AppS
         });
 }
 
+/// Row callback that disconnects from the connected network.
+pub(super) fn disconnect_callback(
+    state: &Rc<RefCell<AppState>>,
+    list_box: &gtk4::ListBox,
+    status: &gtk4::Label,
+) -> Rc<dyn Fn(String)> {
+    let state = Rc::clone(state);
+    let list_box = list_box.clone();
+    let status = status.clone();
+    Rc::new(move |ssid: String| {
+        let state = Rc::clone(&state);
+        let list_box = list_box.clone();
+        let status = status.clone();
+        glib::spawn_future_local(async move {
+            let wifi = get_wifi(&state);
+            state
+                .borrow_mut()
+                .wifi_pending
+                .insert(ssid.clone(), "Disconnecting".to_string());
+            status.set_text(&format!("Disconnecting from {}...", ssid));
+            refresh_list(&state, &list_box, &status).await;
+
+            match wifi.disconnect().await {
+                Ok(_) => {
+                    glib::timeout_future(std::time::Duration::from_millis(500)).await;
+                    state.borrow_mut().wifi_pending.remove(&ssid);
+                    refresh_list(&state, &list_box, &status).await;
+                }
+                Err(e) => {
+                    log::error!("Disconnect failed: {e}");
+                    state.borrow_mut().wifi_pending.remove(&ssid);
+                    refresh_list(&state, &list_box, &status).await;
+                    status.set_text("Disconnect failed");
+                }
+            }
+        });
+    })
+}
+
 /// Wire password dialog connect/cancel buttons and Enter key.
 pub(super) fn setup_password_actions(widgets: &PanelWidgets, state: Rc<RefCell<AppState>>) {
     let revealer = widgets.password_revealer.clone();
@@ -266,15 +274,28 @@ pub(super) fn setup_password_actions(widgets: &PanelWidgets, state: Rc<RefCell<A
                 refresh_list(&state, &list_box, &status).await;
 
                 match wifi.connect_to_network(&network, Some(&password)).await {
-                    Ok(_) => {
+                    Ok(active_path) => {
                         revealer.set_reveal_child(false);
-                        glib::timeout_future(std::time::Duration::from_millis(2000)).await;
-                        state.borrow_mut().wifi_pending.remove(&network.ssid);
+                        track_activation(&state, &list_box, &status, &network.ssid, &active_path)
+                            .await;
+                        let outcome = wifi
+                            .wait_for_activation(&active_path, activation_timeout())
+                            .await;
+                        {
+                            let mut st = state.borrow_mut();
+                            st.wifi_pending.remove(&network.ssid);
+                            st.wifi_pending_active.remove(&network.ssid);
+                        }
                         refresh_list(&state, &list_box, &status).await;
+                        report_activation(&status, &network.ssid, outcome);
                     }
                     Err(e) => {
                         log::error!("Connect with password failed: {e}");
-                        error_label.set_text("Connection failed — check password");
+                        error_label.set_text(if polkit::is_not_authorized(&e) {
+                            polkit::NOT_AUTHORIZED
+                        } else {
+                            "Connection failed — check password"
+                        });
                         error_label.set_visible(true);
                         state.borrow_mut().wifi_pending.remove(&network.ssid);
                     }
@@ -292,3 +313,224 @@ pub(super) fn setup_password_actions(widgets: &PanelWidgets, state: Rc<RefCell<A
         });
     }
 }
+
+/// Stash an open password entry when the panel is hidden.
+///
+/// The entry is cleared so the text doesn't linger in the hidden window.
+pub(super) fn stash_password_draft(
+    state: &Rc<RefCell<AppState>>,
+    revealer: &gtk4::Revealer,
+    entry: &gtk4::Entry,
+) {
+    if !revealer.reveals_child() {
+        return;
+    }
+    let mut st = state.borrow_mut();
+    if let Some(ssid) = st.selected_ssid.clone() {
+        st.password_draft = Some(PasswordDraft {
+            ssid,
+            text: entry.text().to_string(),
+            saved_at: Instant::now(),
+        });
+    }
+    entry.set_text("");
+    revealer.set_reveal_child(false);
+}
+
+/// Reopen the password entry stashed on hide, if it is recent enough
+/// and the network is still listed.
+pub(super) fn restore_password_draft(
+    state: &Rc<RefCell<AppState>>,
+    revealer: &gtk4::Revealer,
+    entry: &gtk4::Entry,
+) {
+    let mut st = state.borrow_mut();
+    let Some(draft) = st.password_draft.take() else {
+        return;
+    };
+    if draft.saved_at.elapsed() > std::time::Duration::from_secs(PASSWORD_DRAFT_TTL_SECS) {
+        log::debug!("Password draft for '{}' expired", draft.ssid);
+        return;
+    }
+    if !st.networks.iter().any(|n| n.ssid == draft.ssid) {
+        return;
+    }
+
+    st.selected_ssid = Some(draft.ssid);
+    drop(st);
+    super::guest::apply_password_reveal(entry);
+    entry.set_text(&draft.text);
+    entry.set_position(-1);
+    revealer.set_reveal_child(true);
+    entry.grab_focus();
+}
+
+/// Ask for a hidden network's SSID/password and connect to it.
+fn prompt_hidden_network(
+    state: &Rc<RefCell<AppState>>,
+    list_box: &gtk4::ListBox,
+    status: &gtk4::Label,
+) {
+    let Some(parent) = list_box.root().and_downcast::<gtk4::Window>() else {
+        return;
+    };
+
+    let state = Rc::clone(state);
+    let list_box = list_box.clone();
+    let status = status.clone();
+    hidden_dialog::show_hidden_dialog(&parent, move |ssid, password| {
+        let state = Rc::clone(&state);
+        let list_box = list_box.clone();
+        let status = status.clone();
+        glib::spawn_future_local(async move {
+            let wifi = get_wifi(&state);
+            status.set_text(&format!("Connecting to {ssid}..."));
+            match wifi.connect_hidden(&ssid, password.as_deref()).await {
+                Ok(active_path) => {
+                    let outcome = wifi
+                        .wait_for_activation(&active_path, activation_timeout())
+                        .await;
+                    refresh_list(&state, &list_box, &status).await;
+                    report_activation(&status, &ssid, outcome);
+                }
+                Err(e) => {
+                    log::error!("Hidden network connect failed: {e}");
+                    status.set_text(&format!("Failed: {}", e));
+                }
+            }
+        });
+    });
+}
+
+/// Whether joining `network` should go through the unencrypted warning.
+fn needs_open_warning(state: &Rc<RefCell<AppState>>, network: &Network) -> bool {
+    network.security == SecurityType::Open
+        && !network.is_owe
+        && !network.is_saved
+        && state.borrow().config.warn_open_networks
+        && !AppStateStore::load().skip_open_network_warning
+}
+
+/// Wire the "Connect anyway" / "Cancel" buttons of the open network warning.
+pub(super) fn setup_open_warning(widgets: &PanelWidgets, state: Rc<RefCell<AppState>>) {
+    let open_warning = widgets.open_warning.clone();
+    let list_box = widgets.network_list_box.clone();
+    let status = widgets.status_label.clone();
+
+    {
+        let revealer = open_warning.revealer.clone();
+        let state = Rc::clone(&state);
+        open_warning.cancel_button.connect_clicked(move |_| {
+            revealer.set_reveal_child(false);
+            state.borrow_mut().selected_ssid = None;
+        });
+    }
+
+    let revealer = open_warning.revealer.clone();
+    let dont_ask = open_warning.dont_ask.clone();
+    open_warning.connect_button.connect_clicked(move |_| {
+        revealer.set_reveal_child(false);
+        if dont_ask.is_active() {
+            log::info!("Open network warning disabled");
+            AppStateStore::update(|s| s.skip_open_network_warning = true);
+        }
+
+        let network = {
+            let st = state.borrow();
+            st.selected_ssid
+                .as_ref()
+                .and_then(|ssid| st.networks.iter().find(|n| n.ssid == *ssid).cloned())
+        };
+        let Some(network) = network else {
+            return;
+        };
+
+        let state = Rc::clone(&state);
+        let list_box = list_box.clone();
+        let status = status.clone();
+        glib::spawn_future_local(async move {
+            connect_without_password(&state, &list_box, &status, &network).await;
+        });
+    });
+}
+
+/// Activate a saved or open network and follow it until NM settles.
+pub(super) async fn connect_without_password(
+    state: &Rc<RefCell<AppState>>,
+    list_box: &gtk4::ListBox,
+    status: &gtk4::Label,
+    network: &Network,
+) {
+    let wifi = get_wifi(state);
+    state
+        .borrow_mut()
+        .wifi_pending
+        .insert(network.ssid.clone(), "Connecting".to_string());
+    status.set_text(&format!("Connecting to {}...", network.ssid));
+    glib::spawn_future_local({
+        let state = Rc::clone(state);
+        let list_box = list_box.clone();
+        let status = status.clone();
+        async move {
+            refresh_list(&state, &list_box, &status).await;
+        }
+    });
+
+    match wifi.connect_to_network(network, None).await {
+        Ok(active_path) => {
+            track_activation(state, list_box, status, &network.ssid, &active_path).await;
+            let outcome = wifi
+                .wait_for_activation(&active_path, activation_timeout())
+                .await;
+            {
+                let mut st = state.borrow_mut();
+                st.wifi_pending.remove(&network.ssid);
+                st.wifi_pending_active.remove(&network.ssid);
+            }
+            refresh_list(state, list_box, status).await;
+            report_activation(status, &network.ssid, outcome);
+        }
+        Err(e) => {
+            log::error!("Connect failed: {e}");
+            status.set_text(&format!("Failed: {}", e));
+            state.borrow_mut().wifi_pending.remove(&network.ssid);
+            refresh_list(state, list_box, status).await;
+        }
+    }
+}
+
+/// Remember the active path of an in-flight connect so its row can cancel it.
+async fn track_activation(
+    state: &Rc<RefCell<AppState>>,
+    list_box: &gtk4::ListBox,
+    status: &gtk4::Label,
+    ssid: &str,
+    active_path: &str,
+) {
+    state
+        .borrow_mut()
+        .wifi_pending_active
+        .insert(ssid.to_string(), active_path.to_string());
+    refresh_list(state, list_box, status).await;
+}
+
+/// Timer passed to `WifiManager::wait_for_activation`.
+fn activation_timeout() -> impl std::future::Future<Output = ()> {
+    glib::timeout_future(std::time::Duration::from_secs(WIFI_ACTIVATION_TIMEOUT_SECS))
+}
+
+/// Show the result of an activation in the status label once NM settles.
+fn report_activation(
+    status: &gtk4::Label,
+    ssid: &str,
+    outcome: zbus::Result<ActivationOutcome>,
+) {
+    match outcome {
+        Ok(ActivationOutcome::Activated) => status.set_text(&format!("Connected to {ssid}")),
+        Ok(ActivationOutcome::Failed(reason)) => status.set_text(&format!("Failed: {reason}")),
+        Ok(ActivationOutcome::TimedOut) => {
+            status.set_text(&format!("Still connecting to {ssid}..."));
+        }
+        Err(e) => log::warn!("Could not follow activation of '{ssid}': {e}"),
+    }
+}
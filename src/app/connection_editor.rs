@@ -0,0 +1,90 @@
+//! Connection editor — advanced options of saved Wi-Fi profiles.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+
+use crate::ui::connection_editor;
+
+use super::{AppState, get_wifi, refresh_list};
+
+/// Row callback that opens the editor for a saved network's profile.
+///
+/// Saving writes the profile through `SettingsConnection.Update`; a connected
+/// network is reactivated so the new options apply right away.
+pub(super) fn edit_callback(
+    state: &Rc<RefCell<AppState>>,
+    list_box: &gtk4::ListBox,
+    status: &gtk4::Label,
+) -> Rc<dyn Fn(String)> {
+    let state = Rc::clone(state);
+    let list_box = list_box.clone();
+    let status = status.clone();
+    Rc::new(move |ssid: String| {
+        let network = state
+            .borrow()
+            .networks
+            .iter()
+            .find(|n| n.ssid == ssid)
+            .cloned();
+        let Some(network) = network else {
+            return;
+        };
+        let Some(conn_path) = network.connection_path.clone() else {
+            return;
+        };
+        let Some(parent) = list_box.root().and_downcast::<gtk4::Window>() else {
+            return;
+        };
+
+        let state = Rc::clone(&state);
+        let list_box = list_box.clone();
+        let status = status.clone();
+        glib::spawn_future_local(async move {
+            let wifi = get_wifi(&state);
+            let options = match wifi.profile_options(&conn_path).await {
+                Ok(options) => options,
+                Err(e) => {
+                    log::error!("Failed to read profile of '{ssid}': {e}");
+                    status.set_text(&format!("Failed to load settings: {}", e));
+                    return;
+                }
+            };
+
+            let resolved = wifi.has_systemd_resolved().await;
+
+            connection_editor::show_connection_editor(
+                &parent,
+                &ssid,
+                options,
+                resolved,
+                move |edited| {
+                    let state = Rc::clone(&state);
+                    let list_box = list_box.clone();
+                    let status = status.clone();
+                    let network = network.clone();
+                    let conn_path = conn_path.clone();
+                    glib::spawn_future_local(async move {
+                        let wifi = get_wifi(&state);
+                        if let Err(e) = wifi.set_profile_options(&conn_path, &edited).await {
+                            log::error!("Failed to save profile of '{}': {e}", network.ssid);
+                            status.set_text(&format!("Failed to save settings: {}", e));
+                            return;
+                        }
+                        if network.is_connected {
+                            super::connection::connect_without_password(
+                                &state, &list_box, &status, &network,
+                            )
+                            .await;
+                        } else {
+                            status.set_text(&format!("Saved settings for {}", network.ssid));
+                            refresh_list(&state, &list_box, &status).await;
+                        }
+                    });
+                },
+            );
+        });
+    })
+}
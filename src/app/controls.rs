@@ -6,6 +6,7 @@ use gtk4::{glib, Scale};
 use crate::controls::brightness::BrightnessManager;
 use crate::controls::volume::VolumeManager;
 use crate::controls::night_mode::NightModeManager;
+use crate::daemon::PanelState;
 use crate::state::AppStateStore;
 use crate::ui::window::PanelWidgets;
 
@@ -48,7 +49,7 @@ fn kelvin_to_slider(kelvin: f64, max: f64) -> f64 {
     t * max
 }
 
-pub fn setup_controls(widgets: &PanelWidgets) {
+pub fn setup_controls(widgets: &PanelWidgets, panel_state: PanelState) {
     let brightness_scale = widgets.controls.brightness_scale().clone();
     let brightness_btn = widgets.controls.brightness_btn().clone();
     let volume_scale = widgets.controls.volume_scale().clone();
@@ -57,6 +58,8 @@ pub fn setup_controls(widgets: &PanelWidgets) {
     let night_mode_scale = widgets.controls.night_mode_scale().clone();
     let night_mode_btn = widgets.controls.night_mode_btn().clone();
 
+    publish_controls_state(widgets, panel_state);
+
     // Load persisted dynamic state
     let state_store = Rc::new(RefCell::new(AppStateStore::load()));
 
@@ -256,9 +259,8 @@ pub fn setup_controls(widgets: &PanelWidgets) {
                     }
                 }
 
-                let mut store = store_btn.borrow_mut();
-                store.night_mode.enabled = new_enabled;
-                store.save();
+                store_btn.borrow_mut().night_mode.enabled = new_enabled;
+                AppStateStore::update(|s| s.night_mode.enabled = new_enabled);
             });
 
             // ── Slider drag: update temperature and persist ───────────
@@ -275,11 +277,76 @@ pub fn setup_controls(widgets: &PanelWidgets) {
                 if let Err(e) = mgr_slider.set_temperature(kelvin) {
                     log::warn!("Failed to set night mode temperature: {}", e);
                 }
-                let mut store = store_slider.borrow_mut();
-                store.night_mode.temperature = kelvin;
-                store.save();
+                store_slider.borrow_mut().night_mode.temperature = kelvin;
+                AppStateStore::update(|s| s.night_mode.temperature = kelvin);
             });
         }
         Err(e) => log::error!("Failed to init NightModeManager: {}", e),
     }
 }
+
+/// Mirror slider values into `PanelState` so bar modules can read them over
+/// D-Bus; property change signals are debounced while a slider is dragged.
+fn publish_controls_state(widgets: &PanelWidgets, panel_state: PanelState) {
+    let brightness_scale = widgets.controls.brightness_scale().clone();
+    let volume_scale = widgets.controls.volume_scale().clone();
+    let night_mode_scale = widgets.controls.night_mode_scale().clone();
+
+    let pending_source: Rc<RefCell<Option<glib::SourceId>>> = Rc::new(RefCell::new(None));
+
+    let publish: Rc<dyn Fn()> = {
+        let brightness_scale = brightness_scale.clone();
+        let volume_scale = volume_scale.clone();
+        let night_mode_scale = night_mode_scale.clone();
+        Rc::new(move || {
+            use std::sync::atomic::Ordering;
+
+            let controls = &panel_state.controls;
+            let night_enabled = night_mode_scale.is_sensitive();
+            let kelvin = if night_enabled {
+                let max = night_mode_scale.adjustment().upper();
+                slider_to_kelvin(night_mode_scale.value(), max)
+            } else {
+                NEUTRAL_TEMP_KELVIN
+            };
+            controls.color_temperature.store(kelvin.round() as u32, Ordering::Relaxed);
+            controls.night_mode.store(night_enabled, Ordering::Relaxed);
+            controls
+                .brightness
+                .store(brightness_scale.value().round() as u32, Ordering::Relaxed);
+            controls
+                .volume
+                .store(volume_scale.value().round() as u32, Ordering::Relaxed);
+
+            if let Some(source_id) = pending_source.borrow_mut().take() {
+                source_id.remove();
+            }
+            let panel_state = panel_state.clone();
+            let pending_clone = Rc::clone(&pending_source);
+            let new_source = glib::timeout_add_local(
+                std::time::Duration::from_millis(200),
+                move || {
+                    let panel_state = panel_state.clone();
+                    glib::spawn_future_local(async move {
+                        if let Err(e) = panel_state.emit_controls_changed().await {
+                            log::warn!("Failed to emit controls PropertiesChanged: {}", e);
+                        }
+                    });
+                    pending_clone.borrow_mut().take();
+                    glib::ControlFlow::Break
+                }
+            );
+            *pending_source.borrow_mut() = Some(new_source);
+        })
+    };
+
+    for scale in [&brightness_scale, &volume_scale, &night_mode_scale] {
+        let publish = Rc::clone(&publish);
+        scale.connect_value_changed(move |_| publish());
+    }
+    {
+        let publish = Rc::clone(&publish);
+        night_mode_scale.connect_sensitive_notify(move |_| publish());
+    }
+    publish();
+}
@@ -0,0 +1,93 @@
+//! Guest mode — gates forget, unpair, password reveal and settings-editing
+//! actions so the panel can be left on a shared machine.
+//!
+//! Connecting stays available. With `guest_pin` set, restricted actions ask for
+//! the PIN each time; without one they are simply refused.
+
+use std::rc::Rc;
+
+use gtk4::prelude::*;
+
+use crate::ui::pin_dialog;
+
+/// Whether guest mode is currently enabled in the config.
+pub(super) fn is_enabled() -> bool {
+    crate::config::Config::load().guest_mode
+}
+
+/// Run `on_allowed` if `action` is permitted, prompting for the PIN in guest mode.
+///
+/// `anchor` is any widget inside the panel; its window parents the PIN prompt.
+pub(super) fn authorize(
+    anchor: &impl IsA<gtk4::Widget>,
+    status: &gtk4::Label,
+    action: &str,
+    on_allowed: impl Fn() + 'static,
+) {
+    let config = crate::config::Config::load();
+    if !config.guest_mode {
+        on_allowed();
+        return;
+    }
+
+    if config.guest_pin.is_empty() {
+        status.set_text("Not allowed in guest mode");
+        return;
+    }
+    let Some(parent) = anchor.root().and_downcast::<gtk4::Window>() else {
+        return;
+    };
+
+    let status = status.clone();
+    pin_dialog::show_pin_dialog(&parent, action, move |pin| {
+        if pin == config.guest_pin {
+            on_allowed();
+        } else {
+            log::warn!("Guest mode: wrong PIN entered");
+            status.set_text("Wrong PIN");
+        }
+    });
+}
+
+/// Wrap a row callback so it only runs once `authorize` allows it.
+pub(super) fn guard(
+    anchor: &impl IsA<gtk4::Widget>,
+    status: &gtk4::Label,
+    action: &'static str,
+    inner: Rc<dyn Fn(String)>,
+) -> Rc<dyn Fn(String)> {
+    let anchor = anchor.clone().upcast::<gtk4::Widget>();
+    let status = status.clone();
+    Rc::new(move |key: String| {
+        let inner = Rc::clone(&inner);
+        authorize(&anchor, &status, action, move || inner(key.clone()));
+    })
+}
+
+/// Two-argument variant of `guard`, e.g. VPN row callbacks `(conn_path, name/uuid)`.
+pub(super) fn guard2(
+    anchor: &impl IsA<gtk4::Widget>,
+    status: &gtk4::Label,
+    action: &'static str,
+    inner: Rc<dyn Fn(String, String)>,
+) -> Rc<dyn Fn(String, String)> {
+    let anchor = anchor.clone().upcast::<gtk4::Widget>();
+    let status = status.clone();
+    Rc::new(move |first: String, second: String| {
+        let inner = Rc::clone(&inner);
+        authorize(&anchor, &status, action, move || {
+            inner(first.clone(), second.clone())
+        });
+    })
+}
+
+/// Show or hide the password entry's reveal icon depending on guest mode.
+pub(super) fn apply_password_reveal(entry: &gtk4::Entry) {
+    entry.set_visibility(false);
+    if is_enabled() {
+        entry.set_secondary_icon_name(None);
+    } else {
+        entry.set_secondary_icon_name(Some("view-reveal-symbolic"));
+        entry.set_secondary_icon_tooltip_text(Some("Show password"));
+    }
+}
@@ -0,0 +1,80 @@
+//! Wi-Fi adapter hotplug — rebuilds the `WifiManager` when the device is
+//! removed and another one (or the same dongle) is plugged back in.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+
+use crate::daemon::PanelState;
+use crate::dbus::network_manager::WifiManager;
+use crate::ui::window::{self, PanelWidgets};
+
+use super::{AppState, get_wifi, live_updates, refresh_list, scanning};
+
+/// Watch for the active WiFi device disappearing and swap in a new one.
+pub(super) fn setup_device_hotplug(
+    widgets: &PanelWidgets,
+    state: Rc<RefCell<AppState>>,
+    panel_state: PanelState,
+) {
+    let list_box = widgets.network_list_box.clone();
+    let status = widgets.status_label.clone();
+    let switch = widgets.wifi_switch.clone();
+    let wifi_tab = widgets.wifi_tab.clone();
+
+    glib::spawn_future_local(async move {
+        loop {
+            let wifi = get_wifi(&state);
+            if let Err(e) = wifi.wait_for_removal().await {
+                log::error!("Failed to watch for WiFi device removal: {e}");
+                return;
+            }
+
+            log::warn!("WiFi device {} removed", wifi.wifi_device_path());
+            scanning::stop_wifi_auto_scan(&state);
+            scanning::stop_wifi_bg_reconnect(&state);
+            {
+                let mut st = state.borrow_mut();
+                st.networks.clear();
+                st.wifi_row_ssids.clear();
+                st.wifi_pending.clear();
+            }
+            while let Some(row) = list_box.first_child() {
+                list_box.remove(&row);
+            }
+            window::set_panel_class(&list_box, "connected", false);
+            window::set_panel_class(&list_box, "disconnected", true);
+            status.set_text("Wi-Fi adapter removed — waiting for one...");
+
+            let wifi = match WifiManager::wait_for_device().await {
+                Ok(wifi) => wifi,
+                Err(e) => {
+                    log::error!("Gave up waiting for a WiFi device: {e}");
+                    return;
+                }
+            };
+            panel_state.set_wifi(wifi.clone());
+            state.borrow_mut().wifi = wifi;
+            log::info!("WiFi device available again, resubscribing");
+
+            live_updates::subscribe_device_signals(
+                Rc::clone(&state),
+                list_box.clone(),
+                status.clone(),
+                switch.clone(),
+                panel_state.visible.clone(),
+            );
+            refresh_list(&state, &list_box, &status).await;
+            if wifi_tab.is_active() {
+                scanning::start_wifi_auto_scan(
+                    Rc::clone(&state),
+                    wifi_tab.clone(),
+                    list_box.clone(),
+                    status.clone(),
+                );
+            }
+        }
+    });
+}
@@ -0,0 +1,89 @@
+//! Known-network alerts — notify when a saved network comes into range.
+//!
+//! While disconnected, every `AccessPointAdded` is checked against the saved
+//! profiles. Each SSID is announced once until the next connection; clicking
+//! the notification connects to it.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::glib;
+
+use super::{AppState, get_notifier, get_wifi};
+
+/// Announce `ap_path` if it belongs to a saved network and we're offline.
+pub(super) fn check_access_point(
+    state: &Rc<RefCell<AppState>>,
+    list_box: &gtk4::ListBox,
+    status: &gtk4::Label,
+    ap_path: String,
+) {
+    if !state.borrow().config.notify_known_networks {
+        return;
+    }
+
+    let state = Rc::clone(state);
+    let list_box = list_box.clone();
+    let status = status.clone();
+    glib::spawn_future_local(async move {
+        let wifi = get_wifi(&state);
+        if let Ok(Some(_)) = wifi.active_ssid().await {
+            // Connected: start announcing afresh after the next disconnect
+            state.borrow_mut().wifi_known_notified.clear();
+            return;
+        }
+
+        let ssid = match wifi.saved_ssid_for_ap(&ap_path).await {
+            Ok(Some(ssid)) => ssid,
+            Ok(None) => return,
+            Err(e) => {
+                log::debug!("Known networks: skipping AP {ap_path}: {e}");
+                return;
+            }
+        };
+        if !state.borrow_mut().wifi_known_notified.insert(ssid.clone()) {
+            return;
+        }
+
+        log::info!("Known networks: '{ssid}' came into range");
+        let notifier = match get_notifier(&state).await {
+            Ok(n) => n,
+            Err(e) => {
+                log::warn!("Known networks: notifications unavailable: {e}");
+                return;
+            }
+        };
+        let clicked = notifier
+            .notify_with_action(
+                "network-wireless-symbolic",
+                &format!("{ssid} available"),
+                "Click to connect",
+                "Connect",
+            )
+            .await;
+        match clicked {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(e) => {
+                log::warn!("Known networks: failed to notify: {e}");
+                return;
+            }
+        }
+
+        let network = match wifi.get_networks(false).await {
+            Ok(nets) => nets.into_iter().find(|n| n.ssid == ssid && n.is_saved),
+            Err(e) => {
+                log::error!("Known networks: failed to list networks: {e}");
+                return;
+            }
+        };
+        match network {
+            Some(network) if network.is_connected => {}
+            Some(network) => {
+                super::connection::connect_without_password(&state, &list_box, &status, &network)
+                    .await;
+            }
+            None => status.set_text(&format!("{ssid} is out of range")),
+        }
+    });
+}
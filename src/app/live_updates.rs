@@ -5,6 +5,7 @@ use std::rc::Rc;
 
 use gtk4::glib;
 
+use crate::dbus::network_manager::Connectivity;
 use crate::ui::window::PanelWidgets;
 
 use super::{AppState, get_wifi, refresh_list};
@@ -14,6 +15,7 @@ use super::{AppState, get_wifi, refresh_list};
 /// Watches:
 /// - Device StateChanged — fires when connection state changes (connected/disconnected/etc)
 /// - Wireless AccessPointAdded/Removed — fires when APs appear/disappear
+/// - NM Connectivity — fires when the internet becomes (un)reachable
 ///
 /// On any change, the network list is auto-refreshed after a brief debounce.
 pub(super) fn setup_live_updates(
@@ -21,12 +23,60 @@ pub(super) fn setup_live_updates(
     state: Rc<RefCell<AppState>>,
     panel_visible: std::sync::Arc<std::sync::atomic::AtomicBool>,
 ) {
-    let list_box = widgets.network_list_box.clone();
-    let status = widgets.status_label.clone();
-    let switch = widgets.wifi_switch.clone();
+    subscribe_device_signals(
+        Rc::clone(&state),
+        widgets.network_list_box.clone(),
+        widgets.status_label.clone(),
+        widgets.wifi_switch.clone(),
+        panel_visible,
+    );
+
+    // Subscribe to NM Connectivity property changes (not tied to the device)
+    {
+        let list_box = widgets.network_list_box.clone();
+        let status = widgets.status_label.clone();
+
+        glib::spawn_future_local(async move {
+            let wifi = get_wifi(&state);
+            let nm = match crate::dbus::proxies::NetworkManagerProxy::new(wifi.connection()).await {
+                Ok(p) => p,
+                Err(e) => {
+                    log::error!("Failed to create NM proxy for connectivity updates: {e}");
+                    return;
+                }
+            };
+
+            let mut stream = nm.receive_connectivity_changed().await;
+            log::info!("Live updates: watching NM connectivity");
+
+            use futures_util::StreamExt;
+            while let Some(change) = stream.next().await {
+                if let Ok(connectivity) = change.get().await {
+                    log::info!("Connectivity changed: {:?}", Connectivity::from(connectivity));
+                }
+                refresh_list(&state, &list_box, &status).await;
+            }
+        });
+    }
+}
+
+/// Subscribe to the signals of the WiFi device currently held in `state`.
+///
+/// Called again after a hotplugged adapter replaces the `WifiManager`; the
+/// previous device's listeners are aborted first.
+pub(super) fn subscribe_device_signals(
+    state: Rc<RefCell<AppState>>,
+    list_box: gtk4::ListBox,
+    status: gtk4::Label,
+    switch: gtk4::Switch,
+    panel_visible: std::sync::Arc<std::sync::atomic::AtomicBool>,
+) {
+    for handle in state.borrow_mut().wifi_device_signals.drain(..) {
+        handle.abort();
+    }
 
     // Subscribe to Device.StateChanged signal
-    {
+    let state_changed = {
         let state = Rc::clone(&state);
         let list_box = list_box.clone();
         let status = status.clone();
@@ -103,11 +153,11 @@ pub(super) fn setup_live_updates(
                 glib::timeout_future(std::time::Duration::from_millis(500)).await;
                 refresh_list(&state, &list_box, &status).await;
             }
-        });
-    }
+        })
+    };
 
     // Subscribe to Wireless AccessPointAdded/Removed signals
-    {
+    let access_points = {
         let state = Rc::clone(&state);
         let list_box = list_box.clone();
         let status = status.clone();
@@ -158,27 +208,42 @@ pub(super) fn setup_live_updates(
                 let state_added = Rc::clone(&state);
                 let list_box_added = list_box.clone();
                 let status_added = status.clone();
-                glib::spawn_future_local(async move {
-                    while (ap_added.next().await).is_some() {
+                let handle = glib::spawn_future_local(async move {
+                    while let Some(signal) = ap_added.next().await {
+                        if let Ok(args) = signal.args() {
+                            super::known_networks::check_access_point(
+                                &state_added,
+                                &list_box_added,
+                                &status_added,
+                                args.access_point.to_string(),
+                            );
+                        }
                         log::debug!("AccessPoint added, refreshing list");
                         glib::timeout_future(std::time::Duration::from_millis(300)).await;
                         refresh_list(&state_added, &list_box_added, &status_added).await;
                     }
                 });
+                state.borrow_mut().wifi_device_signals.push(handle);
             }
 
             if let Some(mut ap_removed) = ap_removed {
                 let state_removed = Rc::clone(&state);
                 let list_box_removed = list_box.clone();
                 let status_removed = status.clone();
-                glib::spawn_future_local(async move {
+                let handle = glib::spawn_future_local(async move {
                     while (ap_removed.next().await).is_some() {
                         log::debug!("AccessPoint removed, refreshing list");
                         glib::timeout_future(std::time::Duration::from_millis(300)).await;
                         refresh_list(&state_removed, &list_box_removed, &status_removed).await;
                     }
                 });
+                state.borrow_mut().wifi_device_signals.push(handle);
             }
-        });
-    }
+        })
+    };
+
+    state
+        .borrow_mut()
+        .wifi_device_signals
+        .extend([state_changed, access_points]);
 }
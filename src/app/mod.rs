@@ -5,23 +5,52 @@
 //! - `connection` — WiFi toggle, network click, password dialog
 //! - `live_updates` — D-Bus signal subscriptions for real-time changes
 //! - `shortcuts` — Escape key, reload polling
+//! - `quality` — connection quality grade in the header tooltip
+//! - `portal` — captive-portal login hooks
+//! - `guest` — guest mode gating of destructive actions
+//! - `hotplug` — rebuilds the WiFi manager when the adapter is replugged
+//! - `quick_toggles` — WiFi / Bluetooth / Airplane / Night mode strip
+//! - `search` — SSID filter for the network list (Ctrl+F)
+//! - `vpn_policy` — auto-activates a VPN on networks listed in `require_vpn`
+//! - `tray` — optional StatusNotifierItem icon showing the Wi-Fi state
 
 mod bluetooth;
+mod bt_adapters;
+mod bt_details;
+mod bt_discoverable;
 mod bt_helpers;
 mod bt_live_updates;
+mod bt_pairing;
+mod bt_reconnect;
+mod bt_rename;
+mod bt_repair;
 mod bt_scanning;
+mod bt_transfer;
 mod connection;
+mod connection_editor;
 mod controls;
+mod guest;
+mod hotplug;
+mod known_networks;
 mod live_updates;
+mod network_details;
+mod portal;
+mod quality;
+mod quick_toggles;
 mod scanning;
+mod search;
 mod shortcuts;
+mod tray;
 mod vpn;
 mod vpn_import;
+mod vpn_policy;
 mod vpn_utils;
+mod weak_signal;
 
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
+use std::sync::atomic::Ordering;
 use std::time::Instant;
 
 use gtk4::glib;
@@ -30,10 +59,12 @@ use gtk4::prelude::*;
 use crate::dbus::access_point::Network;
 use crate::dbus::bluetooth_device::BluetoothDevice;
 use crate::dbus::bluetooth_manager::BluetoothManager;
+use crate::dbus::link_quality::LinkSampler;
 use crate::dbus::network_manager::WifiManager;
+use crate::dbus::notifications::Notifier;
 use crate::dbus::vpn_manager::{VpnActive, VpnManager};
 use crate::ui::network_list;
-use crate::ui::window::PanelWidgets;
+use crate::ui::window::{self, PanelWidgets};
 
 /// Shared application state accessible from GTK callbacks.
 #[derive(Clone)]
@@ -42,7 +73,16 @@ struct PendingVpnAction {
     started_at: Instant,
 }
 
+/// Password entry in progress when the panel was hidden.
+struct PasswordDraft {
+    ssid: String,
+    text: String,
+    saved_at: Instant,
+}
+
 struct AppState {
+    /// Config as of startup or the last reload.
+    config: crate::config::Config,
     wifi: WifiManager,
     vpn: VpnManager,
     /// The network list — refreshed on scan.
@@ -57,8 +97,13 @@ struct AppState {
     bt_row_paths: Vec<Option<String>>,
     /// Pending Bluetooth actions by device path (label).
     bt_pending: HashMap<String, String>,
+    /// GATT battery levels of connected LE devices by device path; `None`
+    /// while the one read per connection is in flight or after it failed.
+    bt_gatt_batteries: HashMap<String, Option<u8>>,
     /// Whether a Bluetooth scan is currently running.
     bt_scan_in_progress: bool,
+    /// Dropped to end the running Bluetooth scan window early (panel hidden).
+    bt_scan_abort: Option<async_channel::Sender<()>>,
     /// Periodic auto-scan timer for Bluetooth (when BT tab is active).
     bt_auto_scan_source: Option<glib::SourceId>,
     /// Periodic refresh timer for Bluetooth list (when BT tab is active).
@@ -67,10 +112,20 @@ struct AppState {
     bt_auto_scan_active: bool,
     /// Whether a Bluetooth device menu is open (avoid refresh to prevent popover closing).
     bt_menu_open: bool,
+    /// Sends files over OBEX (set once a Bluetooth adapter is found).
+    bt_file_sender: Option<bt_transfer::FileSender>,
+    /// Inline rename section for device aliases (set once an adapter is found).
+    bt_renamer: Option<bt_rename::DeviceRenamer>,
+    /// Inline details section opened from a device's row menu.
+    bt_details: Option<crate::ui::network_details::NetworkDetails>,
+    /// Told about the new manager after an adapter switch (quick toggle watcher).
+    bt_adapter_switched: Option<async_channel::Sender<BluetoothManager>>,
     /// Whether a Wi-Fi scan is currently running.
     wifi_scan_in_progress: bool,
     /// Periodic auto-scan timer for Wi-Fi (when Wi-Fi tab is active).
     wifi_auto_scan_source: Option<glib::SourceId>,
+    /// Once-a-second "Updated Xs ago" ticker; runs only while the panel is shown.
+    wifi_scan_age_source: Option<glib::SourceId>,
     /// Background 60-second reconnect scan timer.
     /// Active only when the panel is hidden AND Wi-Fi is disconnected.
     wifi_bg_reconnect_source: Option<glib::SourceId>,
@@ -78,6 +133,10 @@ struct AppState {
     wifi_row_ssids: Vec<Option<String>>,
     /// Pending Wi-Fi actions by SSID.
     wifi_pending: HashMap<String, String>,
+    /// Active connection paths of in-flight connects by SSID (for cancel).
+    wifi_pending_active: HashMap<String, String>,
+    /// Saved SSIDs already announced as in range since the last connection.
+    wifi_known_notified: HashSet<String>,
     /// Pending VPN actions by Settings.Connection path.
     vpn_pending: HashMap<String, PendingVpnAction>,
     /// Cached active VPN connections keyed by Settings.Connection path.
@@ -88,6 +147,22 @@ struct AppState {
     vpn_busy_count: usize,
     /// Prevent re-entrant single-active normalization loops.
     vpn_normalizing: bool,
+    /// When NM last finished a Wi-Fi scan (drives the "Updated Xs ago" label).
+    wifi_last_scan: Option<Instant>,
+    /// No new Wi-Fi scan is requested before this (manual cooldown / NM backoff).
+    wifi_scan_not_before: Option<Instant>,
+    /// Results older than this many seconds are shown as stale.
+    wifi_stale_after_secs: u64,
+    /// Previous traffic counter sample for the connection quality grade.
+    link_sampler: LinkSampler,
+    /// Password entry stashed on hide, restored on the next show.
+    password_draft: Option<PasswordDraft>,
+    /// Background weak-signal monitor; restarted on config reload.
+    weak_signal_monitor: Option<glib::JoinHandle<()>>,
+    /// Signal listeners of the current WiFi device; replaced on hotplug.
+    wifi_device_signals: Vec<glib::JoinHandle<()>>,
+    /// Notification daemon connection, opened on first use.
+    notifier: Option<Notifier>,
 }
 
 
@@ -101,7 +176,11 @@ pub fn setup(
     panel_state: crate::daemon::PanelState,
 ) {
     let vpn = VpnManager::new(wifi.connection());
+    panel_state.set_wifi(wifi.clone());
+    let config = crate::config::Config::load();
     let state = Rc::new(RefCell::new(AppState {
+        wifi_stale_after_secs: config.stale_scan_secs,
+        config,
         wifi,
         vpn,
         networks: Vec::new(),
@@ -110,29 +189,51 @@ pub fn setup(
         bt_devices: Vec::new(),
         bt_row_paths: Vec::new(),
         bt_pending: HashMap::new(),
+        bt_gatt_batteries: HashMap::new(),
         bt_scan_in_progress: false,
+        bt_scan_abort: None,
         bt_auto_scan_source: None,
         bt_live_refresh_source: None,
         bt_auto_scan_active: false,
         bt_menu_open: false,
+        bt_file_sender: None,
+        bt_renamer: None,
+        bt_details: None,
+        bt_adapter_switched: None,
         wifi_scan_in_progress: false,
         wifi_auto_scan_source: None,
+        wifi_scan_age_source: None,
         wifi_bg_reconnect_source: None,
         wifi_row_ssids: Vec::new(),
         wifi_pending: HashMap::new(),
+        wifi_pending_active: HashMap::new(),
+        wifi_known_notified: HashSet::new(),
         vpn_pending: HashMap::new(),
         vpn_active_by_conn: HashMap::new(),
         vpn_refresh_source: None,
         vpn_busy_count: 0,
         vpn_normalizing: false,
+        wifi_last_scan: None,
+        wifi_scan_not_before: None,
+        link_sampler: LinkSampler::default(),
+        password_draft: None,
+        weak_signal_monitor: None,
+        wifi_device_signals: Vec::new(),
+        notifier: None,
     }));
 
     connection::setup_wifi_toggle(widgets, Rc::clone(&state));
     connection::setup_network_click(widgets, Rc::clone(&state));
     connection::setup_password_actions(widgets, Rc::clone(&state));
+    connection::setup_open_warning(widgets, Rc::clone(&state));
+    network_details::setup_network_details(widgets);
     live_updates::setup_live_updates(widgets, Rc::clone(&state), panel_state.visible.clone());
+    portal::setup_portal_login(widgets, Rc::clone(&state));
+    weak_signal::setup_weak_signal_alerts(&state, &widgets.status_label);
+    vpn_policy::setup_vpn_policy(widgets, Rc::clone(&state));
+    hotplug::setup_device_hotplug(widgets, Rc::clone(&state), panel_state.clone());
     scanning::setup_scan_on_show(widgets, Rc::clone(&state), scan_requested);
-    bluetooth::setup_bluetooth(widgets, Rc::clone(&state));
+    bluetooth::setup_bluetooth(widgets, Rc::clone(&state), panel_state.clone());
     bt_live_updates::setup_bt_live_updates(widgets, Rc::clone(&state));
     setup_scan_button_dispatch(widgets, Rc::clone(&state));
     setup_wifi_tab_sync(widgets, Rc::clone(&state));
@@ -147,9 +248,19 @@ pub fn setup(
     }
     let reload_requested = panel_state.reload_requested.clone();
     shortcuts::setup_escape_key(widgets, panel_state.clone());
+    search::setup_network_search(widgets, Rc::clone(&state));
     shortcuts::setup_reload_on_request(widgets, Rc::clone(&state), reload_requested);
     scanning::setup_initial_state(widgets, Rc::clone(&state));
-    controls::setup_controls(widgets);
+    if panel_state.visible.load(Ordering::Relaxed) {
+        scanning::start_scan_age_indicator(
+            Rc::clone(&state),
+            widgets.scan_age_label.clone(),
+            widgets.network_list_box.clone(),
+        );
+    }
+    controls::setup_controls(widgets, panel_state.clone());
+    quick_toggles::setup_quick_toggles(widgets, Rc::clone(&state));
+    tray::setup_tray(Rc::clone(&state), panel_state.clone());
     setup_visibility_pause(widgets, Rc::clone(&state), panel_state);
 }
 
@@ -158,6 +269,16 @@ fn get_wifi(state: &Rc<RefCell<AppState>>) -> WifiManager {
     state.borrow().wifi.clone()
 }
 
+/// The shared Notifier, connecting to the session bus on first use.
+async fn get_notifier(state: &Rc<RefCell<AppState>>) -> zbus::Result<Notifier> {
+    if let Some(notifier) = state.borrow().notifier.clone() {
+        return Ok(notifier);
+    }
+    let notifier = Notifier::new().await?;
+    state.borrow_mut().notifier = Some(notifier.clone());
+    Ok(notifier)
+}
+
 /// Refresh the network list from D-Bus and update the UI.
 async fn refresh_list(
     state: &Rc<RefCell<AppState>>,
@@ -165,23 +286,37 @@ async fn refresh_list(
     status: &gtk4::Label,
 ) {
     let wifi = get_wifi(state);
-    let networks = wifi.get_networks().await;
+    let config = state.borrow().config.clone();
+    let networks = wifi.get_networks(config.show_hidden_networks).await;
 
     match networks {
         Ok(nets) => {
             // Update status with connected network
             let connected = nets.iter().find(|n| n.is_connected);
             match connected {
-                Some(n) => status.set_text(&format!("Connected to {}", n.ssid)),
+                Some(n) => {
+                    // Associated to the AP doesn't mean the upstream works
+                    let note = wifi
+                        .connectivity()
+                        .await
+                        .ok()
+                        .and_then(|c| c.status_note());
+                    let name = n.display_name();
+                    match note {
+                        Some(note) => status.set_text(&format!("Connected to {name} · {note}")),
+                        None => status.set_text(&format!("Connected to {name}")),
+                    }
+                }
                 None => status.set_text("Not connected"),
             }
+            window::set_panel_class(list_box, "connected", connected.is_some());
+            window::set_panel_class(list_box, "disconnected", connected.is_none());
 
-            let config = crate::config::Config::load();
             let on_forget = {
                 let state = Rc::clone(state);
                 let list_box = list_box.clone();
                 let status = status.clone();
-                std::rc::Rc::new(move |ssid: String| {
+                Rc::new(move |ssid: String| {
                     let state = Rc::clone(&state);
                     let list_box = list_box.clone();
                     let status = status.clone();
@@ -201,6 +336,69 @@ async fn refresh_list(
                     });
                 })
             };
+            let on_forget = guest::guard(list_box, status, "forget this network", on_forget);
+            let on_lock_bssid = {
+                let state = Rc::clone(state);
+                let list_box = list_box.clone();
+                let status = status.clone();
+                Rc::new(move |ssid: String, bssid: String| {
+                    let Some(network) = state
+                        .borrow()
+                        .networks
+                        .iter()
+                        .find(|n| n.ssid == ssid)
+                        .cloned()
+                    else {
+                        return;
+                    };
+                    let state = Rc::clone(&state);
+                    let list_box = list_box.clone();
+                    let status = status.clone();
+                    glib::spawn_future_local(async move {
+                        let wifi = get_wifi(&state);
+                        let lock = (!bssid.is_empty()).then_some(bssid.as_str());
+                        match wifi.set_bssid_lock(&network, lock).await {
+                            Ok(_) => {
+                                let text = match lock {
+                                    Some(bssid) => format!("{ssid} pinned to {bssid}"),
+                                    None => format!("{ssid} can use any access point"),
+                                };
+                                status.set_text(&text);
+                                refresh_list(&state, &list_box, &status).await;
+                            }
+                            Err(e) => {
+                                log::error!("BSSID lock failed: {e}");
+                                status.set_text(&format!("Failed to pin access point: {}", e));
+                            }
+                        }
+                    });
+                })
+            };
+            let on_lock_bssid =
+                guest::guard2(list_box, status, "change this network", on_lock_bssid);
+            let on_edit = connection_editor::edit_callback(state, list_box, status);
+            let on_edit = guest::guard(list_box, status, "edit this network", on_edit);
+            let on_disconnect = connection::disconnect_callback(state, list_box, status);
+            let on_cancel = {
+                let state = Rc::clone(state);
+                let status = status.clone();
+                Rc::new(move |ssid: String| {
+                    let Some(active_path) = state.borrow().wifi_pending_active.get(&ssid).cloned()
+                    else {
+                        return;
+                    };
+                    let state = Rc::clone(&state);
+                    let status = status.clone();
+                    glib::spawn_future_local(async move {
+                        let wifi = get_wifi(&state);
+                        status.set_text(&format!("Cancelling connection to {ssid}..."));
+                        if let Err(e) = wifi.cancel_activation(&active_path).await {
+                            log::error!("Cancel failed: {e}");
+                            status.set_text(&format!("Failed to cancel: {}", e));
+                        }
+                    });
+                })
+            };
             let row_ssids = network_list::populate_network_list(
                 list_box,
                 &nets,
@@ -208,12 +406,26 @@ async fn refresh_list(
                 &wifi,
                 status,
                 &state.borrow().wifi_pending,
+                &state.borrow().wifi_pending_active,
                 on_forget,
+                on_lock_bssid,
+                on_edit,
+                on_disconnect,
+                on_cancel,
             );
             log::info!("Network list refreshed: {} networks", nets.len());
-            let mut st = state.borrow_mut();
-            st.networks = nets;
-            st.wifi_row_ssids = row_ssids;
+            let is_connected = nets.iter().any(|n| n.is_connected);
+            let scan_age = wifi.last_scan_age().await.ok().flatten();
+            {
+                let mut st = state.borrow_mut();
+                st.networks = nets;
+                st.wifi_row_ssids = row_ssids;
+                st.wifi_last_scan = scan_age.and_then(|age| Instant::now().checked_sub(age));
+                st.wifi_stale_after_secs = config.stale_scan_secs;
+            }
+            // Row SSIDs changed; re-apply the search filter
+            list_box.invalidate_filter();
+            quality::update_quality_tooltip(state, status, is_connected).await;
         }
         Err(e) => {
             log::error!("Failed to get networks: {e}");
@@ -286,6 +498,7 @@ fn setup_wifi_tab_sync(widgets: &PanelWidgets, state: Rc<RefCell<AppState>>) {
         }
 
         title.set_text("Wi-Fi");
+        title.set_tooltip_text(None);
         switch.set_tooltip_text(Some("Enable/Disable Wi-Fi"));
         if vpn_tab.is_active() {
             scan_btn.set_sensitive(false);
@@ -345,14 +558,15 @@ fn setup_visibility_pause(
     state: Rc<RefCell<AppState>>,
     panel_state: crate::daemon::PanelState,
 ) {
-    use std::sync::atomic::Ordering;
-
     let last_visible = Rc::new(RefCell::new(panel_state.visible.load(Ordering::Relaxed)));
     let wifi_tab = widgets.wifi_tab.clone();
     let bt_tab = widgets.bt_tab.clone();
     let wifi_list_box = widgets.network_list_box.clone();
     let bt_list_box = widgets.bt_list_box.clone();
     let status = widgets.status_label.clone();
+    let revealer = widgets.password_revealer.clone();
+    let entry = widgets.password_entry.clone();
+    let scan_age_label = widgets.scan_age_label.clone();
 
     glib::timeout_add_local(std::time::Duration::from_millis(200), move || {
         let visible = panel_state.visible.load(Ordering::Relaxed);
@@ -360,7 +574,9 @@ fn setup_visibility_pause(
         if *last != visible {
             *last = visible;
             if !visible {
+                connection::stash_password_draft(&state, &revealer, &entry);
                 scanning::stop_wifi_auto_scan(&state);
+                scanning::stop_scan_age_indicator(&state);
                 // Stop bg reconnect too — panel is opening so fast loop takes over.
                 scanning::stop_wifi_bg_reconnect(&state);
                 vpn::stop_vpn_refresh(&state);
@@ -406,6 +622,12 @@ fn setup_visibility_pause(
                     }
                 });
             } else {
+                connection::restore_password_draft(&state, &revealer, &entry);
+                scanning::start_scan_age_indicator(
+                    Rc::clone(&state),
+                    scan_age_label.clone(),
+                    wifi_list_box.clone(),
+                );
                 if wifi_tab.is_active() {
                     scanning::start_wifi_auto_scan(
                         Rc::clone(&state),
@@ -0,0 +1,74 @@
+//! Network details — what clicking the connected row shows instead of disconnecting.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::prelude::*;
+
+use crate::dbus::access_point::{self, Network};
+use crate::ui::network_details::{self, NetworkDetails};
+use crate::ui::window::PanelWidgets;
+
+use super::{AppState, get_wifi};
+
+/// Fill the details section for the connected `network` and reveal it.
+///
+/// Link rate and addressing are read live; anything NM can't report yet
+/// (e.g. no DHCP lease) is left out rather than shown as blank.
+pub(super) async fn show_details(
+    state: &Rc<RefCell<AppState>>,
+    details: &NetworkDetails,
+    network: &Network,
+) {
+    let wifi = get_wifi(state);
+
+    let mut rows = vec![("Signal", format!("{}%", network.strength))];
+    let mut radio = vec![network.band.to_string()];
+    if let Some(channel) = access_point::channel_from_frequency(network.frequency_mhz) {
+        radio.push(format!("Ch {channel}"));
+    }
+    if network.bandwidth_mhz > 0 {
+        radio.push(format!("{} MHz", network.bandwidth_mhz));
+    }
+    rows.push(("Band", radio.join(" · ")));
+    if let Some(generation) = access_point::generation_label(&network.band, network.bandwidth_mhz) {
+        rows.push(("Standard", generation.to_string()));
+    }
+    if let Ok(stats) = wifi.get_link_stats().await
+        && stats.bitrate_kbps > 0
+    {
+        rows.push(("Link rate", format!("{} Mbit/s", stats.bitrate_kbps / 1000)));
+    }
+    rows.push(("Security", network.security.to_string()));
+    if let Some(ap) = network
+        .access_points
+        .iter()
+        .find(|ap| ap.path == network.ap_path)
+    {
+        rows.push(("BSSID", ap.bssid.clone()));
+    }
+    match wifi.ipv4_info().await {
+        Ok(ipv4) => {
+            if let Some(address) = ipv4.address {
+                rows.push(("IPv4", address));
+            }
+            if let Some(gateway) = ipv4.gateway {
+                rows.push(("Gateway", gateway));
+            }
+        }
+        Err(e) => log::debug!("No IPv4 details for '{}': {e}", network.ssid),
+    }
+
+    details.title.set_text(network.display_name());
+    network_details::set_detail_rows(details, &rows);
+    details.revealer.set_reveal_child(true);
+}
+
+/// Wire the Close button of the details section.
+pub(super) fn setup_network_details(widgets: &PanelWidgets) {
+    let revealer = widgets.network_details.revealer.clone();
+    widgets
+        .network_details
+        .close_button
+        .connect_clicked(move |_| revealer.set_reveal_child(false));
+}
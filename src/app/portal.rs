@@ -0,0 +1,87 @@
+//! Captive portal — runs per-SSID login commands when NM detects a portal.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::glib;
+
+use crate::dbus::network_manager::Connectivity;
+use crate::ui::window::PanelWidgets;
+
+use super::{AppState, get_wifi};
+
+/// Watch NM connectivity and run the configured `portal_login` command
+/// once per portal detection on the current SSID.
+pub(super) fn setup_portal_login(widgets: &PanelWidgets, state: Rc<RefCell<AppState>>) {
+    let status = widgets.status_label.clone();
+
+    glib::spawn_future_local(async move {
+        // The NM root object outlives adapter hotplug; only the device changes
+        let connection = get_wifi(&state).connection().clone();
+        let nm = match crate::dbus::proxies::NetworkManagerProxy::new(&connection).await {
+            Ok(p) => p,
+            Err(e) => {
+                log::error!("Failed to create NM proxy for portal detection: {e}");
+                return;
+            }
+        };
+
+        let mut stream = nm.receive_connectivity_changed().await;
+        log::info!("Portal login: watching NM connectivity");
+
+        // SSID the login command last ran for; cleared once the portal is gone
+        let mut handled_ssid: Option<String> = None;
+
+        use futures_util::StreamExt;
+        while let Some(change) = stream.next().await {
+            let Ok(connectivity) = change.get().await else {
+                continue;
+            };
+            if Connectivity::from(connectivity) != Connectivity::Portal {
+                handled_ssid = None;
+                continue;
+            }
+
+            // Re-read each time: hotplug replaces the WifiManager
+            let wifi = get_wifi(&state);
+            let ssid = match wifi.active_ssid().await {
+                Ok(Some(ssid)) => ssid,
+                Ok(None) => continue,
+                Err(e) => {
+                    log::warn!("Portal detected but SSID lookup failed: {e}");
+                    continue;
+                }
+            };
+            if handled_ssid.as_deref() == Some(ssid.as_str()) {
+                continue;
+            }
+
+            let Some(command) = state.borrow().config.portal_login.get(&ssid).cloned() else {
+                log::info!("Captive portal on '{ssid}', no login command configured");
+                continue;
+            };
+
+            handled_ssid = Some(ssid.clone());
+            status.set_text(&format!("Logging in to {ssid} portal..."));
+            run_login_command(ssid, command);
+        }
+    });
+}
+
+/// Run a portal login command in the background and log its outcome.
+fn run_login_command(ssid: String, command: String) {
+    log::info!("Running portal login command for '{ssid}'");
+    std::thread::spawn(move || {
+        match std::process::Command::new("sh")
+            .arg("-c")
+            .arg(&command)
+            .status()
+        {
+            Ok(status) if status.success() => {
+                log::info!("Portal login for '{ssid}' finished");
+            }
+            Ok(status) => log::warn!("Portal login for '{ssid}' exited with {status}"),
+            Err(e) => log::error!("Failed to run portal login for '{ssid}': {e}"),
+        }
+    });
+}
@@ -0,0 +1,56 @@
+//! Connection quality grade — shown as the header status tooltip.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Instant;
+
+use gtk4::prelude::*;
+
+use super::{AppState, get_wifi};
+use crate::dbus::link_quality::{self, Throughput};
+
+/// Grade the active link and update the status label tooltip.
+/// Clears the tooltip when there is no active connection.
+pub(super) async fn update_quality_tooltip(
+    state: &Rc<RefCell<AppState>>,
+    status: &gtk4::Label,
+    connected: bool,
+) {
+    if !connected {
+        state.borrow_mut().link_sampler.reset();
+        status.set_tooltip_text(None);
+        return;
+    }
+
+    let wifi = get_wifi(state);
+    let stats = match wifi.get_link_stats().await {
+        Ok(stats) => stats,
+        Err(e) => {
+            log::debug!("Link statistics unavailable: {e}");
+            status.set_tooltip_text(None);
+            return;
+        }
+    };
+
+    let throughput = state
+        .borrow_mut()
+        .link_sampler
+        .record(stats, Instant::now());
+    let (grade, reason) = link_quality::grade_link(&stats, throughput);
+
+    let mut text = format!(
+        "Connection quality: {grade}\n{reason}\nSignal: {}%",
+        stats.strength
+    );
+    if stats.bitrate_kbps > 0 {
+        text.push_str(&format!(" · Link: {} Mbit/s", stats.bitrate_kbps / 1000));
+    }
+    if let Some(Throughput { rx_bps, tx_bps }) = throughput {
+        text.push_str(&format!(
+            "\nTraffic: ↓ {} · ↑ {}",
+            link_quality::format_rate(rx_bps),
+            link_quality::format_rate(tx_bps)
+        ));
+    }
+    status.set_tooltip_text(Some(&text));
+}
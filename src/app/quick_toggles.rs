@@ -0,0 +1,252 @@
+//! Quick toggles — wires the WiFi / Bluetooth / Airplane / Night mode strip.
+//!
+//! Each button drives the existing managers and is kept in sync with
+//! D-Bus property changes, so the header switch and tabs stay consistent.
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use futures_util::StreamExt;
+use futures_util::future::Either;
+use gtk4::glib;
+use gtk4::prelude::*;
+
+use crate::dbus::bluetooth_manager::BluetoothManager;
+use crate::dbus::proxies::NetworkManagerProxy;
+use crate::ui::quick_toggles::QuickToggles;
+use crate::ui::window::PanelWidgets;
+
+use super::bt_helpers::get_bt;
+use super::bt_reconnect::reconnect_trusted_audio;
+use super::{AppState, get_wifi};
+
+/// Shared toggle handles plus a guard against feedback from programmatic updates.
+struct ToggleSync {
+    wifi: gtk4::ToggleButton,
+    bluetooth: gtk4::ToggleButton,
+    airplane: gtk4::ToggleButton,
+    syncing: Cell<bool>,
+}
+
+impl ToggleSync {
+    /// Reflect an external state change without running the toggle handler.
+    fn set(&self, button: &gtk4::ToggleButton, active: bool) {
+        self.syncing.set(true);
+        button.set_active(active);
+        self.syncing.set(false);
+        self.update_airplane();
+    }
+
+    /// Airplane mode is on when every available radio is off.
+    fn update_airplane(&self) {
+        let bt_on = self.bluetooth.is_visible() && self.bluetooth.is_active();
+        let airplane = !self.wifi.is_active() && !bt_on;
+        if self.airplane.is_active() != airplane {
+            self.syncing.set(true);
+            self.airplane.set_active(airplane);
+            self.syncing.set(false);
+        }
+    }
+}
+
+/// Set up the quick toggles strip.
+pub(super) fn setup_quick_toggles(widgets: &PanelWidgets, state: Rc<RefCell<AppState>>) {
+    let QuickToggles {
+        wifi,
+        bluetooth,
+        airplane,
+        night_mode,
+        ..
+    } = &widgets.quick_toggles;
+    let sync = Rc::new(ToggleSync {
+        wifi: wifi.clone(),
+        bluetooth: bluetooth.clone(),
+        airplane: airplane.clone(),
+        syncing: Cell::new(false),
+    });
+
+    setup_wifi_toggle(Rc::clone(&state), Rc::clone(&sync));
+    setup_bluetooth_toggle(state, Rc::clone(&sync));
+
+    // Airplane: flip both radios; their own handlers do the D-Bus work
+    {
+        let sync = Rc::clone(&sync);
+        airplane.connect_toggled(move |btn| {
+            if sync.syncing.get() {
+                return;
+            }
+            let radios_on = !btn.is_active();
+            log::info!("Quick toggle: airplane mode {}", if radios_on { "off" } else { "on" });
+            sync.wifi.set_active(radios_on);
+            if sync.bluetooth.is_visible() {
+                sync.bluetooth.set_active(radios_on);
+            }
+        });
+    }
+
+    // Night mode: mirror the controls panel moon button, which owns the state
+    let night_btn = widgets.controls.night_mode_btn().clone();
+    let night_scale = widgets.controls.night_mode_scale().clone();
+    night_mode.set_active(night_scale.is_sensitive());
+    {
+        let night_btn = night_btn.clone();
+        let night_scale = night_scale.clone();
+        night_mode.connect_toggled(move |btn| {
+            if btn.is_active() != night_scale.is_sensitive() {
+                night_btn.emit_clicked();
+            }
+            // Night mode manager unavailable — the moon button did nothing
+            if btn.is_active() != night_scale.is_sensitive() {
+                btn.set_active(night_scale.is_sensitive());
+            }
+        });
+    }
+    {
+        let night_mode = night_mode.clone();
+        night_scale.connect_sensitive_notify(move |scale| {
+            if night_mode.is_active() != scale.is_sensitive() {
+                night_mode.set_active(scale.is_sensitive());
+            }
+        });
+    }
+}
+
+/// Drive the WiFi radio from its toggle and follow NM `WirelessEnabled`.
+fn setup_wifi_toggle(state: Rc<RefCell<AppState>>, sync: Rc<ToggleSync>) {
+    {
+        let state = Rc::clone(&state);
+        let sync_cb = Rc::clone(&sync);
+        sync.wifi.connect_toggled(move |btn| {
+            if sync_cb.syncing.get() {
+                return;
+            }
+            sync_cb.update_airplane();
+            let enabled = btn.is_active();
+            let state = Rc::clone(&state);
+            let sync = Rc::clone(&sync_cb);
+            glib::spawn_future_local(async move {
+                let wifi = get_wifi(&state);
+                if let Err(e) = wifi.set_wifi_enabled(enabled).await {
+                    log::error!("Quick toggle: failed to set WiFi: {e}");
+                    sync.set(&sync.wifi, !enabled);
+                }
+            });
+        });
+    }
+
+    glib::spawn_future_local(async move {
+        let wifi = get_wifi(&state);
+        match wifi.is_wifi_enabled().await {
+            Ok(enabled) => sync.set(&sync.wifi, enabled),
+            Err(e) => log::error!("Quick toggle: failed to read WiFi state: {e}"),
+        }
+
+        let nm = match NetworkManagerProxy::new(wifi.connection()).await {
+            Ok(p) => p,
+            Err(e) => {
+                log::error!("Failed to create NM proxy for quick toggles: {e}");
+                return;
+            }
+        };
+
+        let mut stream = nm.receive_wireless_enabled_changed().await;
+        while let Some(change) = stream.next().await {
+            if let Ok(enabled) = change.get().await {
+                sync.set(&sync.wifi, enabled);
+            }
+        }
+    });
+}
+
+/// Drive the Bluetooth adapter from its toggle and follow BlueZ `Powered`.
+///
+/// The button stays hidden when no adapter shows up.
+fn setup_bluetooth_toggle(state: Rc<RefCell<AppState>>, sync: Rc<ToggleSync>) {
+    {
+        let state = Rc::clone(&state);
+        let sync_cb = Rc::clone(&sync);
+        sync.bluetooth.connect_toggled(move |btn| {
+            if sync_cb.syncing.get() {
+                return;
+            }
+            sync_cb.update_airplane();
+            let powered = btn.is_active();
+            let state = Rc::clone(&state);
+            let sync = Rc::clone(&sync_cb);
+            glib::spawn_future_local(async move {
+                let Some(bt) = get_bt(&state) else {
+                    return;
+                };
+                match bt.set_powered(powered).await {
+                    Ok(()) if powered => reconnect_trusted_audio(&state),
+                    Ok(()) => {}
+                    Err(e) => {
+                        log::error!("Quick toggle: failed to set Bluetooth power: {e}");
+                        sync.set(&sync.bluetooth, !powered);
+                    }
+                }
+            });
+        });
+    }
+
+    glib::spawn_future_local(async move {
+        // Wait until the BT manager is initialized (setup_bluetooth runs concurrently)
+        let mut attempts = 0;
+        let bt = loop {
+            if let Some(bt) = get_bt(&state) {
+                break bt;
+            }
+            attempts += 1;
+            if attempts > 10 {
+                log::debug!("Quick toggles: no Bluetooth adapter, hiding toggle");
+                return;
+            }
+            glib::timeout_future(std::time::Duration::from_millis(500)).await;
+        };
+
+        sync.bluetooth.set_visible(true);
+
+        // Follow the managed adapter; a switch replaces the watcher
+        let (switched_tx, switched_rx) = async_channel::unbounded();
+        state.borrow_mut().bt_adapter_switched = Some(switched_tx);
+        let mut bt = bt;
+        loop {
+            let switched = {
+                let watch = std::pin::pin!(watch_powered(&bt, &sync));
+                let next = std::pin::pin!(switched_rx.recv());
+                match futures_util::future::select(watch, next).await {
+                    // Stream ended (adapter gone); wait for the next switch
+                    Either::Left(((), next)) => next.await,
+                    Either::Right((switched, _)) => switched,
+                }
+            };
+            match switched {
+                Ok(new) => bt = new,
+                Err(_) => return,
+            }
+        }
+    });
+}
+
+/// Show `bt`'s adapter power on the toggle until its `Powered` stream ends.
+async fn watch_powered(bt: &BluetoothManager, sync: &ToggleSync) {
+    let adapter = match bt.adapter_proxy().await {
+        Ok(p) => p,
+        Err(e) => {
+            log::error!("Failed to create adapter proxy for quick toggles: {e}");
+            return;
+        }
+    };
+
+    match adapter.powered().await {
+        Ok(powered) => sync.set(&sync.bluetooth, powered),
+        Err(e) => log::error!("Quick toggle: failed to read Bluetooth power: {e}"),
+    }
+
+    let mut stream = adapter.receive_powered_changed().await;
+    while let Some(change) = stream.next().await {
+        if let Ok(powered) = change.get().await {
+            sync.set(&sync.bluetooth, powered);
+        }
+    }
+}
@@ -2,18 +2,28 @@
 
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::time::Instant;
 
 use gtk4::glib;
 use gtk4::prelude::*;
 
+use crate::dbus::network_manager::is_scan_rate_limited;
 use crate::ui::window::PanelWidgets;
 
 use super::{AppState, get_wifi, refresh_list};
 
-const WIFI_SCAN_RESULT_WAIT_MS: u64 = 2500;
+/// Upper bound on waiting for NM to report a finished scan.
+const WIFI_SCAN_TIMEOUT_MS: u64 = 8000;
 const WIFI_AUTO_SCAN_INTERVAL_MS: u64 = 15000;
 /// Interval for the background reconnect scan when panel is hidden and disconnected.
 const WIFI_BG_RECONNECT_INTERVAL_MS: u64 = 60_000;
+/// How long to hold off after NM rejected a scan as too frequent.
+const WIFI_SCAN_RATE_LIMIT_BACKOFF_SECS: u64 = 10;
+
+/// Timer passed to `WifiManager::request_scan_and_wait`.
+pub(super) fn scan_timeout() -> impl std::future::Future<Output = ()> {
+    glib::timeout_future(std::time::Duration::from_millis(WIFI_SCAN_TIMEOUT_MS))
+}
 
 /// Poll the scan_requested flag and trigger scan+refresh when set.
 /// This runs on the GTK main thread via glib::timeout_add_local.
@@ -43,13 +53,9 @@ pub(super) fn setup_scan_on_show(
                 }
 
                 // Scan and refresh
-                if let Err(e) = wifi.request_scan().await {
+                if let Err(e) = wifi.request_scan_and_wait(scan_timeout()).await {
                     log::warn!("Scan-on-show scan failed: {e}");
                 }
-                glib::timeout_future(std::time::Duration::from_millis(
-                    WIFI_SCAN_RESULT_WAIT_MS,
-                ))
-                .await;
                 refresh_list(&state, &list_box, &status).await;
             });
         }
@@ -57,6 +63,62 @@ pub(super) fn setup_scan_on_show(
     });
 }
 
+/// Tick the "Updated Xs ago" label under the network list once per second
+/// and grey out the list when results are older than `stale_scan_secs`.
+///
+/// Runs only while the panel is shown; see `stop_scan_age_indicator`.
+pub(super) fn start_scan_age_indicator(
+    state: Rc<RefCell<AppState>>,
+    label: gtk4::Label,
+    list_box: gtk4::ListBox,
+) {
+    if state.borrow().wifi_scan_age_source.is_some() {
+        return;
+    }
+
+    let source = glib::timeout_add_seconds_local(1, {
+        let state = Rc::clone(&state);
+        move || {
+            let (last_scan, stale_after) = {
+                let st = state.borrow();
+                (st.wifi_last_scan, st.wifi_stale_after_secs)
+            };
+
+            let Some(last_scan) = last_scan else {
+                label.set_visible(false);
+                list_box.remove_css_class("stale");
+                return glib::ControlFlow::Continue;
+            };
+
+            let secs = last_scan.elapsed().as_secs();
+            let text = match secs {
+                0..=4 => "Updated just now".to_string(),
+                5..=59 => format!("Updated {secs}s ago"),
+                _ => format!("Updated {} min ago", secs / 60),
+            };
+            label.set_text(&text);
+            label.set_visible(true);
+
+            if secs > stale_after {
+                list_box.add_css_class("stale");
+                label.add_css_class("stale");
+            } else {
+                list_box.remove_css_class("stale");
+                label.remove_css_class("stale");
+            }
+            glib::ControlFlow::Continue
+        }
+    });
+    state.borrow_mut().wifi_scan_age_source = Some(source);
+}
+
+/// Stop ticking the scan age label (panel hidden).
+pub(super) fn stop_scan_age_indicator(state: &Rc<RefCell<AppState>>) {
+    if let Some(id) = state.borrow_mut().wifi_scan_age_source.take() {
+        id.remove();
+    }
+}
+
 /// Initial state: check WiFi status and trigger first scan.
 pub(super) fn setup_initial_state(widgets: &PanelWidgets, state: Rc<RefCell<AppState>>) {
     let switch = widgets.wifi_switch.clone();
@@ -74,13 +136,10 @@ pub(super) fn setup_initial_state(widgets: &PanelWidgets, state: Rc<RefCell<AppS
             Err(e) => log::error!("Failed to get WiFi state: {e}"),
         }
 
-        // Trigger initial scan
-        if let Err(e) = wifi.request_scan().await {
+        // Trigger initial scan and wait for NM to populate APs
+        if let Err(e) = wifi.request_scan_and_wait(scan_timeout()).await {
             log::warn!("Initial scan failed: {e}");
         }
-
-        // Brief delay to let NM populate APs after scan
-        glib::timeout_future(std::time::Duration::from_millis(WIFI_SCAN_RESULT_WAIT_MS)).await;
         refresh_list(&state, &list_box, &status).await;
 
         // Hide spinner, show network list
@@ -103,6 +162,13 @@ pub(super) fn run_manual_scan(
     scan_btn.grab_focus();
     scan_btn.set_sensitive(false);
 
+    if scan_cooldown_remaining(&state).is_some() {
+        release_scan_button(&state, &scan_btn);
+        return;
+    }
+    let cooldown_secs = state.borrow().config.scan_cooldown_secs;
+    hold_off_scans(&state, std::time::Duration::from_secs(cooldown_secs));
+
     glib::spawn_future_local(async move {
         run_wifi_scan(
             state,
@@ -237,6 +303,54 @@ pub(super) fn stop_wifi_bg_reconnect(state: &Rc<RefCell<AppState>>) {
     }
 }
 
+/// Time left before another scan may be requested, if any.
+fn scan_cooldown_remaining(state: &Rc<RefCell<AppState>>) -> Option<std::time::Duration> {
+    let not_before = state.borrow().wifi_scan_not_before?;
+    let left = not_before.saturating_duration_since(Instant::now());
+    (!left.is_zero()).then_some(left)
+}
+
+/// Don't request scans for `duration` (keeps the later deadline if one is set).
+fn hold_off_scans(state: &Rc<RefCell<AppState>>, duration: std::time::Duration) {
+    let until = Instant::now() + duration;
+    let mut st = state.borrow_mut();
+    if st.wifi_scan_not_before.is_none_or(|current| current < until) {
+        st.wifi_scan_not_before = Some(until);
+    }
+}
+
+/// Re-enable the scan button, counting down first while a cooldown applies.
+fn release_scan_button(state: &Rc<RefCell<AppState>>, scan_btn: &gtk4::Button) {
+    let Some(left) = scan_cooldown_remaining(state) else {
+        scan_btn.set_sensitive(true);
+        return;
+    };
+
+    let until = Instant::now() + left;
+    let show_remaining = {
+        let scan_btn = scan_btn.clone();
+        move || {
+            let secs = until.saturating_duration_since(Instant::now()).as_secs_f64().ceil() as u64;
+            if secs == 0 {
+                scan_btn.set_icon_name("view-refresh-symbolic");
+                scan_btn.set_tooltip_text(Some("Scan"));
+                scan_btn.remove_css_class("cooling-down");
+                scan_btn.set_sensitive(true);
+                return glib::ControlFlow::Break;
+            }
+            scan_btn.set_label(&format!("{secs}s"));
+            scan_btn.set_tooltip_text(Some(&format!("Scan available in {secs}s")));
+            glib::ControlFlow::Continue
+        }
+    };
+
+    scan_btn.set_sensitive(false);
+    scan_btn.add_css_class("cooling-down");
+    if show_remaining() == glib::ControlFlow::Continue {
+        glib::timeout_add_local(std::time::Duration::from_millis(250), show_remaining);
+    }
+}
+
 struct ManualWifiScanUi {
     scan_btn: gtk4::Button,
     spinner: gtk4::Spinner,
@@ -256,15 +370,15 @@ async fn run_wifi_scan(
         }
     }
 
-    {
+    let busy = {
         let mut st = state.borrow_mut();
-        if st.wifi_scan_in_progress {
-            if let Some(ui) = manual_ui {
-                ui.scan_btn.set_sensitive(true);
-            }
-            return;
+        std::mem::replace(&mut st.wifi_scan_in_progress, true)
+    };
+    if busy {
+        if let Some(ui) = manual_ui {
+            release_scan_button(&state, &ui.scan_btn);
         }
-        st.wifi_scan_in_progress = true;
+        return;
     }
     let _guard = ScanGuard(Rc::clone(&state));
 
@@ -275,7 +389,7 @@ async fn run_wifi_scan(
                 ui.spinner.set_spinning(false);
                 ui.spinner.set_visible(false);
                 ui.scrolled.set_visible(true);
-                ui.scan_btn.set_sensitive(true);
+                release_scan_button(&state, &ui.scan_btn);
             }
             status.set_text("Wi-Fi is disabled");
             return;
@@ -292,25 +406,51 @@ async fn run_wifi_scan(
         ui.scrolled.set_visible(false);
     }
 
-    if let Err(e) = wifi.request_scan().await {
+    // Background scans skip the request while held off; results still refresh
+    let held_off = manual_ui.is_none() && scan_cooldown_remaining(&state).is_some();
+    let scan = if held_off {
+        Ok(false)
+    } else {
+        wifi.request_scan_and_wait(scan_timeout()).await
+    };
+
+    if let Err(e) = &scan
+        && is_scan_rate_limited(e)
+    {
+        log::info!("NM rejected the scan as too frequent, backing off");
+        hold_off_scans(
+            &state,
+            std::time::Duration::from_secs(WIFI_SCAN_RATE_LIMIT_BACKOFF_SECS),
+        );
+        refresh_list(&state, &list_box, &status).await;
+        status.set_text("Scanned recently — showing latest results");
+        if let Some(ui) = manual_ui {
+            ui.spinner.set_spinning(false);
+            ui.spinner.set_visible(false);
+            ui.scrolled.set_visible(true);
+            release_scan_button(&state, &ui.scan_btn);
+        }
+        return;
+    }
+
+    if let Err(e) = scan {
         log::error!("Scan failed: {e}");
         status.set_text("Scan failed");
         if let Some(ui) = manual_ui {
             ui.spinner.set_spinning(false);
             ui.spinner.set_visible(false);
             ui.scrolled.set_visible(true);
-            ui.scan_btn.set_sensitive(true);
+            release_scan_button(&state, &ui.scan_btn);
         }
         return;
     }
 
-    glib::timeout_future(std::time::Duration::from_millis(WIFI_SCAN_RESULT_WAIT_MS)).await;
     refresh_list(&state, &list_box, &status).await;
 
     if let Some(ui) = manual_ui {
         ui.spinner.set_spinning(false);
         ui.spinner.set_visible(false);
         ui.scrolled.set_visible(true);
-        ui.scan_btn.set_sensitive(true);
+        release_scan_button(&state, &ui.scan_btn);
     }
 }
@@ -0,0 +1,89 @@
+//! Network search — filters the WiFi list by SSID substring.
+//!
+//! The Bluetooth list has its own filter (see `bluetooth.rs`); Ctrl+F here
+//! opens whichever search bar belongs to the active tab.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::prelude::*;
+use gtk4::{gdk, glib};
+
+use crate::ui::window::PanelWidgets;
+
+use super::AppState;
+
+/// Install the list filter and the Ctrl+F shortcut.
+///
+/// Rows are matched through `wifi_row_ssids`; separators are hidden while a
+/// query is active and the "No networks found" placeholder is always shown.
+pub(super) fn setup_network_search(widgets: &PanelWidgets, state: Rc<RefCell<AppState>>) {
+    let list_box = widgets.network_list_box.clone();
+    let search_entry = widgets.search_entry.clone();
+    let search_bar = widgets.search_bar.clone();
+
+    {
+        let search_entry = search_entry.clone();
+        list_box.set_filter_func(move |row| {
+            let query = search_entry.text().to_lowercase();
+            if query.is_empty() {
+                return true;
+            }
+            // The list is being rebuilt; re-filtered once the SSIDs are stored
+            let Ok(st) = state.try_borrow() else {
+                return true;
+            };
+            match st.wifi_row_ssids.get(row.index() as usize) {
+                Some(Some(ssid)) => ssid.to_lowercase().contains(&query),
+                Some(None) => false,
+                None => true,
+            }
+        });
+    }
+
+    {
+        let list_box = list_box.clone();
+        search_entry.connect_search_changed(move |_| list_box.invalidate_filter());
+    }
+
+    // Clear the query when the bar is closed so the full list comes back
+    {
+        let search_entry = search_entry.clone();
+        search_bar.connect_search_mode_enabled_notify(move |bar| {
+            if !bar.is_search_mode() {
+                search_entry.set_text("");
+            }
+        });
+    }
+
+    // Ctrl+F: toggle the device search on the BT tab; otherwise jump to the
+    // Wi-Fi networks view and toggle its search bar
+    let wifi_tab = widgets.wifi_tab.clone();
+    let bt_tab = widgets.bt_tab.clone();
+    let networks_tab = widgets.wifi_networks_tab.clone();
+    let bt_search_bar = widgets.bt_search_bar.clone();
+    let bt_search_entry = widgets.bt_search_entry.clone();
+    let key_controller = gtk4::EventControllerKey::new();
+    key_controller.connect_key_pressed(move |_, key, _, modifiers| {
+        if modifiers.contains(gdk::ModifierType::CONTROL_MASK)
+            && matches!(key, gdk::Key::f | gdk::Key::F)
+        {
+            if bt_tab.is_active() {
+                bt_search_bar.set_search_mode(!bt_search_bar.is_search_mode());
+                if bt_search_bar.is_search_mode() {
+                    bt_search_entry.grab_focus();
+                }
+                return glib::Propagation::Stop;
+            }
+            wifi_tab.set_active(true);
+            networks_tab.set_active(true);
+            search_bar.set_search_mode(!search_bar.is_search_mode());
+            if search_bar.is_search_mode() {
+                search_entry.grab_focus();
+            }
+            return glib::Propagation::Stop;
+        }
+        glib::Propagation::Proceed
+    });
+    widgets.window.add_controller(key_controller);
+}
@@ -1,13 +1,20 @@
 //! Shortcuts — keyboard and D-Bus triggered actions (Escape, reload).
+//!
+//! Reloads also happen on their own when `config.toml` or `style.css` is
+//! saved, via a GFileMonitor on the config directory.
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
+use gtk4::gio;
 use gtk4::glib;
+use gtk4::prelude::*;
 
 use crate::ui::window::PanelWidgets;
 
-use super::{AppState, refresh_list};
+use super::{AppState, get_notifier, refresh_list};
 
 /// Set up Escape key handler to hide panel (with proper state tracking).
 pub(super) fn setup_escape_key(widgets: &PanelWidgets, panel_state: crate::daemon::PanelState) {
@@ -28,25 +35,134 @@ pub(super) fn setup_escape_key(widgets: &PanelWidgets, panel_state: crate::daemo
 pub(super) fn setup_reload_on_request(
     widgets: &PanelWidgets,
     state: Rc<RefCell<AppState>>,
-    reload_requested: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    reload_requested: Arc<AtomicBool>,
 ) {
+    let window = widgets.window.clone();
     let list_box = widgets.network_list_box.clone();
     let status = widgets.status_label.clone();
+    let monitor = watch_config_files(Arc::clone(&reload_requested));
+    let restore_offered = Rc::new(Cell::new(false));
+    offer_config_restore(&state, &reload_requested, &restore_offered);
 
     glib::timeout_add_local(std::time::Duration::from_millis(200), move || {
-        if reload_requested.swap(false, std::sync::atomic::Ordering::Relaxed) {
+        // Owned here so the watch lives as long as the poll
+        let _ = &monitor;
+        if reload_requested.swap(false, Ordering::Relaxed) {
             log::info!("Reload requested - refreshing network list with new config");
+            crate::config::Config::remember_good();
+            state.borrow_mut().config = crate::config::Config::load();
+            offer_config_restore(&state, &reload_requested, &restore_offered);
             let state = Rc::clone(&state);
             let list_box = list_box.clone();
             let status = status.clone();
+            let window = window.clone();
 
             glib::spawn_future_local(async move {
-                // Reload CSS
+                // Reload CSS and re-apply layer-shell placement
                 crate::ui::window::reload_css();
-                // Refresh network list (which will reload config for icons)
+                crate::ui::window::reapply_config(&window);
+                super::weak_signal::setup_weak_signal_alerts(&state, &status);
+                // Refresh network list (picks up the new config for icons)
                 refresh_list(&state, &list_box, &status).await;
             });
         }
         glib::ControlFlow::Continue
     });
 }
+
+/// If `config.toml` is broken and the backup is in use, offer to restore the
+/// backup from a notification (same as `--restore-config`), then reload.
+///
+/// At most one offer is shown at a time.
+fn offer_config_restore(
+    state: &Rc<RefCell<AppState>>,
+    reload_requested: &Arc<AtomicBool>,
+    offered: &Rc<Cell<bool>>,
+) {
+    if offered.get() || !crate::config::Config::using_backup() {
+        return;
+    }
+    offered.set(true);
+
+    let state = Rc::clone(state);
+    let reload_requested = Arc::clone(reload_requested);
+    let offered = Rc::clone(offered);
+    glib::spawn_future_local(async move {
+        let clicked = match get_notifier(&state).await {
+            Ok(notifier) => notifier
+                .notify_with_action(
+                    "dialog-warning-symbolic",
+                    "config.toml has errors",
+                    "Using the last good config. Click to restore it; \
+                     the broken file is kept as config.toml.broken.",
+                    "Restore",
+                )
+                .await
+                .unwrap_or_else(|e| {
+                    log::warn!("Config restore: failed to notify: {e}");
+                    false
+                }),
+            Err(e) => {
+                log::warn!("Config restore: notifications unavailable: {e}");
+                false
+            }
+        };
+        offered.set(false);
+        if !clicked {
+            return;
+        }
+
+        match crate::config::Config::restore_backup() {
+            Ok(path) => {
+                log::info!("Restored last good config to {}", path.display());
+                reload_requested.store(true, Ordering::Relaxed);
+            }
+            Err(e) => log::error!("Could not restore config backup: {e}"),
+        }
+    });
+}
+
+/// Request a reload whenever `config.toml` or `style.css` is saved.
+///
+/// The directory is watched rather than the files, since editors often save
+/// by writing a new file and renaming it over the old one. Bursts of events
+/// from one save collapse into a single reload through the poll above.
+fn watch_config_files(reload_requested: Arc<AtomicBool>) -> Option<gio::FileMonitor> {
+    let config_file = crate::config::config_file_path()?;
+    let config_dir = crate::config::config_dir()?;
+    let watched = [
+        config_file.file_name()?.to_os_string(),
+        std::ffi::OsString::from("style.css"),
+    ];
+
+    let monitor = match gio::File::for_path(&config_dir)
+        .monitor_directory(gio::FileMonitorFlags::WATCH_MOVES, gio::Cancellable::NONE)
+    {
+        Ok(monitor) => monitor,
+        Err(e) => {
+            log::warn!("Not watching {config_dir:?} for config changes: {e}");
+            return None;
+        }
+    };
+    monitor.connect_changed(move |_, file, other_file, event| {
+        if !matches!(
+            event,
+            gio::FileMonitorEvent::ChangesDoneHint
+                | gio::FileMonitorEvent::Created
+                | gio::FileMonitorEvent::Deleted
+                | gio::FileMonitorEvent::MovedIn
+                | gio::FileMonitorEvent::Renamed
+        ) {
+            return;
+        }
+        let is_watched = |f: &gio::File| {
+            f.basename()
+                .is_some_and(|name| watched.iter().any(|w| name.as_os_str() == w.as_os_str()))
+        };
+        if is_watched(file) || other_file.is_some_and(is_watched) {
+            log::info!("Config files changed, reloading");
+            reload_requested.store(true, Ordering::Relaxed);
+        }
+    });
+    Some(monitor)
+}
@@ -0,0 +1,102 @@
+//! Tray icon — mirrors the Wi-Fi state onto a StatusNotifierItem.
+//!
+//! Only started when `tray_icon` is set. The icon is polled from NM rather
+//! than fed from the list refresh, since live updates pause while the panel
+//! is hidden and the tray is mostly looked at then.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use gtk4::glib;
+
+use crate::daemon::PanelState;
+use crate::dbus::network_manager::WifiManager;
+use crate::dbus::tray::{Tray, TrayEvent, TrayStatus};
+
+use super::{AppState, get_wifi};
+
+/// How often the icon's SSID and signal are re-read.
+const TRAY_REFRESH_SECS: u64 = 5;
+
+/// Register the tray icon and keep it in sync with the Wi-Fi state.
+pub(super) fn setup_tray(state: Rc<RefCell<AppState>>, panel_state: PanelState) {
+    if !state.borrow().config.tray_icon {
+        return;
+    }
+
+    glib::spawn_future_local(async move {
+        let (tray, events) = match Tray::register().await {
+            Ok(registered) => registered,
+            Err(e) => {
+                log::warn!("Failed to serve the tray icon: {e}");
+                return;
+            }
+        };
+
+        {
+            let tray = tray.clone();
+            glib::spawn_future_local(async move {
+                if let Err(e) = tray.keep_registered().await {
+                    log::warn!("Not watching for a tray (StatusNotifierWatcher): {e}");
+                }
+            });
+        }
+
+        {
+            let state = Rc::clone(&state);
+            glib::spawn_future_local(async move {
+                while let Ok(event) = events.recv().await {
+                    match event {
+                        TrayEvent::TogglePanel => panel_state.toggle(),
+                        TrayEvent::ToggleWifi => toggle_wifi(&get_wifi(&state)).await,
+                    }
+                }
+            });
+        }
+
+        let mut shown = None;
+        loop {
+            let status = read_status(&get_wifi(&state)).await;
+            if shown.as_ref() != Some(&status) {
+                match tray.update(status.clone()).await {
+                    Ok(()) => shown = Some(status),
+                    Err(e) => log::warn!("Failed to update tray icon: {e}"),
+                }
+            }
+            glib::timeout_future(Duration::from_secs(TRAY_REFRESH_SECS)).await;
+        }
+    });
+}
+
+async fn read_status(wifi: &WifiManager) -> TrayStatus {
+    let wifi_enabled = wifi.is_wifi_enabled().await.unwrap_or(false);
+    let ssid = if wifi_enabled {
+        wifi.active_ssid().await.ok().flatten()
+    } else {
+        None
+    };
+    let strength = match ssid {
+        Some(_) => wifi.get_link_stats().await.map(|s| s.strength).unwrap_or(0),
+        None => 0,
+    };
+    TrayStatus {
+        wifi_enabled,
+        ssid,
+        strength,
+    }
+}
+
+/// Flip the radio; the panel's switches follow through live updates.
+async fn toggle_wifi(wifi: &WifiManager) {
+    let enabled = match wifi.is_wifi_enabled().await {
+        Ok(enabled) => enabled,
+        Err(e) => {
+            log::error!("Tray: failed to read WiFi state: {e}");
+            return;
+        }
+    };
+    if let Err(e) = wifi.set_wifi_enabled(!enabled).await {
+        log::error!("Tray: failed to set WiFi: {e}");
+    }
+}
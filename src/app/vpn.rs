@@ -15,7 +15,7 @@ use crate::dbus::proxies::NetworkManagerProxy;
 use crate::ui::vpn_list;
 use crate::ui::window::PanelWidgets;
 
-use super::{AppState, PendingVpnAction};
+use super::{AppState, PendingVpnAction, guest};
 use super::vpn_utils::{
     confirm_delete_dialog, find_blocking_active_path_for_connect, humanize_vpn_error,
     launch_nm_connection_editor, update_vpn_header_status,
@@ -123,34 +123,48 @@ pub(super) fn setup_vpn(
         let window = window.clone();
         let import_btn = vpn_import_btn.clone();
         let open_btn = vpn_open_btn.clone();
-        move |_btn| {
-            begin_vpn_work(&state, &import_btn, &open_btn);
-            open_import_dialog(
-                Rc::clone(&state),
-                window.clone(),
-                list_box.clone(),
-                status.clone(),
-                spinner.clone(),
-                scrolled.clone(),
-                import_btn.clone(),
-                open_btn.clone(),
-                {
-                    let state = Rc::clone(&state);
-                    let import_btn = import_btn.clone();
-                    let open_btn = open_btn.clone();
-                    move || end_vpn_work(&state, &import_btn, &open_btn)
-                },
-            );
+        move |btn| {
+            let state = Rc::clone(&state);
+            let status_for_import = status.clone();
+            let list_box = list_box.clone();
+            let spinner = spinner.clone();
+            let scrolled = scrolled.clone();
+            let window = window.clone();
+            let import_btn = import_btn.clone();
+            let open_btn = open_btn.clone();
+            guest::authorize(btn, &status, "import a VPN profile", move || {
+                begin_vpn_work(&state, &import_btn, &open_btn);
+                open_import_dialog(
+                    Rc::clone(&state),
+                    window.clone(),
+                    list_box.clone(),
+                    status_for_import.clone(),
+                    spinner.clone(),
+                    scrolled.clone(),
+                    import_btn.clone(),
+                    open_btn.clone(),
+                    {
+                        let state = Rc::clone(&state);
+                        let import_btn = import_btn.clone();
+                        let open_btn = open_btn.clone();
+                        move || end_vpn_work(&state, &import_btn, &open_btn)
+                    },
+                );
+            });
         }
     });
 
     vpn_open_btn.connect_clicked({
         let status = status.clone();
         let panel_state = panel_state.clone();
-        move |_btn| {
-            if let Err(e) = launch_nm_connection_editor(None, Some(&panel_state), None) {
-                status.set_text(&format!("Failed to open settings: {e}"));
-            }
+        move |btn| {
+            let status_for_open = status.clone();
+            let panel_state = panel_state.clone();
+            guest::authorize(btn, &status, "open network settings", move || {
+                if let Err(e) = launch_nm_connection_editor(None, Some(&panel_state), None) {
+                    status_for_open.set_text(&format!("Failed to open settings: {e}"));
+                }
+            });
         }
     });
 }
@@ -514,6 +528,8 @@ pub(super) async fn refresh_vpn_list(
             .map(|(k, v)| (k.clone(), v.label.clone()))
             .collect::<std::collections::HashMap<String, String>>()
     };
+    let on_edit = guest::guard2(&list_box, &status, "edit this VPN", on_edit);
+    let on_delete = guest::guard2(&list_box, &status, "delete this VPN", on_delete);
     let _row_paths = vpn_list::populate_vpn_list(
         &list_box,
         &profiles,
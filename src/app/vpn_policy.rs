@@ -0,0 +1,128 @@
+//! VPN enforcement — brings up a chosen VPN on untrusted networks.
+//!
+//! `require_vpn` in the config maps an SSID to a NetworkManager VPN profile
+//! (name or UUID). While connected to that SSID the profile is activated
+//! automatically, and the user is warned if it drops.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::glib;
+
+use crate::dbus::proxies::NetworkManagerProxy;
+use crate::ui::window::PanelWidgets;
+
+use super::vpn_utils::humanize_vpn_error;
+use super::{AppState, get_wifi};
+
+/// Give up re-activating a dropped VPN after this many attempts per SSID.
+const MAX_VPN_ATTEMPTS: u32 = 3;
+
+/// Enforcement progress for the SSID currently connected.
+#[derive(Default)]
+struct Enforcement {
+    ssid: String,
+    attempts: u32,
+    /// The VPN has been seen up on this SSID, so losing it is a drop.
+    was_up: bool,
+}
+
+/// Watch NM active connections and keep the required VPN up.
+pub(super) fn setup_vpn_policy(widgets: &PanelWidgets, state: Rc<RefCell<AppState>>) {
+    let status = widgets.status_label.clone();
+
+    glib::spawn_future_local(async move {
+        let wifi = get_wifi(&state);
+        let nm = match NetworkManagerProxy::new(wifi.connection()).await {
+            Ok(p) => p,
+            Err(e) => {
+                log::error!("Failed to create NM proxy for VPN policy: {e}");
+                return;
+            }
+        };
+
+        let mut stream = nm.receive_active_connections_changed().await;
+        log::info!("VPN policy: watching active connections");
+
+        let mut current = Enforcement::default();
+
+        use futures_util::StreamExt;
+        while stream.next().await.is_some() {
+            // Let NM settle; a WiFi handover fires several changes in a row
+            glib::timeout_future(std::time::Duration::from_millis(500)).await;
+            enforce(&state, &status, &mut current).await;
+        }
+    });
+}
+
+/// Activate the required VPN for the current SSID if it isn't up.
+async fn enforce(state: &Rc<RefCell<AppState>>, status: &gtk4::Label, current: &mut Enforcement) {
+    let wifi = get_wifi(state);
+    let ssid = match wifi.active_ssid().await {
+        Ok(Some(ssid)) => ssid,
+        _ => {
+            *current = Enforcement::default();
+            return;
+        }
+    };
+    if current.ssid != ssid {
+        *current = Enforcement {
+            ssid: ssid.clone(),
+            ..Default::default()
+        };
+    }
+
+    let Some(wanted) = state.borrow().config.require_vpn.get(&ssid).cloned() else {
+        return;
+    };
+
+    let vpn = state.borrow().vpn.clone();
+    let profile = match vpn.list_profiles().await {
+        Ok(profiles) => profiles
+            .into_iter()
+            .find(|p| &p.name == wanted || &p.uuid == wanted),
+        Err(e) => {
+            log::warn!("VPN policy: failed to list profiles: {e}");
+            return;
+        }
+    };
+    let Some(profile) = profile else {
+        log::warn!("VPN policy: profile '{wanted}' required on '{ssid}' not found");
+        status.set_text(&format!("VPN '{wanted}' not found — {ssid} is unprotected"));
+        return;
+    };
+
+    let active = vpn.active_by_connection_path().await.unwrap_or_default();
+    match active.get(&profile.connection_path).map(|a| a.state) {
+        // Activated
+        Some(2) => {
+            current.was_up = true;
+            current.attempts = 0;
+            return;
+        }
+        // Activating / deactivating — wait for the next change
+        Some(1) | Some(3) => return,
+        _ => {}
+    }
+
+    if current.attempts >= MAX_VPN_ATTEMPTS {
+        return;
+    }
+    current.attempts += 1;
+
+    if current.was_up {
+        log::warn!("VPN policy: '{}' dropped on '{ssid}', reactivating", profile.name);
+        status.set_text(&format!("VPN {} dropped — reconnecting", profile.name));
+    } else {
+        log::info!("VPN policy: activating '{}' for '{ssid}'", profile.name);
+        status.set_text(&format!("Starting VPN {} for {ssid}", profile.name));
+    }
+
+    if let Err(e) = vpn.connect(&profile.connection_path).await {
+        log::error!("VPN policy: failed to activate '{}': {e}", profile.name);
+        status.set_text(&format!(
+            "VPN required on {ssid}: {}",
+            humanize_vpn_error(&e.to_string())
+        ));
+    }
+}
@@ -0,0 +1,95 @@
+//! Weak-signal alerts — notify when the connected AP stays below a threshold.
+//!
+//! Polls the active AP strength in the background (panel shown or not). A dip
+//! has to last `weak_signal_secs` before it is reported, and it is reported
+//! once until the signal recovers or the network changes.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use gtk4::glib;
+
+use super::{AppState, get_notifier, get_wifi};
+
+/// How often the active AP strength is sampled.
+const WEAK_SIGNAL_POLL_SECS: u64 = 5;
+
+/// Start (or restart) the background weak-signal monitor.
+///
+/// Reads the threshold once; nothing runs while `weak_signal_threshold` is 0.
+/// Called at setup and again on every config reload.
+pub(super) fn setup_weak_signal_alerts(state: &Rc<RefCell<AppState>>, status: &gtk4::Label) {
+    if let Some(monitor) = state.borrow_mut().weak_signal_monitor.take() {
+        monitor.abort();
+    }
+    let (threshold, secs) = {
+        let st = state.borrow();
+        (st.config.weak_signal_threshold, st.config.weak_signal_secs)
+    };
+    if threshold == 0 {
+        return;
+    }
+    let monitor = glib::spawn_future_local(watch_signal(
+        Rc::clone(state),
+        status.clone(),
+        threshold,
+        secs,
+    ));
+    state.borrow_mut().weak_signal_monitor = Some(monitor);
+}
+
+/// Sample the active AP and alert once a dip below `threshold` lasts `secs`.
+async fn watch_signal(state: Rc<RefCell<AppState>>, status: gtk4::Label, threshold: u8, secs: u64) {
+    // (SSID, when it went weak, already alerted)
+    let mut weak: Option<(String, Instant, bool)> = None;
+
+    loop {
+        glib::timeout_future(Duration::from_secs(WEAK_SIGNAL_POLL_SECS)).await;
+
+        let wifi = get_wifi(&state);
+        let (Ok(Some(ssid)), Ok(stats)) = (wifi.active_ssid().await, wifi.get_link_stats().await)
+        else {
+            weak = None;
+            continue;
+        };
+        if stats.strength >= threshold {
+            weak = None;
+            continue;
+        }
+
+        // A different network starts its own dip
+        if weak
+            .as_ref()
+            .is_none_or(|(weak_ssid, _, _)| *weak_ssid != ssid)
+        {
+            weak = Some((ssid.clone(), Instant::now(), false));
+        }
+        let Some((_, since, alerted)) = &mut weak else {
+            continue;
+        };
+        if *alerted || since.elapsed() < Duration::from_secs(secs) {
+            continue;
+        }
+        *alerted = true;
+
+        log::warn!("Weak signal on '{ssid}': {}%", stats.strength);
+        status.set_text(&format!("Weak signal on {ssid} ({}%)", stats.strength));
+        match get_notifier(&state).await {
+            Ok(notifier) => {
+                let body = format!("{ssid} has been at {}% for over {} s", stats.strength, secs);
+                if let Err(e) = notifier
+                    .notify(
+                        "network-wireless-signal-weak-symbolic",
+                        "Weak Wi-Fi signal",
+                        &body,
+                    )
+                    .await
+                {
+                    log::warn!("Weak signal: failed to notify: {e}");
+                }
+            }
+            Err(e) => log::warn!("Weak signal: notifications unavailable: {e}"),
+        }
+    }
+}
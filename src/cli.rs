@@ -0,0 +1,387 @@
+//! Headless subcommands — `list`, `connect`, `disconnect`, `forget`, `status`.
+//!
+//! These talk to NetworkManager directly through `WifiManager`, so they work
+//! from scripts whether or not the panel daemon is running. `list` and
+//! `status` take `--json` for bars and scripts that want structured output;
+//! `status --follow` keeps printing as the state changes, for waybar `exec`.
+
+use std::time::Duration;
+
+use clap::Subcommand;
+use futures_util::StreamExt;
+use futures_util::future::Either;
+use gtk4::glib;
+use serde::Serialize;
+
+use crate::dbus::access_point::{Network, SecurityType};
+use crate::dbus::network_manager::{ActivationOutcome, Connectivity, WifiManager};
+use crate::dbus::proxies::{DeviceProxy, NetworkManagerProxy};
+
+/// How long `list --rescan` waits for NM to finish scanning.
+const RESCAN_TIMEOUT_SECS: u64 = 10;
+/// How long `connect` waits for the connection to come up.
+const CONNECT_TIMEOUT_SECS: u64 = 30;
+/// How often `status --follow` re-reads the state between NM signals,
+/// which don't cover signal strength.
+const FOLLOW_POLL_SECS: u64 = 5;
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// List visible Wi-Fi networks, strongest first
+    List {
+        /// Scan before listing instead of using NM's cached results
+        #[arg(long)]
+        rescan: bool,
+        /// Print a JSON object instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Connect to a Wi-Fi network
+    Connect {
+        /// Network name
+        ssid: String,
+        /// Password for secured networks without a saved profile
+        #[arg(long)]
+        password: Option<String>,
+        /// The network doesn't broadcast its name
+        #[arg(long)]
+        hidden: bool,
+    },
+    /// Disconnect from the current Wi-Fi network
+    Disconnect,
+    /// Delete the saved profile of a Wi-Fi network
+    Forget {
+        /// Network name
+        ssid: String,
+    },
+    /// Show the Wi-Fi radio, connection and IP state
+    Status {
+        /// Print a JSON object instead of text
+        #[arg(long)]
+        json: bool,
+        /// Keep running and print a line whenever the state changes
+        #[arg(long)]
+        follow: bool,
+    },
+}
+
+/// One entry of `list --json`.
+#[derive(Serialize)]
+struct NetworkEntry {
+    ssid: String,
+    strength: u8,
+    security: String,
+    band: String,
+    frequency_mhz: u32,
+    connected: bool,
+    saved: bool,
+}
+
+/// Output of `status`; fields after `connected` are null when not connected.
+#[derive(Serialize, Default, PartialEq)]
+struct Status {
+    wifi_enabled: bool,
+    connected: bool,
+    ssid: Option<String>,
+    strength: Option<u8>,
+    address: Option<String>,
+    gateway: Option<String>,
+    /// "full", "portal", "limited", "offline" or "unknown"
+    internet: Option<&'static str>,
+}
+
+/// Run a subcommand to completion, exiting with status 1 on failure.
+pub fn run(command: Command) {
+    let result = glib::MainContext::default().block_on(async {
+        let wifi = WifiManager::new()
+            .await
+            .map_err(|e| format!("could not reach NetworkManager: {e}"))?;
+        match command {
+            Command::List { rescan, json } => list(&wifi, rescan, json).await,
+            Command::Connect {
+                ssid,
+                password,
+                hidden,
+            } => connect(&wifi, &ssid, password.as_deref(), hidden).await,
+            Command::Disconnect => disconnect(&wifi).await,
+            Command::Forget { ssid } => forget(&wifi, &ssid).await,
+            Command::Status { json, follow: true } => follow_status(&wifi, json).await,
+            Command::Status { json, .. } => status(&wifi, json).await,
+        }
+    });
+
+    if let Err(e) = result {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    }
+}
+
+async fn list(wifi: &WifiManager, rescan: bool, json: bool) -> Result<(), String> {
+    if rescan && let Err(e) = wifi.request_scan_and_wait(rescan_timeout()).await {
+        // Rate-limited or radio off; the cached results are still useful
+        log::warn!("Scan failed: {e}");
+    }
+    let mut networks = wifi
+        .get_networks(false)
+        .await
+        .map_err(|e| format!("could not list networks: {e}"))?;
+    networks.sort_by_key(|n| std::cmp::Reverse(n.strength));
+
+    if json {
+        let entries: Vec<NetworkEntry> = networks
+            .into_iter()
+            .map(|n| NetworkEntry {
+                security: n.security.to_string(),
+                band: n.band.to_string(),
+                strength: n.strength,
+                frequency_mhz: n.frequency_mhz,
+                connected: n.is_connected,
+                saved: n.is_saved,
+                ssid: n.ssid,
+            })
+            .collect();
+        return print_json(&serde_json::json!({ "networks": entries }));
+    }
+
+    for network in &networks {
+        let marker = if network.is_connected { "*" } else { " " };
+        let saved = if network.is_saved { "  saved" } else { "" };
+        println!(
+            "{marker} {:>3}%  {:<10} {:<7}  {}{saved}",
+            network.strength,
+            network.security.to_string(),
+            network.band.to_string(),
+            network.ssid,
+        );
+    }
+    Ok(())
+}
+
+async fn connect(
+    wifi: &WifiManager,
+    ssid: &str,
+    password: Option<&str>,
+    hidden: bool,
+) -> Result<(), String> {
+    let active_path = if hidden {
+        wifi.connect_hidden(ssid, password).await
+    } else {
+        let network = find_network(wifi, ssid).await?;
+        if network.connection_path.is_none()
+            && network.security != SecurityType::Open
+            && password.is_none()
+        {
+            return Err(format!("'{ssid}' is secured — pass --password"));
+        }
+        wifi.connect_to_network(&network, password).await
+    }
+    .map_err(|e| format!("could not connect to '{ssid}': {e}"))?;
+
+    println!("Connecting to {ssid}...");
+    match wifi
+        .wait_for_activation(&active_path, connect_timeout())
+        .await
+    {
+        Ok(ActivationOutcome::Activated) => {
+            println!("Connected to {ssid}");
+            Ok(())
+        }
+        Ok(ActivationOutcome::Failed(reason)) => Err(format!("failed: {reason}")),
+        Ok(ActivationOutcome::TimedOut) => Err(format!(
+            "still connecting to {ssid} after {CONNECT_TIMEOUT_SECS} s"
+        )),
+        Err(e) => Err(format!("could not follow the connection: {e}")),
+    }
+}
+
+/// Timer passed to `WifiManager::request_scan_and_wait`.
+fn rescan_timeout() -> impl std::future::Future<Output = ()> {
+    glib::timeout_future(Duration::from_secs(RESCAN_TIMEOUT_SECS))
+}
+
+/// Timer passed to `WifiManager::wait_for_activation`.
+fn connect_timeout() -> impl std::future::Future<Output = ()> {
+    glib::timeout_future(Duration::from_secs(CONNECT_TIMEOUT_SECS))
+}
+
+/// Find `ssid` among the visible networks, scanning once if it isn't there.
+async fn find_network(wifi: &WifiManager, ssid: &str) -> Result<Network, String> {
+    for attempt in 0..2 {
+        if attempt > 0 {
+            let _ = wifi.request_scan_and_wait(rescan_timeout()).await;
+        }
+        let network = wifi
+            .find_network(ssid)
+            .await
+            .map_err(|e| format!("could not list networks: {e}"))?;
+        if let Some(network) = network {
+            return Ok(network);
+        }
+    }
+    Err(format!(
+        "'{ssid}' is not in range (use --hidden for hidden networks)"
+    ))
+}
+
+async fn disconnect(wifi: &WifiManager) -> Result<(), String> {
+    let ssid = wifi.active_ssid().await.ok().flatten();
+    wifi.disconnect()
+        .await
+        .map_err(|e| format!("could not disconnect: {e}"))?;
+    match ssid {
+        Some(ssid) => println!("Disconnected from {ssid}"),
+        None => println!("Disconnected"),
+    }
+    Ok(())
+}
+
+async fn forget(wifi: &WifiManager, ssid: &str) -> Result<(), String> {
+    wifi.forget_network(ssid)
+        .await
+        .map_err(|e| format!("could not forget: {e}"))?;
+    println!("Forgot {ssid}");
+    Ok(())
+}
+
+async fn status(wifi: &WifiManager, json: bool) -> Result<(), String> {
+    let status = read_status(wifi).await?;
+    if json {
+        return print_json(&status);
+    }
+
+    if !status.wifi_enabled {
+        println!("Wi-Fi: off");
+        return Ok(());
+    }
+    println!("Wi-Fi: on");
+    let Some(ssid) = &status.ssid else {
+        println!("Not connected");
+        return Ok(());
+    };
+    println!("Connected to: {ssid}");
+    if let Some(strength) = status.strength {
+        println!("Signal: {strength}%");
+    }
+    if let Some(address) = &status.address {
+        println!("Address: {address}");
+    }
+    if let Some(gateway) = &status.gateway {
+        println!("Gateway: {gateway}");
+    }
+    let internet = match status.internet {
+        Some("full") => "yes",
+        Some("portal") => "sign-in required",
+        Some("limited" | "offline") => "no",
+        _ => "unknown",
+    };
+    println!("Internet: {internet}");
+    Ok(())
+}
+
+/// Print the status now and again on every change, until NM goes away.
+///
+/// Woken by NM's radio, connectivity and device state signals, with a
+/// periodic re-read for signal strength; unchanged states aren't printed.
+async fn follow_status(wifi: &WifiManager, json: bool) -> Result<(), String> {
+    let subscribe_failed = |e: zbus::Error| format!("could not watch NetworkManager: {e}");
+    let nm = NetworkManagerProxy::new(wifi.connection())
+        .await
+        .map_err(subscribe_failed)?;
+    let device = DeviceProxy::builder(wifi.connection())
+        .path(wifi.wifi_device_path().to_owned())
+        .map_err(subscribe_failed)?
+        .build()
+        .await
+        .map_err(subscribe_failed)?;
+    let radio = nm.receive_wireless_enabled_changed().await.map(|_| ());
+    let connectivity = nm.receive_connectivity_changed().await.map(|_| ());
+    let device_state = device
+        .receive_state_changed()
+        .await
+        .map_err(subscribe_failed)?
+        .map(|_| ());
+    let mut changes = Box::pin(futures_util::stream::select(
+        radio,
+        futures_util::stream::select(connectivity, device_state),
+    ));
+
+    let mut last = None;
+    loop {
+        let status = read_status(wifi).await?;
+        if last.as_ref() != Some(&status) {
+            if json {
+                print_json(&status)?;
+            } else {
+                println!("{}", status_line(&status));
+            }
+            last = Some(status);
+        }
+
+        let tick = std::pin::pin!(glib::timeout_future(Duration::from_secs(FOLLOW_POLL_SECS)));
+        if let Either::Left((None, _)) = futures_util::future::select(changes.next(), tick).await {
+            return Err("lost the connection to NetworkManager".to_string());
+        }
+    }
+}
+
+/// One-line summary for `status --follow` without `--json`.
+fn status_line(status: &Status) -> String {
+    if !status.wifi_enabled {
+        return "Wi-Fi off".to_string();
+    }
+    let Some(ssid) = &status.ssid else {
+        return "Not connected".to_string();
+    };
+    let mut line = ssid.clone();
+    if let Some(strength) = status.strength {
+        line.push_str(&format!(" {strength}%"));
+    }
+    match status.internet {
+        Some("portal") => line.push_str(" (sign-in required)"),
+        Some("limited" | "offline") => line.push_str(" (no internet)"),
+        _ => {}
+    }
+    line
+}
+
+async fn read_status(wifi: &WifiManager) -> Result<Status, String> {
+    let wifi_enabled = wifi
+        .is_wifi_enabled()
+        .await
+        .map_err(|e| format!("could not read Wi-Fi state: {e}"))?;
+    let ssid = if wifi_enabled {
+        wifi.active_ssid().await.ok().flatten()
+    } else {
+        None
+    };
+    let Some(ssid) = ssid else {
+        return Ok(Status {
+            wifi_enabled,
+            ..Status::default()
+        });
+    };
+
+    let ip = wifi.ipv4_info().await.unwrap_or_default();
+    let internet = match wifi.connectivity().await {
+        Ok(Connectivity::Full) => "full",
+        Ok(Connectivity::Portal) => "portal",
+        Ok(Connectivity::Limited) => "limited",
+        Ok(Connectivity::Offline) => "offline",
+        Ok(Connectivity::Unknown) | Err(_) => "unknown",
+    };
+    Ok(Status {
+        wifi_enabled,
+        connected: true,
+        ssid: Some(ssid),
+        strength: wifi.get_link_stats().await.ok().map(|s| s.strength),
+        address: ip.address,
+        gateway: ip.gateway,
+        internet: Some(internet),
+    })
+}
+
+fn print_json(value: &impl Serialize) -> Result<(), String> {
+    let text = serde_json::to_string(value).map_err(|e| format!("could not encode JSON: {e}"))?;
+    println!("{text}");
+    Ok(())
+}
@@ -1,7 +1,13 @@
-//! Application configuration loaded from `~/.config/wifi-manager/config.toml`.
+//! Application configuration loaded from `~/.config/wifi-manager/config.toml`
+//! (or the file given with `--config`).
 
 use serde::Deserialize;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Config file given with `--config`, used instead of the default location.
+static PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
 
 /// Window position on screen.
 #[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
@@ -19,6 +25,68 @@ pub enum Position {
     CenterLeft,
 }
 
+/// Order of the WiFi network list (the connected network always comes first).
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortOrder {
+    /// Saved networks, then an "Available networks" section; A–Z within each
+    #[default]
+    SavedFirst,
+    /// Strongest signal first
+    Strength,
+    /// A–Z by SSID
+    Alphabetical,
+    /// Highest band (6 → 5 → 2.4 GHz) first, then by signal
+    BandFirst,
+}
+
+/// Order of the "Available" Bluetooth section (connected and paired
+/// devices stay A–Z).
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum BtSortOrder {
+    /// A–Z by name
+    #[default]
+    Name,
+    /// Strongest RSSI first, so the device next to you floats to the top
+    Proximity,
+}
+
+/// Numeric signal readout shown next to the signal icon.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SignalDisplay {
+    /// Icon only
+    #[default]
+    Icon,
+    /// Strength percentage as reported by NM
+    Percent,
+    /// Approximate dBm, derived from the percentage
+    Dbm,
+}
+
+/// Layer-shell keyboard interactivity for the panel.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeyboardMode {
+    /// Grab the keyboard while the panel is shown
+    Exclusive,
+    /// Focus follows the compositor's normal rules
+    #[default]
+    OnDemand,
+    /// Never receive keyboard input
+    None,
+}
+
+/// Layer-shell layer the panel is placed on.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PanelLayer {
+    #[default]
+    Top,
+    Overlay,
+}
+
 
 /// Application configuration.
 #[derive(Debug, Clone, Deserialize)]
@@ -39,9 +107,80 @@ pub struct Config {
     /// Margin from left edge in pixels
     pub margin_left: i32,
 
+    /// Layer-shell keyboard mode (default: "on-demand")
+    pub keyboard_mode: KeyboardMode,
+
+    /// Layer-shell layer (default: "top")
+    pub layer: PanelLayer,
+
+    /// Output to show the panel on, by connector name (e.g. "DP-1"), or
+    /// "focused" to let the compositor pick (default: unset = "focused")
+    pub output: Option<String>,
+
+    /// List empty-SSID access points as "Hidden network" (default: false)
+    pub show_hidden_networks: bool,
+
+    /// Notify when a saved network comes into range while disconnected (default: true)
+    pub notify_known_networks: bool,
+
+    /// Ask before joining a network without encryption (default: true)
+    pub warn_open_networks: bool,
+
+    /// Alert when the connected signal stays below this percentage (0 = off, default: 0)
+    pub weak_signal_threshold: u8,
+
+    /// Seconds the signal must stay weak before alerting (default: 30)
+    pub weak_signal_secs: u64,
+
+    /// Start with unnamed Bluetooth devices (bare MAC addresses) hidden;
+    /// the header filter button toggles it per session (default: false)
+    pub bt_hide_unnamed: bool,
+
+    /// Read battery levels of connected LE devices straight from their GATT
+    /// Battery Service when BlueZ doesn't report one (default: false)
+    pub ble_gatt_battery: bool,
+
+    /// Give up pairing after this many seconds, including time spent typing
+    /// a PIN; 0 waits until BlueZ gives up (default: 60)
+    pub bt_pairing_timeout: u64,
+
+    /// Power the Bluetooth adapter on when switching to the BT tab instead
+    /// of showing "Bluetooth disabled" (default: false)
+    pub bt_auto_power_on: bool,
+
+    /// Connect trusted headphones and speakers again after Bluetooth is
+    /// switched on from the panel (default: true)
+    pub bt_reconnect_audio: bool,
+
+    /// How long "Make discoverable" keeps this machine visible to other
+    /// Bluetooth devices, in seconds; 0 keeps it on until toggled off
+    /// (default: 180)
+    pub bt_discoverable_timeout: u32,
+
+    /// Make the adapter discoverable (or hidden) at startup; unset leaves
+    /// BlueZ's state alone (default: unset)
+    pub bt_discoverable: Option<bool>,
+
+    /// Accept (or refuse) incoming pairing requests, set at startup; unset
+    /// leaves BlueZ's state alone (default: unset)
+    pub bt_pairable: Option<bool>,
+
+    /// Seconds until pairable mode turns itself off, set at startup;
+    /// 0 = never (default: unset)
+    pub bt_pairable_timeout: Option<u32>,
+
+    /// Network list order (default: "saved-first")
+    pub sort_order: SortOrder,
+
+    /// Bluetooth "Available" section order (default: "name")
+    pub bt_sort_order: BtSortOrder,
+
     /// Custom signal strength icons [weak, fair, good, strong]
     pub signal_icons: [String; 4],
 
+    /// Numeric signal next to the icon: "icon", "percent" or "dbm" (default: "icon")
+    pub signal_display: SignalDisplay,
+
     /// Custom lock icon for secured networks
     pub lock_icon: String,
 
@@ -50,6 +189,32 @@ pub struct Config {
 
     /// Whether to show the panel when the daemon starts (default: false)
     pub show_on_start: bool,
+
+    /// Show a tray icon (StatusNotifierItem) with the Wi-Fi state (default: false)
+    pub tray_icon: bool,
+
+    /// Scan results older than this many seconds are greyed out (default: 60)
+    pub stale_scan_secs: u64,
+
+    /// Minimum seconds between manual Wi-Fi scans (default: 10)
+    pub scan_cooldown_secs: u64,
+
+    /// Guest mode: disable forget/unpair/password reveal/settings editing (default: false)
+    pub guest_mode: bool,
+
+    /// Optional PIN that unlocks restricted actions in guest mode (empty = none)
+    pub guest_pin: String,
+
+    /// Captive-portal login commands keyed by SSID, run via `sh -c`
+    /// when NetworkManager detects a portal on that network
+    pub portal_login: HashMap<String, String>,
+
+    /// VPN profiles (name or UUID) to activate automatically, keyed by SSID
+    pub require_vpn: HashMap<String, String>,
+
+    /// Bluetooth row icons keyed by BlueZ icon name (e.g. "audio-headset"),
+    /// replacing the built-in glyph
+    pub bt_icons: HashMap<String, String>,
 }
 
 impl Default for Config {
@@ -60,22 +225,51 @@ impl Default for Config {
             margin_right: 10,
             margin_bottom: 10,
             margin_left: 10,
+            keyboard_mode: KeyboardMode::default(),
+            layer: PanelLayer::default(),
+            output: None,
+            show_hidden_networks: false,
+            notify_known_networks: true,
+            warn_open_networks: true,
+            weak_signal_threshold: 0,
+            weak_signal_secs: 30,
+            bt_hide_unnamed: false,
+            ble_gatt_battery: false,
+            bt_pairing_timeout: 60,
+            bt_auto_power_on: false,
+            bt_reconnect_audio: true,
+            bt_discoverable_timeout: 180,
+            bt_discoverable: None,
+            bt_pairable: None,
+            bt_pairable_timeout: None,
+            sort_order: SortOrder::default(),
+            bt_sort_order: BtSortOrder::default(),
             signal_icons: [
                 "󰤟".to_string(),  // weak
                 "󰤢".to_string(),  // fair
                 "󰤥".to_string(),  // good
                 "󰤨".to_string(),  // strong
             ],
+            signal_display: SignalDisplay::default(),
             lock_icon: "󰌾".to_string(),
             saved_icon: "".to_string(),
             show_on_start: false,
+            tray_icon: false,
+            stale_scan_secs: 60,
+            scan_cooldown_secs: 10,
+            guest_mode: false,
+            guest_pin: String::new(),
+            portal_login: HashMap::new(),
+            require_vpn: HashMap::new(),
+            bt_icons: HashMap::new(),
         }
     }
 }
 
 impl Config {
     /// Load config from `~/.config/wifi-manager/config.toml`.
-    /// Falls back to defaults if file doesn't exist or has errors.
+    /// Falls back to the last good copy (`config.toml.bak`) if the file has
+    /// errors, and to defaults if it doesn't exist or no backup is usable.
     pub fn load() -> Self {
         let Some(path) = config_file_path() else {
             return Self::default();
@@ -89,12 +283,12 @@ impl Config {
         match std::fs::read_to_string(&path) {
             Ok(contents) => match toml::from_str::<Config>(&contents) {
                 Ok(config) => {
-                    log::info!("Config loaded from {:?}", path);
+                    log::debug!("Config loaded from {:?}", path);
                     config
                 }
                 Err(e) => {
-                    log::warn!("Failed to parse config file: {e}, using defaults");
-                    Self::default()
+                    log::error!("Failed to parse config file: {e}");
+                    Self::load_backup(&path)
                 }
             },
             Err(e) => {
@@ -103,10 +297,107 @@ impl Config {
             }
         }
     }
+
+    /// Use the last config that parsed, instead of silently resetting
+    /// every setting because of one typo.
+    fn load_backup(path: &Path) -> Self {
+        let backup = crate::state::backup_path(path);
+        let config = std::fs::read_to_string(&backup)
+            .ok()
+            .and_then(|contents| toml::from_str::<Config>(&contents).ok());
+        match config {
+            Some(config) => {
+                log::warn!(
+                    "Using last good config from {:?}; fix config.toml or run \
+                     `wifi-manager --restore-config`",
+                    backup
+                );
+                config
+            }
+            None => {
+                log::warn!("No usable config backup, using defaults");
+                Self::default()
+            }
+        }
+    }
+
+    /// Whether `config.toml` fails to parse and `load()` is running on the
+    /// backup, i.e. there is something for `restore_backup()` to restore.
+    pub fn using_backup() -> bool {
+        let Some(path) = config_file_path() else {
+            return false;
+        };
+        let parses = |path: &Path| {
+            std::fs::read_to_string(path)
+                .is_ok_and(|contents| toml::from_str::<Config>(&contents).is_ok())
+        };
+        path.exists() && !parses(&path) && parses(&crate::state::backup_path(&path))
+    }
+
+    /// Read the config from `path` for the rest of the process (`--config`).
+    ///
+    /// A `style.css` next to it replaces the user theme as well.
+    pub fn set_path(path: PathBuf) {
+        let _ = PATH_OVERRIDE.set(path);
+    }
+
+    /// Keep a copy of `config.toml` as `config.toml.bak` if it parses, for
+    /// recovery from later edits.
+    ///
+    /// Called at startup and on reload; `load()` runs in refresh paths and
+    /// doesn't touch the backup.
+    pub fn remember_good() {
+        let Some(path) = config_file_path() else {
+            return;
+        };
+        let Ok(contents) = std::fs::read_to_string(&path) else {
+            return;
+        };
+        if toml::from_str::<Config>(&contents).is_err() {
+            return;
+        }
+        let backup = crate::state::backup_path(&path);
+        if std::fs::read_to_string(&backup).is_ok_and(|saved| saved == contents) {
+            return;
+        }
+        if let Err(e) = crate::state::write_atomic_without_backup(&backup, &contents) {
+            log::warn!("Failed to update config backup: {e}");
+        }
+    }
+
+    /// Put the last good config back in place of a broken `config.toml`.
+    ///
+    /// The broken file is kept with a `.broken` suffix. Returns the path
+    /// of the restored config.
+    pub fn restore_backup() -> std::io::Result<PathBuf> {
+        let path = config_file_path().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::NotFound, "HOME is not set")
+        })?;
+        let backup = crate::state::backup_path(&path);
+        let contents = std::fs::read_to_string(&backup)?;
+        if let Err(e) = toml::from_str::<Config>(&contents) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("backup is not valid either: {e}"),
+            ));
+        }
+
+        if path.exists() {
+            let mut broken = path.file_name().unwrap_or_default().to_os_string();
+            broken.push(".broken");
+            std::fs::rename(&path, path.with_file_name(broken))?;
+        }
+        crate::state::write_atomic(&path, &contents)?;
+        Ok(path)
+    }
 }
 
-/// Get the config file path: ~/.config/wifi-manager/config.toml
-fn config_file_path() -> Option<PathBuf> {
+/// Get the config file path: ~/.config/wifi-manager/config.toml unless
+/// overridden with `--config`
+pub fn config_file_path() -> Option<PathBuf> {
+    if let Some(path) = PATH_OVERRIDE.get() {
+        return Some(path.clone());
+    }
     let home = std::env::var("HOME").ok()?;
     Some(
         PathBuf::from(home)
@@ -115,3 +406,12 @@ fn config_file_path() -> Option<PathBuf> {
             .join("config.toml"),
     )
 }
+
+/// Directory holding the config file, where the user's `style.css` lives.
+pub fn config_dir() -> Option<PathBuf> {
+    let path = config_file_path()?;
+    Some(match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    })
+}
@@ -0,0 +1,148 @@
+//! Bluetooth audio profile switching via PulseAudio / PipeWire card profiles.
+//!
+//! A connected headset shows up as one `bluez_card.<address>` card offering an
+//! A2DP profile (stereo playback, no microphone) and an HFP/HSP profile (mono
+//! with microphone). Switching mode is a card profile change.
+
+use std::fmt;
+use std::time::Duration;
+
+use gtk4::glib;
+
+use libpulse_binding::callbacks::ListResult;
+use libpulse_binding::context::{Context, FlagSet as ContextFlagSet, State};
+use libpulse_binding::proplist::Proplist;
+use libpulse_glib_binding::Mainloop;
+
+/// Which kind of card profile to switch a headset to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioMode {
+    /// A2DP — high-quality playback, microphone off
+    HighQuality,
+    /// HFP / HSP — headset with microphone, low-quality audio
+    Headset,
+}
+
+impl fmt::Display for AudioMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AudioMode::HighQuality => write!(f, "high-quality audio"),
+            AudioMode::Headset => write!(f, "headset mode"),
+        }
+    }
+}
+
+impl AudioMode {
+    /// Whether a card profile name belongs to this mode.
+    ///
+    /// Covers PulseAudio (`a2dp_sink`, `headset_head_unit`) and PipeWire
+    /// (`a2dp-sink-aac`, `headset-head-unit-msbc`, ...) naming.
+    fn matches(self, profile: &str) -> bool {
+        match self {
+            AudioMode::HighQuality => profile.starts_with("a2dp"),
+            AudioMode::Headset => {
+                profile.starts_with("headset") || profile.starts_with("handsfree")
+            }
+        }
+    }
+}
+
+/// Short-lived PulseAudio connection used to switch card profiles.
+///
+/// Fields drop in order, so `context` goes before the `mainloop` it runs on.
+pub struct AudioProfiles {
+    context: Context,
+    _mainloop: Mainloop,
+}
+
+impl AudioProfiles {
+    /// Connect to the sound server and wait until the context is ready.
+    pub async fn connect() -> Result<Self, String> {
+        let mut proplist = Proplist::new().ok_or("Failed to create PulseAudio proplist")?;
+        proplist
+            .set_str(
+                libpulse_binding::proplist::properties::APPLICATION_NAME,
+                "wifi-manager",
+            )
+            .map_err(|_| "Failed to set application name in proplist")?;
+
+        let mainloop = Mainloop::new(None).ok_or("Failed to create PulseAudio GLib mainloop")?;
+        let mut context =
+            Context::new_with_proplist(&mainloop, "wifi-manager-audio-profiles", &proplist)
+                .ok_or("Failed to create PulseAudio context")?;
+        context
+            .connect(None, ContextFlagSet::NOFLAGS, None)
+            .map_err(|e| format!("PulseAudio connect error: {}", e))?;
+
+        const MAX_RETRIES: u32 = 50; // 5 seconds at 100ms intervals
+        for _ in 0..MAX_RETRIES {
+            match context.get_state() {
+                State::Ready => {
+                    return Ok(Self {
+                        context,
+                        _mainloop: mainloop,
+                    });
+                }
+                State::Failed | State::Terminated => {
+                    return Err("PulseAudio context failed or terminated".to_string());
+                }
+                _ => glib::timeout_future(Duration::from_millis(100)).await,
+            }
+        }
+        Err("PulseAudio context connection timed out".to_string())
+    }
+
+    /// Switch the card of the Bluetooth device at `address` to `mode`.
+    ///
+    /// Picks the highest-priority available profile of that kind, so PipeWire
+    /// codec variants (e.g. mSBC over CVSD) are chosen automatically.
+    pub async fn set_mode(&self, address: &str, mode: AudioMode) -> Result<(), String> {
+        let card = card_name(address);
+
+        let (tx, rx) = async_channel::bounded(1);
+        self.context
+            .introspect()
+            .get_card_info_by_name(&card, move |res| {
+                if let ListResult::Item(info) = res {
+                    let profile = info
+                        .profiles
+                        .iter()
+                        .filter(|p| p.available)
+                        .filter(|p| p.name.as_deref().is_some_and(|n| mode.matches(n)))
+                        .max_by_key(|p| p.priority)
+                        .and_then(|p| p.name.as_deref().map(str::to_string));
+                    let _ = tx.try_send(profile);
+                }
+            });
+        let profile = match rx.recv().await {
+            Ok(Some(profile)) => profile,
+            Ok(None) => return Err(format!("No {mode} profile available")),
+            Err(_) => return Err("Device has no audio card".to_string()),
+        };
+
+        log::info!("Switching {card} to profile {profile}");
+        let (tx, rx) = async_channel::bounded(1);
+        self.context.introspect().set_card_profile_by_name(
+            &card,
+            &profile,
+            Some(Box::new(move |success| {
+                let _ = tx.try_send(success);
+            })),
+        );
+        match rx.recv().await {
+            Ok(true) => Ok(()),
+            _ => Err(format!("Failed to switch to {mode}")),
+        }
+    }
+}
+
+impl Drop for AudioProfiles {
+    fn drop(&mut self) {
+        self.context.disconnect();
+    }
+}
+
+/// Card name the BlueZ module gives a device, e.g. `bluez_card.AA_BB_CC_DD_EE_FF`.
+fn card_name(address: &str) -> String {
+    format!("bluez_card.{}", address.replace(':', "_"))
+}
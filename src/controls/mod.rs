@@ -2,3 +2,4 @@ pub mod brightness;
 pub mod volume;
 pub mod night_mode;
 pub mod power;
+pub mod audio_profile;
@@ -1,13 +1,22 @@
 //! D-Bus daemon service — exposes Toggle/Show/Hide methods on the session bus.
 //!
-//! This allows `wifi-manager --toggle` to control a running instance.
+//! Bars and scripts use it to control a running instance; the CLI's own
+//! `--toggle`/`--show`/`--hide`/`--reload` go through GApplication instead.
+//! GetNetworks/Connect/Disconnect/Rescan let bars and scripts drive Wi-Fi
+//! through the daemon's own `WifiManager`.
 //! The interface is registered at `com.github.wifi_manager.WifiManager`
 //! on the session bus at path `/com/github/wifi_manager/WifiManager`.
 
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 
+use serde::Serialize;
 use zbus::interface;
+use zbus::object_server::SignalEmitter;
+use zbus::zvariant::{OwnedObjectPath, Type};
+
+use crate::dbus::bluetooth_manager::BluetoothManager;
+use crate::dbus::network_manager::WifiManager;
 
 /// The D-Bus interface name and path.
 pub const DBUS_NAME: &str = "com.github.wifi_manager.Daemon";
@@ -15,6 +24,43 @@ pub const DBUS_PATH: &str = "/com/github/wifi_manager/Daemon";
 
 /// Thread-safe callback type for toggling visibility from D-Bus thread.
 type ToggleFn = Arc<dyn Fn(bool) + Send + Sync>;
+/// Thread-safe callback that switches the panel to a tab.
+type TabFn = Arc<dyn Fn(PanelTab) + Send + Sync>;
+
+/// Panel page that ShowTab() and `--toggle <tab>` can open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanelTab {
+    Wifi,
+    Vpn,
+    Bluetooth,
+}
+
+impl PanelTab {
+    /// Names accepted on the command line and by ShowTab().
+    pub const NAMES: [&str; 3] = ["wifi", "vpn", "bluetooth"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "wifi" => Some(Self::Wifi),
+            "vpn" => Some(Self::Vpn),
+            "bluetooth" => Some(Self::Bluetooth),
+            _ => None,
+        }
+    }
+}
+
+/// Controls values mirrored from the GTK sliders for external bar modules.
+#[derive(Default)]
+pub struct ControlsState {
+    /// Current color temperature in Kelvin (6500 when night mode is off).
+    pub color_temperature: AtomicU32,
+    /// Whether night mode is enabled.
+    pub night_mode: AtomicBool,
+    /// Screen brightness percent (0–100).
+    pub brightness: AtomicU32,
+    /// Output volume percent (0–100+).
+    pub volume: AtomicU32,
+}
 
 /// State shared between the D-Bus service and the GTK window.
 /// Must be Send + Sync because zbus runs on its own async runtime.
@@ -26,8 +72,18 @@ pub struct PanelState {
     pub scan_requested: Arc<AtomicBool>,
     /// Flag set by reload() — polled by GTK main thread to reload config/CSS.
     pub reload_requested: Arc<AtomicBool>,
+    /// Brightness / volume / night mode values exposed as D-Bus properties.
+    pub controls: Arc<ControlsState>,
+    /// Session bus connection of the daemon service, set once registered.
+    connection: Arc<OnceLock<zbus::Connection>>,
+    /// Wi-Fi backend for the network methods; replaced on adapter hotplug.
+    wifi: Arc<Mutex<Option<WifiManager>>>,
+    /// Bluetooth backend for the Bt* methods; replaced on adapter switch.
+    bluetooth: Arc<Mutex<Option<BluetoothManager>>>,
     /// Callback to toggle visibility — dispatches to GTK main thread.
     toggle_fn: ToggleFn,
+    /// Callback to switch tabs, set once the widgets exist.
+    tab_fn: Arc<OnceLock<TabFn>>,
 }
 
 impl PanelState {
@@ -36,19 +92,91 @@ impl PanelState {
             visible: Arc::new(AtomicBool::new(false)),
             scan_requested: Arc::new(AtomicBool::new(false)),
             reload_requested: Arc::new(AtomicBool::new(false)),
+            controls: Arc::new(ControlsState::default()),
+            connection: Arc::new(OnceLock::new()),
+            wifi: Arc::new(Mutex::new(None)),
+            bluetooth: Arc::new(Mutex::new(None)),
             toggle_fn: Arc::new(toggle_fn),
+            tab_fn: Arc::new(OnceLock::new()),
         }
     }
 
+    /// Emit PropertiesChanged for the controls properties.
+    ///
+    /// No-op until the daemon service has been registered.
+    pub async fn emit_controls_changed(&self) -> zbus::Result<()> {
+        let Some(conn) = self.connection.get() else {
+            return Ok(());
+        };
+        let iface_ref = conn
+            .object_server()
+            .interface::<_, DaemonInterface>(DBUS_PATH)
+            .await?;
+        let iface = iface_ref.get().await;
+        let emitter = iface_ref.signal_emitter();
+        iface.color_temperature_changed(emitter).await?;
+        iface.night_mode_changed(emitter).await?;
+        iface.brightness_changed(emitter).await?;
+        iface.volume_changed(emitter).await?;
+        Ok(())
+    }
+
+    /// Hand the network methods the current `WifiManager`.
+    pub fn set_wifi(&self, wifi: WifiManager) {
+        *self.wifi.lock().unwrap() = Some(wifi);
+    }
+
+    /// The `WifiManager` for a D-Bus call, or an error before NM is up.
+    fn wifi(&self) -> zbus::fdo::Result<WifiManager> {
+        self.wifi
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| zbus::fdo::Error::Failed("NetworkManager unavailable".into()))
+    }
+
+    /// Hand the Bluetooth methods the `BluetoothManager` the panel uses.
+    pub fn set_bluetooth(&self, bt: BluetoothManager) {
+        *self.bluetooth.lock().unwrap() = Some(bt);
+    }
+
+    /// The `BluetoothManager` for a D-Bus call, or an error without an adapter.
+    fn bluetooth(&self) -> zbus::fdo::Result<BluetoothManager> {
+        self.bluetooth
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| zbus::fdo::Error::Failed("No Bluetooth adapter".into()))
+    }
+
     pub fn show(&self) {
-        self.visible.store(true, Ordering::Relaxed);
+        let was_visible = self.visible.swap(true, Ordering::Relaxed);
         self.scan_requested.store(true, Ordering::Relaxed);
         (self.toggle_fn)(true);
+        if !was_visible {
+            self.emit_visibility_changed(true);
+        }
     }
 
     pub fn hide(&self) {
-        self.visible.store(false, Ordering::Relaxed);
+        let was_visible = self.visible.swap(false, Ordering::Relaxed);
         (self.toggle_fn)(false);
+        if was_visible {
+            self.emit_visibility_changed(false);
+        }
+    }
+
+    /// Set the callback `show_tab` uses to switch tabs.
+    pub fn set_tab_handler(&self, tab_fn: impl Fn(PanelTab) + Send + Sync + 'static) {
+        let _ = self.tab_fn.set(Arc::new(tab_fn));
+    }
+
+    /// Show the panel already switched to `tab`.
+    pub fn show_tab(&self, tab: PanelTab) {
+        if let Some(tab_fn) = self.tab_fn.get() {
+            tab_fn(tab);
+        }
+        self.show();
     }
 
     pub fn toggle(&self) {
@@ -58,6 +186,81 @@ impl PanelState {
             self.show();
         }
     }
+
+    /// Hide the panel if it's visible, otherwise show it on `tab`.
+    pub fn toggle_tab(&self, tab: PanelTab) {
+        if self.visible.load(Ordering::Relaxed) {
+            self.hide();
+        } else {
+            self.show_tab(tab);
+        }
+    }
+
+    /// Emit VisibilityChanged and PropertiesChanged for `Visible`.
+    ///
+    /// Callable from any thread: the emission runs on the connection's own
+    /// executor. No-op until the daemon service has been registered.
+    fn emit_visibility_changed(&self, visible: bool) {
+        let Some(conn) = self.connection.get() else {
+            return;
+        };
+        let emit_conn = conn.clone();
+        let emit = async move {
+            if let Err(e) = emit_visibility(&emit_conn, visible).await {
+                log::warn!("Failed to emit VisibilityChanged: {e}");
+            }
+        };
+        conn.executor().spawn(emit, "visibility-changed").detach();
+    }
+}
+
+async fn emit_visibility(conn: &zbus::Connection, visible: bool) -> zbus::Result<()> {
+    let iface_ref = conn
+        .object_server()
+        .interface::<_, DaemonInterface>(DBUS_PATH)
+        .await?;
+    let emitter = iface_ref.signal_emitter();
+    DaemonInterface::visibility_changed(emitter, visible).await?;
+    iface_ref.get().await.visible_changed(emitter).await?;
+    Ok(())
+}
+
+/// One entry of GetNetworks(), D-Bus signature `(sysbb)`.
+#[derive(Serialize, Type)]
+struct NetworkInfo {
+    ssid: String,
+    /// Signal strength percent (0–100).
+    strength: u8,
+    /// "Open", "WPA2", "WPA3", "Enterprise" or "WEP".
+    security: String,
+    connected: bool,
+    saved: bool,
+}
+
+/// One entry of BtDevices(), D-Bus signature `(sssbb)`.
+#[derive(Serialize, Type)]
+struct BtDeviceInfo {
+    address: String,
+    name: String,
+    /// "Audio", "Input", "Mouse", "Phone", … or "Device".
+    kind: String,
+    paired: bool,
+    connected: bool,
+}
+
+fn failed(e: zbus::Error) -> zbus::fdo::Error {
+    zbus::fdo::Error::Failed(e.to_string())
+}
+
+/// Object path of the device with `address`, matched case-insensitively.
+async fn device_path(bt: &BluetoothManager, address: &str) -> zbus::fdo::Result<String> {
+    bt.get_devices()
+        .await
+        .map_err(failed)?
+        .into_iter()
+        .find(|d| d.address.eq_ignore_ascii_case(address))
+        .map(|d| d.device_path)
+        .ok_or_else(|| zbus::fdo::Error::Failed(format!("No device {address}")))
 }
 
 /// D-Bus interface implementation — exposed on the session bus.
@@ -85,22 +288,156 @@ impl DaemonInterface {
         self.state.hide();
     }
 
+    /// Show the panel on a tab: "wifi", "vpn" or "bluetooth".
+    fn show_tab(&self, name: String) -> zbus::fdo::Result<()> {
+        log::info!("D-Bus ShowTab('{name}') called");
+        let tab = PanelTab::from_name(&name).ok_or_else(|| {
+            zbus::fdo::Error::InvalidArgs(format!(
+                "Unknown tab '{name}', expected one of {}",
+                PanelTab::NAMES.join(", ")
+            ))
+        })?;
+        self.state.show_tab(tab);
+        Ok(())
+    }
+
     /// Reload config and CSS.
     fn reload(&self) {
         log::info!("D-Bus Reload() called");
         self.state.reload_requested.store(true, Ordering::Relaxed);
     }
 
+    /// Visible Wi-Fi networks, strongest first.
+    async fn get_networks(&self) -> zbus::fdo::Result<Vec<NetworkInfo>> {
+        let wifi = self.state.wifi()?;
+        let mut networks = wifi.get_networks(false).await.map_err(failed)?;
+        networks.sort_by_key(|n| std::cmp::Reverse(n.strength));
+        Ok(networks
+            .into_iter()
+            .map(|n| NetworkInfo {
+                security: n.security.to_string(),
+                strength: n.strength,
+                connected: n.is_connected,
+                saved: n.is_saved,
+                ssid: n.ssid,
+            })
+            .collect())
+    }
+
+    /// Connect to a visible network; pass an empty password for saved or
+    /// open networks.
+    ///
+    /// Returns NM's ActiveConnection path as soon as activation has started.
+    async fn connect(&self, ssid: String, password: String) -> zbus::fdo::Result<OwnedObjectPath> {
+        log::info!("D-Bus Connect('{ssid}') called");
+        let wifi = self.state.wifi()?;
+        let network = wifi
+            .find_network(&ssid)
+            .await
+            .map_err(failed)?
+            .ok_or_else(|| zbus::fdo::Error::Failed(format!("'{ssid}' is not in range")))?;
+        let password = (!password.is_empty()).then_some(password.as_str());
+        let active = wifi
+            .connect_to_network(&network, password)
+            .await
+            .map_err(failed)?;
+        OwnedObjectPath::try_from(active).map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Disconnect from the current Wi-Fi network.
+    async fn disconnect(&self) -> zbus::fdo::Result<()> {
+        log::info!("D-Bus Disconnect() called");
+        self.state.wifi()?.disconnect().await.map_err(failed)
+    }
+
+    /// Ask NM for a Wi-Fi scan; results arrive via GetNetworks().
+    async fn rescan(&self) -> zbus::fdo::Result<()> {
+        log::info!("D-Bus Rescan() called");
+        self.state.wifi()?.request_scan().await.map_err(failed)
+    }
+
+    /// Bluetooth devices known to the adapter, connected first.
+    async fn bt_devices(&self) -> zbus::fdo::Result<Vec<BtDeviceInfo>> {
+        let bt = self.state.bluetooth()?;
+        let mut devices = bt.get_devices().await.map_err(failed)?;
+        devices.sort_by_key(|d| (!d.connected, !d.paired));
+        Ok(devices
+            .into_iter()
+            .map(|d| BtDeviceInfo {
+                kind: d.category.to_string(),
+                paired: d.paired,
+                connected: d.connected,
+                address: d.address,
+                name: d.display_name,
+            })
+            .collect())
+    }
+
+    /// Power the Bluetooth adapter on or off.
+    async fn bt_power(&self, on: bool) -> zbus::fdo::Result<()> {
+        log::info!("D-Bus BtPower({on}) called");
+        self.state
+            .bluetooth()?
+            .set_powered(on)
+            .await
+            .map_err(failed)
+    }
+
+    /// Connect a paired device by address, e.g. "AA:BB:CC:DD:EE:FF".
+    async fn bt_connect(&self, address: String) -> zbus::fdo::Result<()> {
+        log::info!("D-Bus BtConnect('{address}') called");
+        let bt = self.state.bluetooth()?;
+        let path = device_path(&bt, &address).await?;
+        bt.connect_device(&path).await.map_err(failed)
+    }
+
+    /// Disconnect a device by address.
+    async fn bt_disconnect(&self, address: String) -> zbus::fdo::Result<()> {
+        log::info!("D-Bus BtDisconnect('{address}') called");
+        let bt = self.state.bluetooth()?;
+        let path = device_path(&bt, &address).await?;
+        bt.disconnect_device(&path).await.map_err(failed)
+    }
+
+    /// Emitted when the panel is shown (`true`) or hidden (`false`).
+    #[zbus(signal)]
+    async fn visibility_changed(emitter: &SignalEmitter<'_>, visible: bool) -> zbus::Result<()>;
+
     /// Check if the panel is visible.
     #[zbus(property)]
     fn visible(&self) -> bool {
         self.state.visible.load(Ordering::Relaxed)
     }
+
+    /// Current color temperature in Kelvin.
+    #[zbus(property)]
+    fn color_temperature(&self) -> u32 {
+        self.state.controls.color_temperature.load(Ordering::Relaxed)
+    }
+
+    /// Whether night mode is enabled.
+    #[zbus(property)]
+    fn night_mode(&self) -> bool {
+        self.state.controls.night_mode.load(Ordering::Relaxed)
+    }
+
+    /// Screen brightness percent.
+    #[zbus(property)]
+    fn brightness(&self) -> u32 {
+        self.state.controls.brightness.load(Ordering::Relaxed)
+    }
+
+    /// Output volume percent.
+    #[zbus(property)]
+    fn volume(&self) -> u32 {
+        self.state.controls.volume.load(Ordering::Relaxed)
+    }
 }
 
 /// Register the D-Bus service on the session bus.
 /// Returns the connection (keep alive for the daemon's lifetime).
 pub async fn register_service(state: PanelState) -> zbus::Result<zbus::Connection> {
+    let connection = Arc::clone(&state.connection);
     let iface = DaemonInterface { state };
 
     let conn = zbus::connection::Builder::session()?
@@ -108,49 +445,8 @@ pub async fn register_service(state: PanelState) -> zbus::Result<zbus::Connectio
         .serve_at(DBUS_PATH, iface)?
         .build()
         .await?;
+    let _ = connection.set(conn.clone());
 
     log::info!("D-Bus daemon service registered: {DBUS_NAME}");
     Ok(conn)
 }
-
-/// Check if another instance is already running (name is taken on session bus).
-pub async fn is_instance_running() -> bool {
-    let conn = match zbus::Connection::session().await {
-        Ok(c) => c,
-        Err(_) => return false,
-    };
-
-    let result = conn
-        .call_method(
-            Some(DBUS_NAME),
-            DBUS_PATH,
-            Some("org.freedesktop.DBus.Peer"),
-            "Ping",
-            &(),
-        )
-        .await;
-
-    result.is_ok()
-}
-
-/// Send a Toggle() call to the running daemon instance.
-pub async fn send_toggle() -> zbus::Result<()> {
-    let conn = zbus::Connection::session().await?;
-
-    conn.call_method(Some(DBUS_NAME), DBUS_PATH, Some(DBUS_NAME), "Toggle", &())
-        .await?;
-
-    log::info!("Toggle sent to running instance");
-    Ok(())
-}
-
-/// Send Reload() to the running daemon.
-pub async fn send_reload() -> zbus::Result<()> {
-    let conn = zbus::Connection::session().await?;
-
-    conn.call_method(Some(DBUS_NAME), DBUS_PATH, Some(DBUS_NAME), "Reload", &())
-        .await?;
-
-    log::info!("Reload sent to running instance");
-    Ok(())
-}
@@ -7,6 +7,8 @@ pub enum SecurityType {
     WPA2,
     WPA3,
     Enterprise,
+    /// Privacy bit without any WPA/RSN key management — legacy, easily broken.
+    WEP,
 }
 
 impl fmt::Display for SecurityType {
@@ -16,6 +18,7 @@ impl fmt::Display for SecurityType {
             SecurityType::WPA2 => write!(f, "WPA2"),
             SecurityType::WPA3 => write!(f, "WPA3"),
             SecurityType::Enterprise => write!(f, "Enterprise"),
+            SecurityType::WEP => write!(f, "WEP"),
         }
     }
 }
@@ -25,6 +28,7 @@ impl fmt::Display for SecurityType {
 pub enum Band {
     TwoGhz,
     FiveGhz,
+    SixGhz,
 }
 
 impl fmt::Display for Band {
@@ -32,6 +36,7 @@ impl fmt::Display for Band {
         match self {
             Band::TwoGhz => write!(f, "2.4 GHz"),
             Band::FiveGhz => write!(f, "5 GHz"),
+            Band::SixGhz => write!(f, "6 GHz"),
         }
     }
 }
@@ -39,14 +44,54 @@ impl fmt::Display for Band {
 /// Determine band from frequency in MHz.
 impl Band {
     pub fn from_frequency(freq: u32) -> Self {
-        if freq >= 4900 {
-            Band::FiveGhz
-        } else {
-            Band::TwoGhz
+        match freq {
+            5925.. => Band::SixGhz,
+            4900..=5924 => Band::FiveGhz,
+            _ => Band::TwoGhz,
+        }
+    }
+
+    /// Short badge shown in the row subtitle (None for 2.4 GHz).
+    pub fn badge(&self) -> Option<&'static str> {
+        match self {
+            Band::TwoGhz => None,
+            Band::FiveGhz => Some("5G"),
+            Band::SixGhz => Some("6G"),
         }
     }
 }
 
+/// IEEE channel number for a center frequency in MHz.
+pub fn channel_from_frequency(freq: u32) -> Option<u32> {
+    match freq {
+        2484 => Some(14),
+        2412..=2472 => Some((freq - 2407) / 5),
+        // 4.9 GHz public safety / Japan band
+        4910..=4980 => Some((freq - 4000) / 5),
+        5000..=5900 => Some((freq - 5000) / 5),
+        5935 => Some(2),
+        5955..=7115 => Some((freq - 5950) / 5),
+        _ => None,
+    }
+}
+
+/// Best-effort Wi-Fi generation label from what NM exposes.
+///
+/// NM has no HT/VHT/HE/EHT flags on access points, so only what the channel
+/// proves is labelled: 320 MHz channels only exist in Wi-Fi 7 and 6 GHz
+/// requires at least Wi-Fi 6E. Wi-Fi 4/5/6 can't be told apart reliably and
+/// get no label. `bandwidth_mhz` is 0 on NM releases that predate the
+/// `Bandwidth` property.
+pub fn generation_label(band: &Band, bandwidth_mhz: u32) -> Option<&'static str> {
+    if bandwidth_mhz >= 320 {
+        Some("Wi-Fi 7")
+    } else if *band == Band::SixGhz {
+        Some("Wi-Fi 6E")
+    } else {
+        None
+    }
+}
+
 /// NM AP flags — maps to NM80211ApFlags.
 /// Bit 0x1 = privacy (network requires encryption).
 const NM_802_11_AP_FLAGS_PRIVACY: u32 = 0x1;
@@ -55,13 +100,32 @@ const NM_802_11_AP_FLAGS_PRIVACY: u32 = 0x1;
 const NM_802_11_AP_SEC_KEY_MGMT_PSK: u32 = 0x100;
 const NM_802_11_AP_SEC_KEY_MGMT_SAE: u32 = 0x400;
 const NM_802_11_AP_SEC_KEY_MGMT_802_1X: u32 = 0x200;
+const NM_802_11_AP_SEC_KEY_MGMT_OWE: u32 = 0x800;
+const NM_802_11_AP_SEC_KEY_MGMT_OWE_TM: u32 = 0x1000;
+const NM_802_11_AP_SEC_KEY_MGMT_EAP_SUITE_B_192: u32 = 0x2000;
+
+/// All key-management bits this parser understands; cipher bits live below 0x100.
+const NM_802_11_AP_SEC_KEY_MGMT_KNOWN: u32 = NM_802_11_AP_SEC_KEY_MGMT_PSK
+    | NM_802_11_AP_SEC_KEY_MGMT_802_1X
+    | NM_802_11_AP_SEC_KEY_MGMT_SAE
+    | NM_802_11_AP_SEC_KEY_MGMT_OWE
+    | NM_802_11_AP_SEC_KEY_MGMT_OWE_TM
+    | NM_802_11_AP_SEC_KEY_MGMT_EAP_SUITE_B_192;
+
+/// Open network that still encrypts traffic (OWE / "Enhanced Open").
+pub fn is_enhanced_open(wpa_flags: u32, rsn_flags: u32) -> bool {
+    let owe = NM_802_11_AP_SEC_KEY_MGMT_OWE | NM_802_11_AP_SEC_KEY_MGMT_OWE_TM;
+    (wpa_flags | rsn_flags) & owe != 0
+}
 
 /// Determine security type from NM AP flags.
 pub fn security_from_flags(flags: u32, wpa_flags: u32, rsn_flags: u32) -> SecurityType {
     let all_sec_flags = wpa_flags | rsn_flags;
 
-    // Check for Enterprise (802.1X)
-    if all_sec_flags & NM_802_11_AP_SEC_KEY_MGMT_802_1X != 0 {
+    // Check for Enterprise (802.1X, including WPA3 Suite-B 192-bit)
+    if all_sec_flags & (NM_802_11_AP_SEC_KEY_MGMT_802_1X | NM_802_11_AP_SEC_KEY_MGMT_EAP_SUITE_B_192)
+        != 0
+    {
         return SecurityType::Enterprise;
     }
 
@@ -75,14 +139,58 @@ pub fn security_from_flags(flags: u32, wpa_flags: u32, rsn_flags: u32) -> Securi
         return SecurityType::WPA2;
     }
 
-    // Check basic privacy flag (WEP or similar)
+    // Enhanced Open (OWE) is encrypted but needs no credentials
+    if all_sec_flags & (NM_802_11_AP_SEC_KEY_MGMT_OWE | NM_802_11_AP_SEC_KEY_MGMT_OWE_TM) != 0 {
+        return SecurityType::Open;
+    }
+
+    // A key-management scheme newer than this parser: don't pretend it's open.
+    // Enterprise is the one type we refuse to connect to without a dialog.
+    let unknown_key_mgmt = all_sec_flags & !0xff & !NM_802_11_AP_SEC_KEY_MGMT_KNOWN;
+    if unknown_key_mgmt != 0 {
+        log::debug!("Unknown AP key-management flags {unknown_key_mgmt:#x}");
+        return SecurityType::Enterprise;
+    }
+
+    // Privacy without WPA/RSN key management can only be static WEP
     if flags & NM_802_11_AP_FLAGS_PRIVACY != 0 {
-        return SecurityType::WPA2; // Treat WEP as "secured" — rare these days
+        return SecurityType::WEP;
     }
 
     SecurityType::Open
 }
 
+/// One access point (BSSID) broadcasting a network's SSID.
+#[derive(Debug, Clone)]
+pub struct AccessPoint {
+    /// MAC address, upper-case and colon-separated as NM reports it.
+    pub bssid: String,
+    pub strength: u8,
+    pub band: Band,
+    /// Center frequency in MHz.
+    pub frequency_mhz: u32,
+    /// D-Bus path of the AP object.
+    pub path: String,
+}
+
+/// Format 6 raw bytes as a colon-separated MAC address.
+pub fn format_bssid(bytes: &[u8]) -> Option<String> {
+    if bytes.len() != 6 {
+        return None;
+    }
+    let parts: Vec<String> = bytes.iter().map(|b| format!("{b:02X}")).collect();
+    Some(parts.join(":"))
+}
+
+/// Parse a colon-separated MAC address into its 6 bytes.
+pub fn parse_bssid(bssid: &str) -> Option<Vec<u8>> {
+    let bytes: Vec<u8> = bssid
+        .split(':')
+        .map(|part| u8::from_str_radix(part, 16).ok())
+        .collect::<Option<_>>()?;
+    (bytes.len() == 6).then_some(bytes)
+}
+
 /// A WiFi network as presented to the UI.
 /// May represent multiple APs with the same SSID (deduplicated).
 #[derive(Debug, Clone)]
@@ -90,11 +198,36 @@ pub struct Network {
     pub ssid: String,
     pub strength: u8,
     pub security: SecurityType,
+    /// Open, but encrypted with OWE ("Enhanced Open").
+    pub is_owe: bool,
     pub is_connected: bool,
     pub is_saved: bool,
     pub band: Band,
+    /// Center frequency of the strongest AP in MHz.
+    pub frequency_mhz: u32,
+    /// Channel width in MHz (0 if NM doesn't report it).
+    pub bandwidth_mhz: u32,
+    /// Highest rate the AP advertises, in kbit/s.
+    pub max_bitrate_kbps: u32,
     /// D-Bus path of the strongest AP for this SSID (used when connecting).
     pub ap_path: String,
     /// D-Bus path of the saved connection profile, if any.
     pub connection_path: Option<String>,
+    /// AP broadcasts no SSID; `ssid` is empty and the name must be entered.
+    pub is_hidden: bool,
+    /// Every AP seen for this SSID, strongest first.
+    pub access_points: Vec<AccessPoint>,
+    /// BSSID the saved profile is pinned to (`802-11-wireless.bssid`), if any.
+    pub locked_bssid: Option<String>,
+}
+
+impl Network {
+    /// Name shown in the list ("Hidden network" for empty-SSID APs).
+    pub fn display_name(&self) -> &str {
+        if self.is_hidden {
+            "Hidden network"
+        } else {
+            &self.ssid
+        }
+    }
 }
@@ -2,9 +2,14 @@
 //!
 //! Equivalent to `access_point.rs` for WiFi networks.
 
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::time::Instant;
 
-/// Category of a Bluetooth device, derived from the BlueZ `Icon` property.
+use zbus::zvariant::OwnedValue;
+
+/// Category of a Bluetooth device, derived from the BlueZ `Icon` property
+/// (or its `Class` when there is no icon).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DeviceCategory {
     Audio,
@@ -12,6 +17,8 @@ pub enum DeviceCategory {
     Mouse,
     Computer,
     Phone,
+    /// Watches and fitness sensors (BLE)
+    Wearable,
     Peripheral,
     Other,
 }
@@ -24,6 +31,7 @@ impl fmt::Display for DeviceCategory {
             DeviceCategory::Mouse => write!(f, "Mouse"),
             DeviceCategory::Computer => write!(f, "Computer"),
             DeviceCategory::Phone => write!(f, "Phone"),
+            DeviceCategory::Wearable => write!(f, "Wearable"),
             DeviceCategory::Peripheral => write!(f, "Peripheral"),
             DeviceCategory::Other => write!(f, "Device"),
         }
@@ -55,6 +63,44 @@ impl DeviceCategory {
         }
     }
 
+    /// Category from the Bluetooth Class of Device, for devices BlueZ gives
+    /// no icon.
+    ///
+    /// Major class is bits 8–12; for peripherals, bit 6 means keyboard and
+    /// bit 7 pointing device.
+    pub fn from_class(class: u32) -> Option<Self> {
+        let major = (class >> 8) & 0x1f;
+        let minor = (class >> 2) & 0x3f;
+        Some(match major {
+            0x01 => DeviceCategory::Computer,
+            0x02 => DeviceCategory::Phone,
+            // Video minors (camera, monitor, display, conferencing)
+            0x04 if (0x0B..=0x10).contains(&minor) => DeviceCategory::Peripheral,
+            0x04 => DeviceCategory::Audio,
+            0x05 if class & 0x80 != 0 && class & 0x40 == 0 => DeviceCategory::Mouse,
+            0x05 => DeviceCategory::Input,
+            // Network access point, imaging (printer, scanner, camera)
+            0x03 | 0x06 => DeviceCategory::Peripheral,
+            // Wearable, health
+            0x07 | 0x09 => DeviceCategory::Wearable,
+            _ => return None,
+        })
+    }
+
+    /// Guess a category from advertised GATT services, for LE devices that
+    /// BlueZ gives no icon.
+    pub fn from_gatt_uuids(uuids: &[String]) -> Option<Self> {
+        uuids.iter().find_map(|uuid| match short_uuid(uuid)? {
+            // HID over GATT
+            0x1812 => Some(DeviceCategory::Input),
+            // Heart rate, running / cycling speed, cycling power, fitness machine
+            0x180D | 0x1814 | 0x1816 | 0x1818 | 0x1826 => Some(DeviceCategory::Wearable),
+            // LE Audio: published / common audio capabilities
+            0x1850 | 0x1853 => Some(DeviceCategory::Audio),
+            _ => None,
+        })
+    }
+
     /// Default Nerd Font icon for this device category.
     pub fn default_icon(&self) -> &'static str {
         match self {
@@ -63,12 +109,33 @@ impl DeviceCategory {
             DeviceCategory::Input => "󰌌",       // input
             DeviceCategory::Computer => "󰍹",    // monitor/desktop
             DeviceCategory::Phone => "󰏲",       // phone
+            DeviceCategory::Wearable => "󰖉",    // watch
             DeviceCategory::Peripheral => "󰐻",  // device
             DeviceCategory::Other => "󰂯",       // bluetooth
         }
     }
 }
 
+/// Section of the Bluetooth list, in display order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DeviceSection {
+    Connected,
+    /// Paired but not connected
+    MyDevices,
+    /// Discovered nearby, not paired
+    Available,
+}
+
+impl fmt::Display for DeviceSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeviceSection::Connected => write!(f, "Connected"),
+            DeviceSection::MyDevices => write!(f, "My devices"),
+            DeviceSection::Available => write!(f, "Available"),
+        }
+    }
+}
+
 /// A Bluetooth device as presented to the UI.
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -77,34 +144,313 @@ pub struct BluetoothDevice {
     pub address: String,
     /// Friendly display name (alias preferred, then name, then address).
     pub display_name: String,
+    /// Whether the device has a real Name or a user-set Alias
+    /// (otherwise `display_name` is just the address).
+    pub named: bool,
     /// Category derived from BlueZ icon hint.
     pub category: DeviceCategory,
+    /// BlueZ `Icon` hint (e.g. "audio-headset"), empty if none.
+    pub icon_hint: String,
     /// Whether this device is paired.
     pub paired: bool,
     /// Whether this device is currently connected.
     pub connected: bool,
     /// Whether this device is trusted (auto-connect).
     pub trusted: bool,
+    /// Whether this device is blocked (connections from it are refused).
+    pub blocked: bool,
     /// RSSI signal strength (only valid during discovery, 0 otherwise).
     pub rssi: i16,
+    /// When the device was last heard during discovery (this session only).
+    pub last_seen: Option<Instant>,
+    /// BlueZ `AddressType`: "public" or "random".
+    pub address_type: String,
+    /// Service UUIDs the device advertises.
+    pub uuids: Vec<String>,
+    /// Battery charge in percent, for devices exposing org.bluez.Battery1.
+    pub battery: Option<u8>,
+    /// AVRCP absolute volume (0–127) of the audio transport, when the
+    /// device supports it.
+    pub volume: Option<u16>,
+    /// MediaTransport1 object the volume belongs to.
+    pub transport_path: Option<String>,
+    /// Bluetooth Low Energy only (random address or GATT services only).
+    pub le_only: bool,
+    /// Battery Level characteristic of an LE device without org.bluez.Battery1.
+    pub gatt_battery_path: Option<String>,
     /// D-Bus object path for this device.
     pub device_path: String,
 }
 
 impl BluetoothDevice {
+    /// Glyph for the row: a configured override for the `Icon` hint, then
+    /// the built-in table, then the category default.
+    pub fn glyph<'a>(&self, overrides: &'a HashMap<String, String>) -> &'a str {
+        match overrides.get(&self.icon_hint) {
+            Some(glyph) => glyph,
+            None => icon_glyph(&self.icon_hint).unwrap_or_else(|| self.category.default_icon()),
+        }
+    }
+
     /// Whether the device is currently in range (RSSI only present during discovery).
     pub fn is_in_range(&self) -> bool {
         self.rssi != 0
     }
 
-    /// Sort key: paired first, then connected, then by name.
-    pub fn sort_key(&self) -> (u8, u8, String) {
-        let paired_order = if self.paired { 0 } else { 1 };
-        let connected_order = if self.connected { 0 } else { 1 };
-        (
-            paired_order,
-            connected_order,
-            self.display_name.to_lowercase(),
-        )
+    /// Whether connecting makes sense for this device.
+    ///
+    /// Beacons, tags and sensors that only advertise over LE have nothing to
+    /// connect to; LE keyboards, mice and audio still pair normally.
+    pub fn offers_connect(&self) -> bool {
+        !self.le_only
+            || self.paired
+            || self.connected
+            || matches!(
+                self.category,
+                DeviceCategory::Input | DeviceCategory::Mouse | DeviceCategory::Audio
+            )
+    }
+
+    /// Apply a Device1 `PropertiesChanged` update.
+    ///
+    /// Returns whether anything shown in the row changed; RSSI churn during
+    /// discovery is left to the scan refresh.
+    pub fn apply_changes(&mut self, changed: &HashMap<String, OwnedValue>) -> bool {
+        let flag = |key: &str| {
+            changed
+                .get(key)
+                .and_then(|v| <bool>::try_from(v.clone()).ok())
+        };
+        let mut updated = false;
+
+        if let Some(connected) = flag("Connected") {
+            updated |= self.connected != connected;
+            self.connected = connected;
+        }
+        if let Some(paired) = flag("Paired") {
+            updated |= self.paired != paired;
+            self.paired = paired;
+        }
+        if let Some(trusted) = flag("Trusted") {
+            updated |= self.trusted != trusted;
+            self.trusted = trusted;
+        }
+        if let Some(blocked) = flag("Blocked") {
+            updated |= self.blocked != blocked;
+            self.blocked = blocked;
+        }
+        if let Some(rssi) = changed
+            .get("RSSI")
+            .and_then(|v| <i16>::try_from(v.clone()).ok())
+        {
+            self.rssi = rssi;
+            self.last_seen = Some(Instant::now());
+        }
+        if let Some(alias) = changed
+            .get("Alias")
+            .and_then(|v| <String>::try_from(v.clone()).ok())
+            .filter(|alias| !alias.is_empty())
+        {
+            // BlueZ fills an unset Alias with the address ("AA-BB-...")
+            self.named = alias != self.address.replace(':', "-");
+            updated |= self.display_name != alias;
+            self.display_name = alias;
+        }
+        if changed.contains_key("Name") && !self.named {
+            self.named = true;
+            updated = true;
+        }
+        updated
+    }
+
+    /// Names of the well-known services among the device's UUIDs.
+    pub fn services(&self) -> Vec<&'static str> {
+        let mut names = Vec::new();
+        for name in self
+            .uuids
+            .iter()
+            .filter_map(|uuid| short_uuid(uuid).and_then(service_name))
+        {
+            // Several UUIDs map to one name (e.g. both headset roles)
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// Whether the device shares its internet connection (PAN NAP role).
+    pub fn offers_internet(&self) -> bool {
+        self.uuids
+            .iter()
+            .any(|uuid| short_uuid(uuid) == Some(0x1116))
+    }
+
+    /// Profiles that can be connected on their own with `ConnectProfile`,
+    /// as (UUID, label) pairs.
+    pub fn connectable_profiles(&self) -> Vec<(String, &'static str)> {
+        let mut profiles: Vec<(String, &'static str)> = Vec::new();
+        for uuid in &self.uuids {
+            let Some(label) = short_uuid(uuid).and_then(profile_label) else {
+                continue;
+            };
+            if !profiles.iter().any(|(_, l)| *l == label) {
+                profiles.push((uuid.clone(), label));
+            }
+        }
+        profiles
+    }
+
+    /// Which section of the device list this device belongs in.
+    pub fn section(&self) -> DeviceSection {
+        if self.connected {
+            DeviceSection::Connected
+        } else if self.paired {
+            DeviceSection::MyDevices
+        } else {
+            DeviceSection::Available
+        }
+    }
+
+    /// Sort key: by section (connected, paired, the rest), then by name.
+    pub fn sort_key(&self) -> (DeviceSection, String) {
+        (self.section(), self.display_name.to_lowercase())
+    }
+
+    /// Like `sort_key`, but unpaired devices in range come strongest
+    /// signal first; out-of-range ones follow by name.
+    pub fn proximity_sort_key(&self) -> (DeviceSection, i16, String) {
+        let distance = if self.section() == DeviceSection::Available && self.is_in_range() {
+            // RSSI is negative dBm; closer is higher
+            self.rssi.saturating_neg()
+        } else {
+            i16::MAX
+        };
+        (self.section(), distance, self.display_name.to_lowercase())
+    }
+}
+
+/// 16-bit assigned number of a Bluetooth base UUID
+/// (`0000xxxx-0000-1000-8000-00805f9b34fb`), if it is one.
+fn short_uuid(uuid: &str) -> Option<u16> {
+    let rest = uuid.strip_prefix("0000")?;
+    if !rest
+        .get(4..)?
+        .eq_ignore_ascii_case("-0000-1000-8000-00805f9b34fb")
+    {
+        return None;
     }
+    u16::from_str_radix(rest.get(..4)?, 16).ok()
+}
+
+/// Human-readable name of a service by its 16-bit UUID.
+///
+/// Only services a user would recognise; SDP plumbing and vendor UUIDs are
+/// left out.
+fn service_name(uuid: u16) -> Option<&'static str> {
+    Some(match uuid {
+        0x1101 => "Serial port",
+        0x1105 => "Object push",
+        0x1106 => "File transfer",
+        0x1108 | 0x1112 => "Headset",
+        0x110A => "Audio source",
+        0x110B => "Audio sink",
+        0x110C | 0x110E | 0x110F => "Remote control",
+        0x1115 | 0x1116 => "Network",
+        0x111E | 0x111F => "Hands-free",
+        0x1124 | 0x1812 => "Input",
+        0x112F => "Phonebook",
+        0x1132..=0x1134 => "Messages",
+        0x180D => "Heart rate",
+        0x180F => "Battery",
+        0x1814 => "Running",
+        0x1816 | 0x1818 => "Cycling",
+        0x1850 | 0x1853 => "LE Audio",
+        _ => return None,
+    })
+}
+
+/// Nerd Font glyph for a BlueZ `Icon` hint.
+///
+/// Finer-grained than [`DeviceCategory::default_icon`]: a headset and a
+/// speaker are both "Audio" but get different glyphs here.
+pub fn icon_glyph(hint: &str) -> Option<&'static str> {
+    Some(match hint {
+        "audio-headset" => "󰋎",                 // headset
+        "audio-headphones" => "󰋋",              // headphones
+        "audio-card" | "audio-speakers" => "󰓃", // speaker
+        "input-keyboard" => "󰌌",                // keyboard
+        "input-mouse" => "󰍽",                   // mouse
+        "input-gaming" => "󰊗",                  // gamepad
+        "input-tablet" => "󰓶",                  // tablet
+        "computer" => "󰌢",                      // laptop
+        "phone" => "󰏲",                         // phone
+        "watch" => "󰖉",                         // watch
+        "multimedia-player" => "󰎇",             // music note
+        "camera-photo" => "󰄀",                  // camera
+        "camera-video" => "󰕧",                  // video camera
+        "video-display" => "󰔂",                 // television
+        "printer" => "󰐪",                       // printer
+        "scanner" => "󰚫",                       // scanner
+        "network-wireless" => "󰖩",              // wifi
+        _ => return None,
+    })
+}
+
+/// Menu label of a profile that `ConnectProfile` can bring up alone.
+fn profile_label(uuid: u16) -> Option<&'static str> {
+    Some(match uuid {
+        0x110B => "Audio playback (A2DP)",
+        0x110A => "Audio source (A2DP)",
+        0x1108 => "Headset (HSP)",
+        0x111E => "Hands-free (HFP)",
+        0x1124 => "Input (HID)",
+        0x1116 => "Network (PAN)",
+        _ => return None,
+    })
+}
+
+/// Whether a device is LE-only, from its `AddressType` and `UUIDs`.
+///
+/// Random addresses only exist on LE. A public address with nothing but GATT
+/// services advertised is LE too; classic profiles are 0x1000–0x12FF (SDP,
+/// A2DP, HFP, OPP, HID, PnP info, ...). Without UUIDs we can't tell, so the
+/// device is treated as classic.
+pub fn is_le_only(address_type: &str, uuids: &[String]) -> bool {
+    if address_type == "random" {
+        return true;
+    }
+    !uuids.is_empty()
+        && !uuids
+            .iter()
+            .any(|uuid| short_uuid(uuid).is_some_and(|n| (0x1000..0x1300).contains(&n)))
+}
+
+/// Drop the LE twin of a dual-mode device.
+///
+/// The adapter lists each address once, but a dual-mode device (e.g.
+/// headphones) can also advertise over LE from a random address and show up
+/// a second time. The classic (BR/EDR) entry is the one to connect to, so
+/// that unpaired, unconnected LE entry is removed.
+///
+/// A public LE address is the device's identity, and BlueZ already files it
+/// under the classic entry with that address, so only random-address entries
+/// can be twins. BlueZ doesn't resolve those to an identity until pairing,
+/// which leaves the name as the only link: an unrelated LE device named like
+/// a classic one is hidden too.
+pub fn merge_dual_mode(devices: &mut Vec<BluetoothDevice>) {
+    let classic_names: HashSet<String> = devices
+        .iter()
+        .filter(|d| d.named && !d.le_only)
+        .map(|d| d.display_name.clone())
+        .collect();
+    devices.retain(|d| {
+        let le_twin = d.le_only
+            && d.address_type == "random"
+            && d.named
+            && !d.paired
+            && !d.connected
+            && classic_names.contains(&d.display_name);
+        !le_twin
+    });
 }
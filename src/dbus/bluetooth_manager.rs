@@ -3,16 +3,38 @@
 //! Uses proxy types from `bluez_proxies.rs` to communicate with BlueZ.
 //! Mirrors the structure of `network_manager.rs` for WiFi.
 
-use zbus::zvariant::OwnedObjectPath;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
-use super::bluetooth_device::{BluetoothDevice, DeviceCategory};
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+
+use super::bluetooth_device::{self, BluetoothDevice, DeviceCategory};
 use super::bluez_proxies::*;
+use super::polkit;
+
+/// UUID of the GATT Battery Level characteristic (Battery Service).
+const BATTERY_LEVEL_UUID: &str = "00002a19-0000-1000-8000-00805f9b34fb";
+/// Top of the AVRCP absolute volume range.
+pub const MAX_TRANSPORT_VOLUME: u16 = 127;
+
+/// A Bluetooth adapter as offered in the adapter selector.
+#[derive(Debug, Clone)]
+pub struct AdapterInfo {
+    /// D-Bus object path (e.g. "/org/bluez/hci0").
+    pub path: String,
+    /// Adapter MAC address.
+    pub address: String,
+    /// User-friendly adapter name.
+    pub alias: String,
+}
 
 /// The Bluetooth manager that wraps all BlueZ D-Bus interactions.
 #[derive(Clone)]
 pub struct BluetoothManager {
     connection: zbus::Connection,
     adapter_path: OwnedObjectPath,
+    /// Last transport whose volume was set, reused while a slider is dragged.
+    volume_transport: Arc<Mutex<Option<MediaTransport1Proxy<'static>>>>,
 }
 
 #[allow(dead_code)]
@@ -47,11 +69,12 @@ impl BluetoothManager {
             }
         };
 
-        // Find the first object that implements org.bluez.Adapter1
+        // Pick the first adapter by path (hci0 before hci1) so the choice is stable
         let adapter_path = objects
             .iter()
-            .find(|(_, ifaces)| ifaces.contains_key("org.bluez.Adapter1"))
-            .map(|(path, _)| path.clone());
+            .filter(|(_, ifaces)| ifaces.contains_key("org.bluez.Adapter1"))
+            .map(|(path, _)| path.clone())
+            .min_by(|a, b| a.as_str().cmp(b.as_str()));
 
         let adapter_path = match adapter_path {
             Some(p) => p,
@@ -66,6 +89,89 @@ impl BluetoothManager {
         Some(Self {
             connection,
             adapter_path,
+            volume_transport: Arc::default(),
+        })
+    }
+
+    /// Apply the adapter settings chosen in the config (`bt_pairable`,
+    /// `bt_pairable_timeout`, `bt_discoverable`); unset keys leave BlueZ's
+    /// own state alone. Failures are logged, not fatal.
+    ///
+    /// Called once the adapter to manage has been settled, so the defaults
+    /// don't land on hci0 when another adapter was remembered.
+    pub async fn apply_startup_defaults(&self) {
+        let config = crate::config::Config::load();
+        if config.bt_pairable.is_none()
+            && config.bt_pairable_timeout.is_none()
+            && config.bt_discoverable.is_none()
+        {
+            return;
+        }
+        let adapter = match self.adapter_proxy().await {
+            Ok(adapter) => adapter,
+            Err(e) => {
+                log::warn!("Failed to apply Bluetooth adapter defaults: {e}");
+                return;
+            }
+        };
+
+        // Timeout first: BlueZ arms it when Pairable flips on
+        if let Some(timeout) = config.bt_pairable_timeout
+            && let Err(e) = adapter.set_pairable_timeout(timeout).await
+        {
+            log::warn!("Failed to set Bluetooth pairable timeout: {e}");
+        }
+        if let Some(pairable) = config.bt_pairable
+            && let Err(e) = adapter.set_pairable(pairable).await
+        {
+            log::warn!("Failed to set Bluetooth pairable: {e}");
+        }
+        if let Some(discoverable) = config.bt_discoverable
+            && let Err(e) = self
+                .set_discoverable(discoverable, config.bt_discoverable_timeout)
+                .await
+        {
+            log::warn!("Failed to set Bluetooth discoverable: {e}");
+        }
+    }
+
+    /// List all Bluetooth adapters, sorted by path.
+    pub async fn list_adapters(&self) -> zbus::Result<Vec<AdapterInfo>> {
+        let obj_manager = BluezObjectManagerProxy::new(&self.connection).await?;
+        let objects = obj_manager.get_managed_objects().await?;
+
+        let mut adapters: Vec<AdapterInfo> = objects
+            .iter()
+            .filter_map(|(path, ifaces)| {
+                let props = ifaces.get("org.bluez.Adapter1")?;
+                let string_prop = |key: &str| {
+                    props
+                        .get(key)
+                        .and_then(|v| <String>::try_from(v.clone()).ok())
+                        .unwrap_or_default()
+                };
+                Some(AdapterInfo {
+                    path: path.to_string(),
+                    address: string_prop("Address"),
+                    alias: string_prop("Alias"),
+                })
+            })
+            .collect();
+        adapters.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(adapters)
+    }
+
+    /// A manager for another adapter on the same connection.
+    ///
+    /// Devices and discovery are scoped to the adapter, so switching is just
+    /// swapping the manager.
+    pub fn with_adapter(&self, adapter_path: &str) -> zbus::Result<Self> {
+        let adapter_path = OwnedObjectPath::try_from(adapter_path.to_string())
+            .map_err(|e| zbus::Error::Failure(format!("Invalid adapter path: {e}")))?;
+        Ok(Self {
+            connection: self.connection.clone(),
+            adapter_path,
+            volume_transport: Arc::default(),
         })
     }
 
@@ -127,7 +233,8 @@ impl BluetoothManager {
 
     /// Get a list of all known Bluetooth devices (paired + discovered).
     ///
-    /// Devices are sorted: connected first, then paired, then by name.
+    /// Devices are sorted by section (connected, paired, the rest), then by
+    /// name.
     pub async fn get_devices(&self) -> zbus::Result<Vec<BluetoothDevice>> {
         let obj_manager = BluezObjectManagerProxy::new(&self.connection).await?;
         let objects = obj_manager.get_managed_objects().await?;
@@ -145,10 +252,43 @@ impl BluetoothManager {
                 continue;
             };
 
-            let device = self.parse_device_properties(path_str, props);
+            let mut device = self.parse_device_properties(path_str, props);
+            device.battery = ifaces
+                .get("org.bluez.Battery1")
+                .and_then(|battery| battery.get("Percentage"))
+                .and_then(|v| <u8>::try_from(v.clone()).ok());
+            let child_prefix = format!("{path_str}/");
+            if let Some((transport_path, volume)) = objects
+                .iter()
+                .filter(|(child, _)| child.as_str().starts_with(&child_prefix))
+                .find_map(|(child, child_ifaces)| {
+                    let volume = child_ifaces
+                        .get("org.bluez.MediaTransport1")?
+                        .get("Volume")
+                        .and_then(|v| <u16>::try_from(v.clone()).ok())?;
+                    Some((child.to_string(), volume))
+                })
+            {
+                device.transport_path = Some(transport_path);
+                device.volume = Some(volume);
+            }
+            if device.le_only && device.battery.is_none() {
+                device.gatt_battery_path = objects
+                    .iter()
+                    .filter(|(char_path, _)| char_path.as_str().starts_with(&child_prefix))
+                    .find(|(_, char_ifaces)| {
+                        char_ifaces
+                            .get("org.bluez.GattCharacteristic1")
+                            .and_then(|c| c.get("UUID"))
+                            .and_then(|v| <String>::try_from(v.clone()).ok())
+                            .is_some_and(|uuid| uuid.eq_ignore_ascii_case(BATTERY_LEVEL_UUID))
+                    })
+                    .map(|(char_path, _)| char_path.to_string());
+            }
             devices.push(device);
         }
 
+        bluetooth_device::merge_dual_mode(&mut devices);
         devices.sort_by_cached_key(|a| a.sort_key());
         log::info!("Bluetooth device list: {} devices", devices.len());
         Ok(devices)
@@ -165,6 +305,13 @@ impl BluetoothManager {
         device.connect().await
     }
 
+    /// Connect only one profile of a device (e.g. audio without HID).
+    pub async fn connect_profile(&self, device_path: &str, uuid: &str) -> zbus::Result<()> {
+        let device = self.device_proxy(device_path).await?;
+        log::info!("Connecting profile {uuid} of Bluetooth device: {device_path}");
+        device.connect_profile(uuid).await
+    }
+
     /// Disconnect a connected Bluetooth device.
     pub async fn disconnect_device(&self, device_path: &str) -> zbus::Result<()> {
         let device = self.device_proxy(device_path).await?;
@@ -174,14 +321,102 @@ impl BluetoothManager {
 
     /// Pair with a Bluetooth device.
     ///
-    /// For v1, only "Just Works" pairing is supported (no PIN agent).
-    /// Devices requiring PIN entry should be paired via `bluetoothctl`.
+    /// PIN / passkey prompts go to the agent from `register_agent`; without
+    /// one, only "Just Works" pairing succeeds.
     pub async fn pair_device(&self, device_path: &str) -> zbus::Result<()> {
         let device = self.device_proxy(device_path).await?;
         log::info!("Pairing with Bluetooth device: {device_path}");
         device.pair().await
     }
 
+    /// Abort a pairing started with `pair_device`; it then fails with
+    /// `AuthenticationCanceled`.
+    pub async fn cancel_pairing(&self, device_path: &str) -> zbus::Result<()> {
+        let device = self.device_proxy(device_path).await?;
+        log::info!("Cancelling pairing with Bluetooth device: {device_path}");
+        device.cancel_pairing().await
+    }
+
+    /// Register the pairing agent on this manager's connection.
+    ///
+    /// Returns the stream of prompts the panel has to answer.
+    pub async fn register_agent(
+        &self,
+    ) -> zbus::Result<async_channel::Receiver<super::bluez_agent::AgentEvent>> {
+        super::bluez_agent::register(&self.connection).await
+    }
+
+    /// Set the AVRCP absolute volume (0–127) of an audio transport.
+    pub async fn set_transport_volume(
+        &self,
+        transport_path: &str,
+        volume: u16,
+    ) -> zbus::Result<()> {
+        let cached = self
+            .volume_transport
+            .lock()
+            .unwrap()
+            .clone()
+            .filter(|t| t.inner().path().as_str() == transport_path);
+        let transport = match cached {
+            Some(transport) => transport,
+            None => {
+                let transport = MediaTransport1Proxy::builder(&self.connection)
+                    .path(transport_path.to_owned())?
+                    .build()
+                    .await?;
+                *self.volume_transport.lock().unwrap() = Some(transport.clone());
+                transport
+            }
+        };
+        transport.set_volume(volume.min(MAX_TRANSPORT_VOLUME)).await
+    }
+
+    /// Read an LE device's battery level straight from its GATT characteristic.
+    ///
+    /// For devices whose Battery Service BlueZ doesn't expose as Battery1.
+    pub async fn read_gatt_battery(&self, characteristic_path: &str) -> zbus::Result<u8> {
+        let characteristic = GattCharacteristic1Proxy::builder(&self.connection)
+            .path(characteristic_path)?
+            .build()
+            .await?;
+        let value = characteristic.read_value(std::collections::HashMap::new()).await?;
+        value
+            .first()
+            .map(|level| (*level).min(100))
+            .ok_or_else(|| zbus::Error::Failure("Empty battery level".into()))
+    }
+
+    /// Stream of `PropertiesChanged` signals for any device's Battery1.
+    ///
+    /// Battery levels change without InterfacesAdded, so the device list
+    /// needs this to stay current.
+    pub async fn receive_battery_changes(&self) -> zbus::Result<zbus::MessageStream> {
+        let rule = zbus::MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .sender("org.bluez")?
+            .interface("org.freedesktop.DBus.Properties")?
+            .member("PropertiesChanged")?
+            .arg(0, "org.bluez.Battery1")?
+            .build();
+        zbus::MessageStream::for_match_rule(rule, &self.connection, None).await
+    }
+
+    /// Stream of `PropertiesChanged` signals for any device's Device1.
+    ///
+    /// Connecting, pairing or renaming from another tool (or by the device
+    /// itself) only shows up here.
+    pub async fn receive_device_changes(&self) -> zbus::Result<zbus::MessageStream> {
+        let rule = zbus::MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .sender("org.bluez")?
+            .interface("org.freedesktop.DBus.Properties")?
+            .member("PropertiesChanged")?
+            .arg(0, "org.bluez.Device1")?
+            .build();
+        zbus::MessageStream::for_match_rule(rule, &self.connection, None).await
+    }
+
     /// Set the trusted state of a device (auto-connect on boot).
     pub async fn trust_device(&self, device_path: &str, trusted: bool) -> zbus::Result<()> {
         let device = self.device_proxy(device_path).await?;
@@ -194,6 +429,14 @@ impl BluetoothManager {
         Ok(())
     }
 
+    /// Rename a device; an empty alias reverts to the name it advertises.
+    pub async fn set_alias(&self, device_path: &str, alias: &str) -> zbus::Result<()> {
+        let device = self.device_proxy(device_path).await?;
+        device.set_alias(alias).await?;
+        log::info!("Bluetooth device {device_path} renamed to '{alias}'");
+        Ok(())
+    }
+
     /// Remove (forget/unpair) a device from the adapter.
     pub async fn remove_device(&self, device_path: &str) -> zbus::Result<()> {
         let adapter = self.adapter_proxy().await?;
@@ -215,9 +458,18 @@ impl BluetoothManager {
     }
 
     /// Enable or disable the Bluetooth adapter.
+    ///
+    /// May prompt through the polkit agent where BlueZ requires it.
     pub async fn set_powered(&self, powered: bool) -> zbus::Result<()> {
-        let adapter = self.adapter_proxy().await?;
-        adapter.set_powered(powered).await?;
+        polkit::set_property(
+            &self.connection,
+            "org.bluez",
+            self.adapter_path.as_str(),
+            "org.bluez.Adapter1",
+            "Powered",
+            Value::from(powered),
+        )
+        .await?;
         log::info!(
             "Bluetooth adapter {}",
             if powered { "powered on" } else { "powered off" }
@@ -225,6 +477,42 @@ impl BluetoothManager {
         Ok(())
     }
 
+    /// Check if other devices can currently find this adapter.
+    pub async fn is_discoverable(&self) -> zbus::Result<bool> {
+        let adapter = self.adapter_proxy().await?;
+        adapter.discoverable().await
+    }
+
+    /// Make the adapter discoverable for `timeout_secs` (0 = until turned
+    /// off), or hide it again.
+    pub async fn set_discoverable(
+        &self,
+        discoverable: bool,
+        timeout_secs: u32,
+    ) -> zbus::Result<()> {
+        let adapter = self.adapter_proxy().await?;
+        if discoverable {
+            // Timeout first: BlueZ arms it when Discoverable flips on
+            adapter.set_discoverable_timeout(timeout_secs).await?;
+        }
+        adapter.set_discoverable(discoverable).await?;
+        log::info!(
+            "Bluetooth adapter {}",
+            if discoverable {
+                "discoverable"
+            } else {
+                "hidden"
+            }
+        );
+        Ok(())
+    }
+
+    /// User-friendly name other devices see for this adapter.
+    pub async fn adapter_alias(&self) -> zbus::Result<String> {
+        let adapter = self.adapter_proxy().await?;
+        adapter.alias().await
+    }
+
     // ========================================================================
     // Accessors (for live_updates and other modules)
     // ========================================================================
@@ -239,12 +527,29 @@ impl BluetoothManager {
         self.adapter_path.as_str()
     }
 
+    // ========================================================================
+    // Signal parsing
+    // ========================================================================
+
+    /// Object path and changed properties of a `PropertiesChanged` signal.
+    pub fn parse_properties_changed(
+        msg: &zbus::Message,
+    ) -> Option<(String, HashMap<String, OwnedValue>)> {
+        let path = msg.header().path()?.to_string();
+        let (_interface, changed, _invalidated): (
+            String,
+            HashMap<String, OwnedValue>,
+            Vec<String>,
+        ) = msg.body().deserialize().ok()?;
+        Some((path, changed))
+    }
+
     // ========================================================================
     // Private helpers
     // ========================================================================
 
     /// Create an Adapter1 proxy for our adapter.
-    async fn adapter_proxy(&self) -> zbus::Result<Adapter1Proxy<'_>> {
+    pub(crate) async fn adapter_proxy(&self) -> zbus::Result<Adapter1Proxy<'_>> {
         Adapter1Proxy::builder(&self.connection)
             .path(self.adapter_path.clone())?
             .build()
@@ -300,11 +605,30 @@ impl BluetoothManager {
             .and_then(|v| <bool>::try_from(v.clone()).ok())
             .unwrap_or(false);
 
+        let blocked = props
+            .get("Blocked")
+            .and_then(|v| <bool>::try_from(v.clone()).ok())
+            .unwrap_or(false);
+
         let rssi = props
             .get("RSSI")
             .and_then(|v| <i16>::try_from(v.clone()).ok())
             .unwrap_or(0);
 
+        let address_type = props
+            .get("AddressType")
+            .and_then(|v| <String>::try_from(v.clone()).ok())
+            .unwrap_or_default();
+
+        let uuids = props
+            .get("UUIDs")
+            .and_then(|v| <Vec<String>>::try_from(v.clone()).ok())
+            .unwrap_or_default();
+
+        // BlueZ fills an unset Alias with the address ("AA-BB-...")
+        let named = !name.is_empty()
+            || (!alias.is_empty() && alias != address.replace(':', "-"));
+
         // Display name: prefer alias, then name, then address
         let display_name = if !alias.is_empty() {
             alias
@@ -314,16 +638,43 @@ impl BluetoothManager {
             address.clone()
         };
 
-        let category = DeviceCategory::from_icon_hint(&icon_hint);
+        let class = props
+            .get("Class")
+            .and_then(|v| <u32>::try_from(v.clone()).ok());
+
+        // Icon hint first; classic devices without one still send a Class
+        let le_only = bluetooth_device::is_le_only(&address_type, &uuids);
+        let category = match DeviceCategory::from_icon_hint(&icon_hint) {
+            DeviceCategory::Other => class
+                .and_then(DeviceCategory::from_class)
+                .or_else(|| {
+                    le_only
+                        .then(|| DeviceCategory::from_gatt_uuids(&uuids))
+                        .flatten()
+                })
+                .unwrap_or(DeviceCategory::Other),
+            category => category,
+        };
 
         BluetoothDevice {
             address,
             display_name,
+            named,
             category,
+            icon_hint,
             paired,
             connected,
             trusted,
+            blocked,
             rssi,
+            last_seen: (rssi != 0).then(std::time::Instant::now),
+            address_type,
+            uuids,
+            battery: None,
+            volume: None,
+            transport_path: None,
+            le_only,
+            gatt_battery_path: None,
             device_path: path.to_string(),
         }
     }
@@ -0,0 +1,192 @@
+//! BlueZ pairing agent — answers org.bluez.Agent1 calls from the panel.
+//!
+//! BlueZ calls the agent on its own D-Bus thread, so requests are handed to
+//! the GTK side over a channel and each carries a reply channel back.
+
+use zbus::interface;
+use zbus::zvariant::OwnedObjectPath;
+
+/// Object path the agent is served at on the BlueZ (system bus) connection.
+pub const AGENT_PATH: &str = "/com/github/wifi_manager/BluezAgent";
+
+/// IO capability announced to BlueZ: we can show a code and take typed input.
+const AGENT_CAPABILITY: &str = "KeyboardDisplay";
+
+/// What a device asks for while pairing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PairingPrompt {
+    /// Legacy PIN code (up to 16 characters, often "0000")
+    PinCode,
+    /// Six-digit passkey shown on the remote device
+    Passkey,
+    /// Numeric comparison: both sides show this passkey, the user confirms
+    Confirmation(u32),
+    /// Pairing without a code started by the other device; the user allows it
+    Authorization,
+}
+
+/// One pending agent request; answer it with `respond`.
+pub struct PairingRequest {
+    pub device_path: String,
+    pub prompt: PairingPrompt,
+    reply: async_channel::Sender<Option<String>>,
+}
+
+impl PairingRequest {
+    /// Send the user's answer back to BlueZ; `None` rejects the pairing.
+    pub fn respond(self, answer: Option<String>) {
+        // BlueZ may have given up already; nothing left to tell it then
+        let _ = self.reply.try_send(answer);
+    }
+}
+
+/// Events delivered to the panel by the agent.
+pub enum AgentEvent {
+    Request(PairingRequest),
+    /// A code to type on the remote device (keyboards); needs no answer
+    Display {
+        device_path: String,
+        code: String,
+    },
+    /// BlueZ withdrew the outstanding request (timeout or remote cancel)
+    Cancel,
+}
+
+/// Errors returned to BlueZ, mapped to the names it expects.
+#[derive(Debug, zbus::DBusError)]
+#[zbus(prefix = "org.bluez.Error")]
+enum AgentError {
+    Rejected(String),
+}
+
+/// The org.bluez.Agent1 object.
+struct PairingAgent {
+    events: async_channel::Sender<AgentEvent>,
+}
+
+impl PairingAgent {
+    /// Forward a prompt to the panel and wait for the answer.
+    async fn ask(
+        &self,
+        device: OwnedObjectPath,
+        prompt: PairingPrompt,
+    ) -> Result<String, AgentError> {
+        let (reply, answer) = async_channel::bounded(1);
+        let request = PairingRequest {
+            device_path: device.to_string(),
+            prompt,
+            reply,
+        };
+        self.events
+            .send(AgentEvent::Request(request))
+            .await
+            .map_err(|_| AgentError::Rejected("Panel is not running".into()))?;
+        answer
+            .recv()
+            .await
+            .ok()
+            .flatten()
+            .ok_or_else(|| AgentError::Rejected("Pairing rejected".into()))
+    }
+
+    /// Hand a code to the panel for display; BlueZ doesn't wait for it.
+    fn display(&self, device: OwnedObjectPath, code: String) {
+        let _ = self.events.try_send(AgentEvent::Display {
+            device_path: device.to_string(),
+            code,
+        });
+    }
+}
+
+#[interface(name = "org.bluez.Agent1")]
+impl PairingAgent {
+    /// BlueZ unregistered the agent.
+    fn release(&self) {
+        log::info!("Bluetooth agent released");
+    }
+
+    /// Ask the user for a legacy PIN code.
+    async fn request_pin_code(&self, device: OwnedObjectPath) -> Result<String, AgentError> {
+        log::info!("Bluetooth agent: PIN code requested by {device}");
+        self.ask(device, PairingPrompt::PinCode).await
+    }
+
+    /// Ask the user for the six-digit passkey.
+    async fn request_passkey(&self, device: OwnedObjectPath) -> Result<u32, AgentError> {
+        log::info!("Bluetooth agent: passkey requested by {device}");
+        let answer = self.ask(device, PairingPrompt::Passkey).await?;
+        answer
+            .trim()
+            .parse()
+            .map_err(|_| AgentError::Rejected("Passkey must be a number".into()))
+    }
+
+    /// Ask the user whether both devices show the same passkey.
+    async fn request_confirmation(
+        &self,
+        device: OwnedObjectPath,
+        passkey: u32,
+    ) -> Result<(), AgentError> {
+        log::info!("Bluetooth agent: confirmation of {passkey:06} requested by {device}");
+        self.ask(device, PairingPrompt::Confirmation(passkey))
+            .await
+            .map(|_| ())
+    }
+
+    /// Show a legacy PIN to type on the device.
+    fn display_pin_code(&self, device: OwnedObjectPath, pincode: String) {
+        log::info!("Bluetooth agent: displaying PIN for {device}");
+        self.display(device, pincode);
+    }
+
+    /// Show a passkey to type on the device; called again as keys are entered.
+    fn display_passkey(&self, device: OwnedObjectPath, passkey: u32, entered: u16) {
+        log::debug!("Bluetooth agent: displaying passkey for {device} ({entered} typed)");
+        self.display(device, format!("{passkey:06}"));
+    }
+
+    /// Pairing without input ("Just Works") started by the other device.
+    async fn request_authorization(&self, device: OwnedObjectPath) -> Result<(), AgentError> {
+        log::info!("Bluetooth agent: authorization requested by {device}");
+        self.ask(device, PairingPrompt::Authorization)
+            .await
+            .map(|_| ())
+    }
+
+    /// A paired device wants to use a profile.
+    fn authorize_service(&self, device: OwnedObjectPath, uuid: String) {
+        log::debug!("Bluetooth agent: authorizing service {uuid} for {device}");
+    }
+
+    /// The outstanding request timed out or was cancelled remotely.
+    async fn cancel(&self) {
+        log::info!("Bluetooth agent: request cancelled");
+        let _ = self.events.send(AgentEvent::Cancel).await;
+    }
+}
+
+/// Serve the agent on `connection` and register it with BlueZ.
+///
+/// The agent is also requested as the default, so pairings started from the
+/// other device (e.g. a phone finding a discoverable adapter) prompt here
+/// too. Failing that only logs a warning; local pairings still work.
+pub(crate) async fn register(
+    connection: &zbus::Connection,
+) -> zbus::Result<async_channel::Receiver<AgentEvent>> {
+    let (events, receiver) = async_channel::unbounded();
+    connection
+        .object_server()
+        .at(AGENT_PATH, PairingAgent { events })
+        .await?;
+
+    let path = zbus::zvariant::ObjectPath::try_from(AGENT_PATH)
+        .map_err(|e| zbus::Error::Failure(format!("Invalid agent path: {e}")))?;
+    let manager = super::bluez_proxies::AgentManager1Proxy::new(connection).await?;
+    manager.register_agent(&path, AGENT_CAPABILITY).await?;
+    if let Err(e) = manager.request_default_agent(&path).await {
+        log::warn!("Could not become the default Bluetooth agent: {e}");
+    }
+
+    log::info!("Bluetooth pairing agent registered ({AGENT_CAPABILITY})");
+    Ok(receiver)
+}
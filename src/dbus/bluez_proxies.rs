@@ -41,6 +41,26 @@ pub(crate) trait Adapter1 {
     #[zbus(property)]
     fn set_powered(&self, powered: bool) -> zbus::Result<()>;
 
+    /// Whether other devices can find this adapter.
+    #[zbus(property)]
+    fn discoverable(&self) -> zbus::Result<bool>;
+
+    /// Make the adapter visible to (or hidden from) other devices.
+    #[zbus(property)]
+    fn set_discoverable(&self, discoverable: bool) -> zbus::Result<()>;
+
+    /// Seconds until discoverable mode turns itself off (0 = never).
+    #[zbus(property)]
+    fn set_discoverable_timeout(&self, timeout: u32) -> zbus::Result<()>;
+
+    /// Allow (or refuse) incoming pairing requests.
+    #[zbus(property)]
+    fn set_pairable(&self, pairable: bool) -> zbus::Result<()>;
+
+    /// Seconds until pairable mode turns itself off (0 = never).
+    #[zbus(property)]
+    fn set_pairable_timeout(&self, timeout: u32) -> zbus::Result<()>;
+
     /// Whether the adapter is currently discovering devices.
     #[zbus(property)]
     fn discovering(&self) -> zbus::Result<bool>;
@@ -65,6 +85,9 @@ pub(crate) trait Device1 {
     /// Connect to all auto-connectable profiles on this device.
     fn connect(&self) -> zbus::Result<()>;
 
+    /// Connect a single profile, given by its remote service UUID.
+    fn connect_profile(&self, uuid: &str) -> zbus::Result<()>;
+
     /// Disconnect all profiles and the underlying connection.
     fn disconnect(&self) -> zbus::Result<()>;
 
@@ -86,6 +109,10 @@ pub(crate) trait Device1 {
     #[zbus(property)]
     fn alias(&self) -> zbus::Result<String>;
 
+    /// Set the alias; an empty string reverts to the remote name.
+    #[zbus(property)]
+    fn set_alias(&self, alias: &str) -> zbus::Result<()>;
+
     /// Whether this device is paired.
     #[zbus(property)]
     fn paired(&self) -> zbus::Result<bool>;
@@ -123,6 +150,61 @@ pub(crate) trait Device1 {
     fn adapter(&self) -> zbus::Result<OwnedObjectPath>;
 }
 
+/// Proxy for org.bluez.MediaTransport1
+///
+/// The audio stream of a connected A2DP device.
+#[proxy(
+    interface = "org.bluez.MediaTransport1",
+    default_service = "org.bluez"
+)]
+pub(crate) trait MediaTransport1 {
+    /// AVRCP absolute volume (0–127); only present if the device supports it.
+    #[zbus(property)]
+    fn volume(&self) -> zbus::Result<u16>;
+
+    /// Set the device's absolute volume (0–127).
+    #[zbus(property)]
+    fn set_volume(&self, volume: u16) -> zbus::Result<()>;
+}
+
+/// Proxy for org.bluez.GattCharacteristic1
+///
+/// A GATT characteristic of a connected LE device.
+#[proxy(
+    interface = "org.bluez.GattCharacteristic1",
+    default_service = "org.bluez"
+)]
+pub(crate) trait GattCharacteristic1 {
+    /// Read the characteristic value from the device.
+    fn read_value(
+        &self,
+        options: HashMap<&str, zbus::zvariant::Value<'_>>,
+    ) -> zbus::Result<Vec<u8>>;
+}
+
+/// Proxy for org.bluez.AgentManager1
+///
+/// Registers the pairing agent that answers PIN / passkey prompts.
+#[proxy(
+    interface = "org.bluez.AgentManager1",
+    default_service = "org.bluez",
+    default_path = "/org/bluez"
+)]
+pub(crate) trait AgentManager1 {
+    /// Register an agent object with the given IO capability.
+    fn register_agent(
+        &self,
+        agent: &zbus::zvariant::ObjectPath<'_>,
+        capability: &str,
+    ) -> zbus::Result<()>;
+
+    /// Make a registered agent the one that answers incoming pairing requests.
+    fn request_default_agent(&self, agent: &zbus::zvariant::ObjectPath<'_>) -> zbus::Result<()>;
+
+    /// Unregister a previously registered agent.
+    fn unregister_agent(&self, agent: &zbus::zvariant::ObjectPath<'_>) -> zbus::Result<()>;
+}
+
 /// Proxy for org.freedesktop.DBus.ObjectManager on the BlueZ service.
 ///
 /// Used to enumerate all adapters and devices, and to receive
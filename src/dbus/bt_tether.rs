@@ -0,0 +1,81 @@
+//! Bluetooth PAN tethering via NetworkManager (D-Bus).
+//!
+//! Uses a phone's shared internet over Bluetooth. NetworkManager owns the
+//! actual link; this only finds or creates the `bluetooth` (panu) profile for
+//! the phone's address and activates it.
+
+use zbus::zvariant::{ObjectPath, OwnedObjectPath};
+
+use super::access_point::parse_bssid;
+use super::connection::build_bluetooth_panu_settings;
+use super::polkit;
+use super::proxies::{NetworkManagerProxy, SettingsConnectionProxy, SettingsProxy};
+
+#[derive(Clone)]
+pub struct TetherManager {
+    conn: zbus::Connection,
+}
+
+impl TetherManager {
+    pub fn new(conn: &zbus::Connection) -> Self {
+        Self { conn: conn.clone() }
+    }
+
+    /// Find a saved panu profile bound to `bdaddr`, if any.
+    async fn find_profile(&self, bdaddr: &[u8]) -> zbus::Result<Option<OwnedObjectPath>> {
+        let settings_proxy = SettingsProxy::new(&self.conn).await?;
+
+        for conn_path in settings_proxy.list_connections().await? {
+            let conn = SettingsConnectionProxy::builder(&self.conn)
+                .path(conn_path.clone())?
+                .build()
+                .await?;
+            let Ok(settings) = conn.get_settings().await else {
+                continue;
+            };
+            let Some(bluetooth) = settings.get("bluetooth") else {
+                continue;
+            };
+
+            let bt_type = bluetooth
+                .get("type")
+                .and_then(|v| <String>::try_from(v.clone()).ok())
+                .unwrap_or_default();
+            let profile_bdaddr = bluetooth
+                .get("bdaddr")
+                .and_then(|v| <Vec<u8>>::try_from(v.clone()).ok())
+                .unwrap_or_default();
+            if bt_type == "panu" && profile_bdaddr == bdaddr {
+                return Ok(Some(conn_path));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Connect to the phone's network, creating the profile on first use.
+    ///
+    /// Returns the ActiveConnection path.
+    pub async fn connect(&self, address: &str, name: &str) -> zbus::Result<OwnedObjectPath> {
+        // NM stores the address as raw bytes (same layout as a BSSID)
+        let bdaddr = parse_bssid(address)
+            .ok_or_else(|| zbus::Error::Failure(format!("Invalid Bluetooth address: {address}")))?;
+        let nm = NetworkManagerProxy::new(&self.conn).await?;
+        // NM picks the Bluetooth device itself from the profile's bdaddr
+        let root = ObjectPath::try_from("/")
+            .map_err(|e| zbus::Error::Failure(format!("Invalid root path: {e}")))?;
+
+        if let Some(conn_path) = self.find_profile(&bdaddr).await? {
+            return nm
+                .activate_connection(&conn_path, &root, &root)
+                .await
+                .map_err(polkit::map_denied);
+        }
+
+        let settings = build_bluetooth_panu_settings(bdaddr, name);
+        let (_, active) = nm
+            .add_and_activate_connection(settings, &root, &root)
+            .await
+            .map_err(polkit::map_denied)?;
+        Ok(active)
+    }
+}
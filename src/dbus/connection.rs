@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use zbus::zvariant::Value;
+use zbus::zvariant::{OwnedValue, Value};
 
 /// Build a NM connection settings dict for connecting to a WPA-PSK secured network.
 pub fn build_wpa_psk_settings<'a>(
@@ -50,7 +50,306 @@ pub fn build_wpa3_settings<'a>(
     settings
 }
 
+/// Build a NM connection settings dict for a static WEP network.
+///
+/// 5/13 ASCII or 10/26 hex characters are used as the key itself
+/// (`wep-key-type` 1); anything else is hashed as a passphrase (type 2).
+pub fn build_wep_settings<'a>(
+    ssid: &str,
+    key: &'a str,
+) -> HashMap<String, HashMap<String, Value<'a>>> {
+    let mut settings: HashMap<String, HashMap<String, Value>> = HashMap::new();
+
+    let mut connection = HashMap::new();
+    connection.insert("type".to_string(), Value::from("802-11-wireless"));
+    settings.insert("connection".to_string(), connection);
+
+    let mut wireless = HashMap::new();
+    wireless.insert("ssid".to_string(), Value::from(ssid.as_bytes().to_vec()));
+    settings.insert("802-11-wireless".to_string(), wireless);
+
+    let is_hex = key.chars().all(|c| c.is_ascii_hexdigit());
+    let is_raw_key = matches!(key.len(), 5 | 13) || (is_hex && matches!(key.len(), 10 | 26));
+    let key_type: u32 = if is_raw_key { 1 } else { 2 };
+
+    let mut security = HashMap::new();
+    security.insert("key-mgmt".to_string(), Value::from("none"));
+    security.insert("auth-alg".to_string(), Value::from("open"));
+    security.insert("wep-key0".to_string(), Value::from(key));
+    security.insert("wep-key-type".to_string(), Value::from(key_type));
+    security.insert("wep-tx-keyidx".to_string(), Value::from(0u32));
+    settings.insert("802-11-wireless-security".to_string(), security);
+
+    settings
+}
+
 /// Build an empty settings dict (for open networks — NM fills in the rest).
 pub fn build_open_settings() -> HashMap<String, HashMap<String, Value<'static>>> {
     HashMap::new()
 }
+
+/// Build settings for a network that doesn't broadcast its SSID.
+///
+/// Uses WPA-PSK when a password is given, otherwise an open network.
+pub fn build_hidden_settings<'a>(
+    ssid: &str,
+    password: Option<&'a str>,
+) -> HashMap<String, HashMap<String, Value<'a>>> {
+    let mut settings = match password {
+        Some(psk) => build_wpa_psk_settings(ssid, psk),
+        None => {
+            let mut settings: HashMap<String, HashMap<String, Value>> = HashMap::new();
+            let mut connection = HashMap::new();
+            connection.insert("type".to_string(), Value::from("802-11-wireless"));
+            settings.insert("connection".to_string(), connection);
+
+            let mut wireless = HashMap::new();
+            wireless.insert("ssid".to_string(), Value::from(ssid.as_bytes().to_vec()));
+            settings.insert("802-11-wireless".to_string(), wireless);
+            settings
+        }
+    };
+
+    // Probe for the SSID directly since the AP won't announce it
+    if let Some(wireless) = settings.get_mut("802-11-wireless") {
+        wireless.insert("hidden".to_string(), Value::from(true));
+    }
+    settings
+}
+
+/// Build settings for a Bluetooth PAN (tethering) connection to a phone.
+///
+/// `bdaddr` is the phone's address as six raw bytes; NM binds the profile
+/// to it and acts as the PANU (client) side.
+pub fn build_bluetooth_panu_settings(
+    bdaddr: Vec<u8>,
+    name: &str,
+) -> HashMap<String, HashMap<String, Value<'static>>> {
+    let mut settings: HashMap<String, HashMap<String, Value>> = HashMap::new();
+
+    let mut connection = HashMap::new();
+    connection.insert("type".to_string(), Value::from("bluetooth"));
+    connection.insert("id".to_string(), Value::from(format!("{name} Network")));
+    connection.insert("autoconnect".to_string(), Value::from(false));
+    settings.insert("connection".to_string(), connection);
+
+    let mut bluetooth = HashMap::new();
+    bluetooth.insert("bdaddr".to_string(), Value::from(bdaddr));
+    bluetooth.insert("type".to_string(), Value::from("panu"));
+    settings.insert("bluetooth".to_string(), bluetooth);
+
+    for family in ["ipv4", "ipv6"] {
+        let mut ip = HashMap::new();
+        ip.insert("method".to_string(), Value::from("auto"));
+        settings.insert(family.to_string(), ip);
+    }
+
+    settings
+}
+
+/// NM `proxy.method` values.
+const NM_PROXY_METHOD_NONE: i32 = 0;
+const NM_PROXY_METHOD_AUTO: i32 = 1;
+
+/// Per-connection proxy, as offered in the connection editor.
+///
+/// NM only knows "none" and "auto" (WPAD, a PAC URL or an inline PAC
+/// script), so a manual `host:port` proxy is stored as a one-line PAC script.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ProxyMode {
+    #[default]
+    None,
+    /// WPAD discovery
+    Auto,
+    PacUrl(String),
+    /// `host:port` of an HTTP proxy
+    Manual(String),
+}
+
+impl ProxyMode {
+    const MANUAL_PREFIX: &'static str = "function FindProxyForURL(url, host) { return \"PROXY ";
+    const MANUAL_SUFFIX: &'static str = "\"; }";
+
+    fn manual_script(host_port: &str) -> String {
+        format!("{}{host_port}{}", Self::MANUAL_PREFIX, Self::MANUAL_SUFFIX)
+    }
+
+    /// Parse a manual `host:port` proxy.
+    ///
+    /// It ends up inside a JS string in the PAC script, so only a hostname,
+    /// an IPv4 address or a bracketed IPv6 address plus a numeric port pass.
+    pub fn manual(host_port: &str) -> Option<Self> {
+        let (host, port) = host_port.rsplit_once(':')?;
+        if port.parse::<u16>().ok()? == 0 {
+            return None;
+        }
+        let valid_host = match host.strip_prefix('[').and_then(|h| h.strip_suffix(']')) {
+            Some(ipv6) => ipv6.parse::<std::net::Ipv6Addr>().is_ok(),
+            None => host.split('.').all(|label| {
+                !label.is_empty()
+                    && label.len() <= 63
+                    && !label.starts_with('-')
+                    && !label.ends_with('-')
+                    && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            }),
+        };
+        valid_host.then(|| ProxyMode::Manual(host_port.to_string()))
+    }
+
+    fn from_proxy_setting(proxy: Option<&HashMap<String, OwnedValue>>) -> Self {
+        let Some(proxy) = proxy else {
+            return ProxyMode::None;
+        };
+        let string = |key: &str| {
+            proxy
+                .get(key)
+                .and_then(|v| String::try_from(v.clone()).ok())
+                .unwrap_or_default()
+        };
+        let method = proxy
+            .get("method")
+            .and_then(|v| i32::try_from(v.clone()).ok())
+            .unwrap_or(NM_PROXY_METHOD_NONE);
+        if method != NM_PROXY_METHOD_AUTO {
+            return ProxyMode::None;
+        }
+
+        let pac_url = string("pac-url");
+        if !pac_url.is_empty() {
+            return ProxyMode::PacUrl(pac_url);
+        }
+        let script = string("pac-script");
+        match script
+            .strip_prefix(Self::MANUAL_PREFIX)
+            .and_then(|rest| rest.strip_suffix(Self::MANUAL_SUFFIX))
+        {
+            Some(host_port) => Self::manual(host_port).unwrap_or(ProxyMode::Auto),
+            // A hand-written PAC script we can't represent; treat as auto
+            None => ProxyMode::Auto,
+        }
+    }
+
+    fn to_proxy_setting(&self) -> HashMap<String, Value<'static>> {
+        let mut proxy = HashMap::new();
+        let method = match self {
+            ProxyMode::None => NM_PROXY_METHOD_NONE,
+            _ => NM_PROXY_METHOD_AUTO,
+        };
+        proxy.insert("method".to_string(), Value::from(method));
+        match self {
+            ProxyMode::PacUrl(url) => {
+                proxy.insert("pac-url".to_string(), Value::from(url.clone()));
+            }
+            ProxyMode::Manual(host_port) if Self::manual(host_port).is_some() => {
+                proxy.insert(
+                    "pac-script".to_string(),
+                    Value::from(Self::manual_script(host_port)),
+                );
+            }
+            ProxyMode::Manual(host_port) => {
+                log::warn!("Not writing a PAC script for invalid proxy '{host_port}'");
+            }
+            ProxyMode::None | ProxyMode::Auto => {}
+        }
+        proxy
+    }
+}
+
+/// `connection.dns-over-tls`, honoured when systemd-resolved handles DNS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DnsOverTls {
+    /// Follow the global resolved setting
+    #[default]
+    Default,
+    Off,
+    /// Use TLS when the server supports it, fall back to plain DNS otherwise
+    Opportunistic,
+    Strict,
+}
+
+impl DnsOverTls {
+    fn from_nm(value: i32) -> Self {
+        match value {
+            0 => DnsOverTls::Off,
+            1 => DnsOverTls::Opportunistic,
+            2 => DnsOverTls::Strict,
+            _ => DnsOverTls::Default,
+        }
+    }
+
+    fn to_nm(self) -> i32 {
+        match self {
+            DnsOverTls::Default => -1,
+            DnsOverTls::Off => 0,
+            DnsOverTls::Opportunistic => 1,
+            DnsOverTls::Strict => 2,
+        }
+    }
+}
+
+/// `802-11-wireless.assigned-mac-address` keywords NM accepts besides a MAC.
+pub const MAC_ADDRESS_MODES: [&str; 4] = ["permanent", "preserve", "random", "stable"];
+
+/// Options of a saved profile that the connection editor can change.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ProfileOptions {
+    pub proxy: ProxyMode,
+    /// Interface MTU; 0 lets NM pick.
+    pub mtu: u32,
+    /// Cloned MAC address or one of `MAC_ADDRESS_MODES`; `None` keeps NM's default.
+    pub cloned_mac: Option<String>,
+    pub dns_over_tls: DnsOverTls,
+}
+
+impl ProfileOptions {
+    /// Extract the editable options from a `GetSettings` result.
+    pub fn from_settings(settings: &HashMap<String, HashMap<String, OwnedValue>>) -> Self {
+        let wireless = settings.get("802-11-wireless");
+        let wireless_prop = |key: &str| wireless.and_then(|w| w.get(key)).cloned();
+        let mtu = wireless_prop("mtu")
+            .and_then(|v| u32::try_from(v).ok())
+            .unwrap_or(0);
+        // Prefer the string property; older profiles only carry the raw bytes
+        let cloned_mac = wireless_prop("assigned-mac-address")
+            .and_then(|v| String::try_from(v).ok())
+            .filter(|mac| !mac.is_empty())
+            .or_else(|| {
+                wireless_prop("cloned-mac-address")
+                    .and_then(|v| <Vec<u8>>::try_from(v).ok())
+                    .and_then(|bytes| super::access_point::format_bssid(&bytes))
+            });
+        let dns_over_tls = settings
+            .get("connection")
+            .and_then(|c| c.get("dns-over-tls"))
+            .and_then(|v| i32::try_from(v.clone()).ok())
+            .map(DnsOverTls::from_nm)
+            .unwrap_or_default();
+        Self {
+            proxy: ProxyMode::from_proxy_setting(settings.get("proxy")),
+            mtu,
+            cloned_mac,
+            dns_over_tls,
+        }
+    }
+
+    /// Write the options into a full settings dict before `Update`.
+    pub fn apply(&self, settings: &mut HashMap<String, HashMap<String, Value<'static>>>) {
+        settings.insert("proxy".to_string(), self.proxy.to_proxy_setting());
+        settings
+            .entry("connection".to_string())
+            .or_default()
+            .insert("dns-over-tls".to_string(), Value::from(self.dns_over_tls.to_nm()));
+
+        let wireless = settings.entry("802-11-wireless".to_string()).or_default();
+        wireless.insert("mtu".to_string(), Value::from(self.mtu));
+        wireless.remove("cloned-mac-address");
+        match &self.cloned_mac {
+            Some(mac) => {
+                wireless.insert("assigned-mac-address".to_string(), Value::from(mac.clone()));
+            }
+            None => {
+                wireless.remove("assigned-mac-address");
+            }
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! Connection quality model — grades the active WiFi link from A to D.
+//!
+//! Combines signal strength, the negotiated link rate and recent traffic
+//! counters into a single letter that is easy to read at a glance.
+
+use std::fmt;
+use std::time::Instant;
+
+/// Minimum time between two counter samples before a throughput is derived.
+const MIN_SAMPLE_SECS: f64 = 1.0;
+
+/// Share of the link rate in use above which the link is considered busy.
+const BUSY_LINK_RATIO: f64 = 0.5;
+
+/// Raw link measurements read from NetworkManager.
+#[derive(Debug, Clone, Copy)]
+pub struct LinkStats {
+    /// Signal strength of the active AP (0–100).
+    pub strength: u8,
+    /// Negotiated link rate in kbit/s (0 if unknown).
+    pub bitrate_kbps: u32,
+    /// Total bytes received on the device.
+    pub rx_bytes: u64,
+    /// Total bytes sent on the device.
+    pub tx_bytes: u64,
+}
+
+/// Observed traffic between two samples, in bytes per second.
+#[derive(Debug, Clone, Copy)]
+pub struct Throughput {
+    pub rx_bps: f64,
+    pub tx_bps: f64,
+}
+
+impl Throughput {
+    fn between(prev: &LinkStats, next: &LinkStats, secs: f64) -> Self {
+        Self {
+            rx_bps: next.rx_bytes.saturating_sub(prev.rx_bytes) as f64 / secs,
+            tx_bps: next.tx_bytes.saturating_sub(prev.tx_bytes) as f64 / secs,
+        }
+    }
+}
+
+/// Keeps the previous counter sample so throughput can be derived between refreshes.
+#[derive(Debug, Default)]
+pub struct LinkSampler {
+    last: Option<(Instant, LinkStats)>,
+    throughput: Option<Throughput>,
+}
+
+impl LinkSampler {
+    /// Record a new sample and return the most recent throughput, if known.
+    pub fn record(&mut self, stats: LinkStats, now: Instant) -> Option<Throughput> {
+        match self.last {
+            Some((at, prev)) => {
+                let secs = now.duration_since(at).as_secs_f64();
+                if secs >= MIN_SAMPLE_SECS {
+                    self.throughput = Some(Throughput::between(&prev, &stats, secs));
+                    self.last = Some((now, stats));
+                }
+            }
+            None => self.last = Some((now, stats)),
+        }
+        self.throughput
+    }
+
+    /// Forget all samples (e.g. after disconnecting).
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Letter grade for the active connection (A = best).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum QualityGrade {
+    A,
+    B,
+    C,
+    D,
+}
+
+impl QualityGrade {
+    fn from_signal(strength: u8) -> Self {
+        match strength {
+            70.. => QualityGrade::A,
+            50..=69 => QualityGrade::B,
+            30..=49 => QualityGrade::C,
+            _ => QualityGrade::D,
+        }
+    }
+
+    fn from_bitrate(kbps: u32) -> Self {
+        match kbps / 1000 {
+            300.. => QualityGrade::A,
+            100..=299 => QualityGrade::B,
+            24..=99 => QualityGrade::C,
+            _ => QualityGrade::D,
+        }
+    }
+
+    fn downgrade(self) -> Self {
+        match self {
+            QualityGrade::A => QualityGrade::B,
+            QualityGrade::B => QualityGrade::C,
+            QualityGrade::C | QualityGrade::D => QualityGrade::D,
+        }
+    }
+}
+
+impl fmt::Display for QualityGrade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QualityGrade::A => write!(f, "A"),
+            QualityGrade::B => write!(f, "B"),
+            QualityGrade::C => write!(f, "C"),
+            QualityGrade::D => write!(f, "D"),
+        }
+    }
+}
+
+/// Grade a link and return a short plain-language reason for the grade.
+pub fn grade_link(stats: &LinkStats, throughput: Option<Throughput>) -> (QualityGrade, &'static str) {
+    let signal = QualityGrade::from_signal(stats.strength);
+    // Some drivers don't report a bitrate — judge on signal alone then
+    let rate = if stats.bitrate_kbps > 0 {
+        QualityGrade::from_bitrate(stats.bitrate_kbps)
+    } else {
+        QualityGrade::A
+    };
+
+    let (mut grade, mut reason) = if signal >= rate && signal > QualityGrade::A {
+        (signal, "Weak signal — move closer to the router")
+    } else if rate > QualityGrade::A {
+        (rate, "Slow link rate — the router or band limits speed")
+    } else {
+        (QualityGrade::A, "Strong signal and fast link")
+    };
+
+    // A link that is already saturated by traffic feels slow regardless of signal
+    if let Some(tp) = throughput
+        && stats.bitrate_kbps > 0
+    {
+        let used_bits = (tp.rx_bps + tp.tx_bps) * 8.0;
+        let link_bits = stats.bitrate_kbps as f64 * 1000.0;
+        if used_bits >= link_bits * BUSY_LINK_RATIO {
+            grade = grade.downgrade();
+            reason = "Link is busy — current traffic uses most of the bandwidth";
+        }
+    }
+
+    (grade, reason)
+}
+
+/// Format a byte rate for display (e.g. "1.2 MB/s").
+pub fn format_rate(bytes_per_sec: f64) -> String {
+    if bytes_per_sec >= 1_000_000.0 {
+        format!("{:.1} MB/s", bytes_per_sec / 1_000_000.0)
+    } else if bytes_per_sec >= 1_000.0 {
+        format!("{:.0} KB/s", bytes_per_sec / 1_000.0)
+    } else {
+        format!("{bytes_per_sec:.0} B/s")
+    }
+}
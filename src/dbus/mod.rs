@@ -1,8 +1,16 @@
 pub mod access_point;
+pub mod bluez_agent;
 pub mod bluetooth_device;
 pub mod bluetooth_manager;
+pub mod bt_tether;
 pub(crate) mod bluez_proxies;
 pub mod connection;
+pub mod link_quality;
 pub mod network_manager;
+pub mod notifications;
+pub mod obex;
+pub mod obex_agent;
+pub mod polkit;
+pub mod tray;
 pub mod vpn_manager;
 pub(crate) mod proxies;
@@ -3,21 +3,151 @@
 //! Uses proxy types from `proxies.rs` to communicate with NetworkManager.
 
 use std::collections::HashMap;
-use zbus::zvariant::OwnedObjectPath;
-
-use super::access_point::{self, Band, Network, SecurityType};
+use std::future::Future;
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use futures_util::future::{self, Either};
+use zbus::names::InterfaceName;
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+
+use super::access_point::{self, AccessPoint, Band, Network, SecurityType};
+use super::connection::ProfileOptions;
+use super::link_quality::LinkStats;
+use super::polkit;
 use super::proxies::*;
 
 /// The WiFi manager that wraps all NM D-Bus interactions.
+///
+/// Long-lived proxies are built once in `new()`; cloning is cheap since
+/// zbus proxies share their inner state.
 #[derive(Clone)]
 pub struct WifiManager {
     connection: zbus::Connection,
     wifi_device_path: OwnedObjectPath,
+    nm: NetworkManagerProxy<'static>,
+    settings: SettingsProxy<'static>,
+    device: DeviceProxy<'static>,
+    wireless: WirelessProxy<'static>,
+    stats: StatisticsProxy<'static>,
+}
+
+/// A saved Wi-Fi profile, keyed by SSID in `get_saved_wifi_ssids`.
+struct SavedWifi {
+    /// D-Bus path of the Settings.Connection object.
+    path: String,
+    /// BSSID the profile is locked to, if any.
+    bssid: Option<String>,
 }
 
+/// Well-known bus name of NetworkManager
+const NM_SERVICE: &str = "org.freedesktop.NetworkManager";
+
+/// Object path and interface of the NM root object
+const NM_PATH: &str = "/org/freedesktop/NetworkManager";
+const NM_INTERFACE: &str = "org.freedesktop.NetworkManager";
+
+/// D-Bus interface of NM access point objects
+const NM_AP_INTERFACE: &str = "org.freedesktop.NetworkManager.AccessPoint";
+
 /// NM device type constant for WiFi
 const NM_DEVICE_TYPE_WIFI: u32 = 2;
 
+/// NMActiveConnectionState values
+const NM_ACTIVE_CONNECTION_STATE_ACTIVATED: u32 = 2;
+const NM_ACTIVE_CONNECTION_STATE_DEACTIVATED: u32 = 4;
+
+/// Result of waiting for a connection to activate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActivationOutcome {
+    Activated,
+    /// NM gave up; carries a human-readable reason.
+    Failed(&'static str),
+    /// NM did not settle within the timeout.
+    TimedOut,
+}
+
+/// Whether `err` is NM refusing a scan because the previous one was too recent.
+///
+/// NM answers `RequestScan` with `Device.NotAllowed` ("Scanning not allowed
+/// immediately following previous scan") while its own rate limit applies.
+pub fn is_scan_rate_limited(err: &zbus::Error) -> bool {
+    match err {
+        zbus::Error::MethodError(name, message, _) => {
+            name.as_str() == "org.freedesktop.NetworkManager.Device.NotAllowed"
+                && message
+                    .as_deref()
+                    .is_some_and(|m| m.contains("immediately following previous scan"))
+        }
+        _ => false,
+    }
+}
+
+/// Map an NMActiveConnectionStateReason to a short user-facing message.
+fn activation_failure_reason(reason: u32) -> &'static str {
+    match reason {
+        2 => "disconnected by user",
+        3 => "device disconnected",
+        5 => "could not get an IP address",
+        6 => "connection timed out",
+        9 => "wrong password",
+        10 => "login failed",
+        11 => "connection profile removed",
+        14 => "device removed",
+        _ => "connection failed",
+    }
+}
+
+/// IPv4 settings of the active connection, as handed out by DHCP or the profile.
+#[derive(Debug, Clone, Default)]
+pub struct Ipv4Info {
+    /// First address in CIDR form, e.g. "192.168.1.23/24".
+    pub address: Option<String>,
+    pub gateway: Option<String>,
+}
+
+/// Upstream reachability as reported by NM (NMConnectivityState).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    Unknown,
+    /// Not connected to any network
+    Offline,
+    /// Behind a captive portal
+    Portal,
+    /// Connected, but the internet is not reachable
+    Limited,
+    Full,
+}
+
+impl From<u32> for Connectivity {
+    fn from(value: u32) -> Self {
+        match value {
+            1 => Self::Offline,
+            2 => Self::Portal,
+            3 => Self::Limited,
+            4 => Self::Full,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl Connectivity {
+    /// Short note appended to "Connected to X" when the internet isn't reachable.
+    pub fn status_note(self) -> Option<&'static str> {
+        match self {
+            Self::Portal => Some("Sign-in required"),
+            Self::Offline | Self::Limited => Some("No internet"),
+            Self::Unknown | Self::Full => None,
+        }
+    }
+}
+
+/// Error message returned when NM has no WiFi device.
+pub const NO_WIFI_DEVICE: &str = "No WiFi device found";
+
+/// Counter refresh interval requested from NM when statistics are disabled
+const STATS_REFRESH_RATE_MS: u32 = 2000;
+
 impl WifiManager {
     /// Connect to D-Bus and find the first WiFi device.
     pub async fn new() -> zbus::Result<Self> {
@@ -26,54 +156,129 @@ impl WifiManager {
         // Get the NM proxy
         let nm = NetworkManagerProxy::new(&connection).await?;
 
-        // Find the WiFi device
-        let devices = nm.get_devices().await?;
-        let mut wifi_device_path: Option<OwnedObjectPath> = None;
+        let wifi_device_path = find_wifi_device(&connection, &nm)
+            .await?
+            .ok_or_else(|| zbus::Error::Failure(NO_WIFI_DEVICE.into()))?;
 
-        for device_path in devices {
-            let device = DeviceProxy::builder(&connection)
-                .path(device_path.clone())?
-                .build()
-                .await?;
+        Self::with_device(connection, nm, wifi_device_path).await
+    }
 
-            if device.device_type().await? == NM_DEVICE_TYPE_WIFI {
-                wifi_device_path = Some(device_path);
-                break;
+    /// Like `new()`, but if no WiFi device exists yet, wait for NM to report
+    /// one being added (e.g. a USB dongle plugged in after startup).
+    pub async fn wait_for_device() -> zbus::Result<Self> {
+        let connection = zbus::Connection::system().await?;
+        let nm = NetworkManagerProxy::new(&connection).await?;
+
+        // Subscribe first so a device added while we look isn't missed
+        let mut added = nm.receive_device_added().await?;
+        loop {
+            if let Some(path) = find_wifi_device(&connection, &nm).await? {
+                return Self::with_device(connection, nm, path).await;
+            }
+            log::info!("No WiFi device yet — waiting for one to be added");
+            if added.next().await.is_none() {
+                return Err(zbus::Error::Failure(NO_WIFI_DEVICE.into()));
             }
         }
+    }
 
-        let wifi_device_path =
-            wifi_device_path.ok_or_else(|| zbus::Error::Failure("No WiFi device found".into()))?;
-
+    /// Build the manager and its long-lived proxies for a known WiFi device.
+    async fn with_device(
+        connection: zbus::Connection,
+        nm: NetworkManagerProxy<'static>,
+        wifi_device_path: OwnedObjectPath,
+    ) -> zbus::Result<Self> {
         log::info!("Found WiFi device: {}", wifi_device_path);
 
+        let settings = SettingsProxy::new(&connection).await?;
+        let device = DeviceProxy::builder(&connection)
+            .path(wifi_device_path.clone())?
+            .build()
+            .await?;
+        let wireless = WirelessProxy::builder(&connection)
+            .path(wifi_device_path.clone())?
+            .build()
+            .await?;
+        let stats = StatisticsProxy::builder(&connection)
+            .path(wifi_device_path.clone())?
+            .build()
+            .await?;
+
         Ok(Self {
             connection,
             wifi_device_path,
+            nm,
+            settings,
+            device,
+            wireless,
+            stats,
         })
     }
 
     /// Trigger a WiFi scan.
     pub async fn request_scan(&self) -> zbus::Result<()> {
-        let wireless = WirelessProxy::builder(&self.connection)
-            .path(self.wifi_device_path.clone())?
-            .build()
-            .await?;
+        self.wireless.request_scan(HashMap::new()).await?;
+        log::info!("WiFi scan requested");
+        Ok(())
+    }
+
+    /// Trigger a WiFi scan and wait until NM reports it finished.
+    ///
+    /// Resolves as soon as the `LastScan` property changes, or when `timeout`
+    /// (the caller's timer) fires on drivers that never report one. Returns
+    /// whether the scan completed.
+    pub async fn request_scan_and_wait(
+        &self,
+        timeout: impl Future<Output = ()>,
+    ) -> zbus::Result<bool> {
+        let wireless = &self.wireless;
+
+        let before = wireless.last_scan().await.unwrap_or(-1);
+        // Subscribe before requesting so a fast scan can't slip past us
+        let mut changes = wireless.receive_last_scan_changed().await;
 
         wireless.request_scan(HashMap::new()).await?;
         log::info!("WiFi scan requested");
-        Ok(())
+
+        let finished = async {
+            while let Some(change) = changes.next().await {
+                if let Ok(last_scan) = change.get().await
+                    && last_scan != before
+                {
+                    return true;
+                }
+            }
+            false
+        };
+        futures_util::pin_mut!(finished, timeout);
+
+        match future::select(finished, timeout).await {
+            Either::Left((completed, _)) => Ok(completed),
+            Either::Right(_) => {
+                log::debug!("No LastScan update before the timeout, using current results");
+                Ok(false)
+            }
+        }
     }
 
-    /// Get a list of available networks (deduplicated by SSID).
-    pub async fn get_networks(&self) -> zbus::Result<Vec<Network>> {
-        let wireless = WirelessProxy::builder(&self.connection)
-            .path(self.wifi_device_path.clone())?
-            .build()
-            .await?;
+    /// Time elapsed since NM last finished a scan (None if it never scanned).
+    pub async fn last_scan_age(&self) -> zbus::Result<Option<Duration>> {
+        let last_scan = self.wireless.last_scan().await?;
+        if last_scan < 0 {
+            return Ok(None);
+        }
+        Ok(boottime_ms().map(|now| Duration::from_millis(now.saturating_sub(last_scan as u64))))
+    }
 
-        let ap_paths = wireless.access_points().await?;
+    /// Get a list of available networks (deduplicated by SSID), in no
+    /// particular order.
+    ///
+    /// With `include_hidden`, empty-SSID APs are folded into one
+    /// "Hidden network" entry (the strongest such AP) instead of skipped.
+    pub async fn get_networks(&self, include_hidden: bool) -> zbus::Result<Vec<Network>> {
+        let ap_paths = self.wireless.access_points().await?;
         let mut networks_by_ssid: HashMap<String, Network> = HashMap::new();
+        let mut aps_by_ssid: HashMap<String, Vec<AccessPoint>> = HashMap::new();
 
         // Get the currently active AP path (if any)
         let active_ap = self.get_active_ap_path().await.ok();
@@ -81,28 +286,41 @@ impl WifiManager {
         // Get saved connection SSIDs
         let saved_ssids = self.get_saved_wifi_ssids().await.unwrap_or_default();
 
-        for ap_path in ap_paths {
-            let ap = AccessPointProxy::builder(&self.connection)
-                .path(ap_path.clone())?
-                .build()
-                .await?;
+        // One GetAll per AP, issued concurrently, instead of a call per property
+        let ap_props =
+            future::join_all(ap_paths.iter().map(|path| self.get_ap_properties(path))).await;
 
-            // Read AP properties
-            let ssid_bytes = ap.ssid().await?;
+        for (ap_path, props) in ap_paths.into_iter().zip(ap_props) {
+            let props = match props {
+                Ok(props) => props,
+                Err(e) => {
+                    // AP may have vanished between listing and reading it
+                    log::debug!("Skipping AP {ap_path}: {e}");
+                    continue;
+                }
+            };
+
+            let ssid_bytes: Vec<u8> = ap_prop(&props, "Ssid").unwrap_or_default();
             let ssid = String::from_utf8_lossy(&ssid_bytes).to_string();
 
-            // Skip hidden networks (empty SSID)
-            if ssid.is_empty() {
+            // Hidden networks (empty SSID) are skipped unless requested
+            let is_hidden = ssid.is_empty();
+            if is_hidden && !include_hidden {
                 continue;
             }
 
-            let strength = ap.strength().await?;
-            let frequency = ap.frequency().await?;
-            let flags = ap.flags().await?;
-            let wpa_flags = ap.wpa_flags().await?;
-            let rsn_flags = ap.rsn_flags().await?;
+            let strength: u8 = ap_prop(&props, "Strength").unwrap_or(0);
+            let frequency: u32 = ap_prop(&props, "Frequency").unwrap_or(0);
+            let flags: u32 = ap_prop(&props, "Flags").unwrap_or(0);
+            let wpa_flags: u32 = ap_prop(&props, "WpaFlags").unwrap_or(0);
+            let rsn_flags: u32 = ap_prop(&props, "RsnFlags").unwrap_or(0);
+            // Only exposed by NM >= 1.46
+            let bandwidth_mhz: u32 = ap_prop(&props, "Bandwidth").unwrap_or(0);
+            let max_bitrate_kbps: u32 = ap_prop(&props, "MaxBitrate").unwrap_or(0);
+            let bssid: String = ap_prop(&props, "HwAddress").unwrap_or_default();
 
             let security = access_point::security_from_flags(flags, wpa_flags, rsn_flags);
+            let is_owe = access_point::is_enhanced_open(wpa_flags, rsn_flags);
             let band = Band::from_frequency(frequency);
             let ap_path_str = ap_path.to_string();
 
@@ -111,8 +329,18 @@ impl WifiManager {
                 .map(|active| *active == ap_path_str)
                 .unwrap_or(false);
 
-            let is_saved = saved_ssids.contains_key(&ssid);
-            let connection_path = saved_ssids.get(&ssid).cloned();
+            let saved = saved_ssids.get(&ssid).filter(|_| !is_hidden);
+            let is_saved = saved.is_some();
+            let connection_path = saved.map(|s| s.path.clone());
+            let locked_bssid = saved.and_then(|s| s.bssid.clone());
+
+            aps_by_ssid.entry(ssid.clone()).or_default().push(AccessPoint {
+                bssid: bssid.to_uppercase(),
+                strength,
+                band: band.clone(),
+                frequency_mhz: frequency,
+                path: ap_path_str.clone(),
+            });
 
             // Deduplication: keep the AP with the strongest signal per SSID
             match networks_by_ssid.get(&ssid) {
@@ -127,37 +355,53 @@ impl WifiManager {
                             ssid,
                             strength,
                             security,
+                            is_owe,
                             is_connected,
                             is_saved,
                             band,
+                            frequency_mhz: frequency,
+                            bandwidth_mhz,
+                            max_bitrate_kbps,
                             ap_path: ap_path_str,
                             connection_path,
+                            is_hidden,
+                            access_points: Vec::new(),
+                            locked_bssid,
                         },
                     );
                 }
             }
         }
 
-        // Collect and sort: connected first → saved → by strength descending
-        let mut networks: Vec<Network> = networks_by_ssid.into_values().collect();
-        networks.sort_by(|a, b| {
-            // Connected first
-            b.is_connected
-                .cmp(&a.is_connected)
-                // Then saved
-                .then(b.is_saved.cmp(&a.is_saved))
-                // Then by signal strength
-                .then(b.strength.cmp(&a.strength))
-        });
+        for (ssid, mut aps) in aps_by_ssid {
+            let Some(network) = networks_by_ssid.get_mut(&ssid) else {
+                continue;
+            };
+            aps.sort_by_key(|ap| std::cmp::Reverse(ap.strength));
+            // A locked profile must be activated on its own AP, not the strongest
+            if let Some(locked) = &network.locked_bssid
+                && let Some(ap) = aps.iter().find(|ap| &ap.bssid == locked)
+            {
+                network.ap_path = ap.path.clone();
+            }
+            network.access_points = aps;
+        }
+
+        // Unordered; callers sort for display (the list per `sort_order`)
+        Ok(networks_by_ssid.into_values().collect())
+    }
 
-        Ok(networks)
+    /// Look up a visible network by SSID.
+    pub async fn find_network(&self, ssid: &str) -> zbus::Result<Option<Network>> {
+        let networks = self.get_networks(false).await?;
+        Ok(networks.into_iter().find(|n| n.ssid == ssid))
     }
 
     /// Connect to a network.
     ///
     /// - If the network has a saved connection profile, reactivate it.
     /// - If it's open, call AddAndActivateConnection with empty settings.
-    /// - If it's secured (WPA2/WPA3), build settings with the provided password.
+    /// - If it's secured (WPA2/WPA3/WEP), build settings with the provided password.
     ///
     /// Returns the active connection path on success.
     pub async fn connect_to_network(
@@ -165,7 +409,7 @@ impl WifiManager {
         network: &Network,
         password: Option<&str>,
     ) -> zbus::Result<String> {
-        let nm = NetworkManagerProxy::new(&self.connection).await?;
+        let nm = &self.nm;
         let device_path = zbus::zvariant::ObjectPath::try_from(self.wifi_device_path.as_str())
             .map_err(|e| zbus::Error::Failure(format!("Invalid device path: {e}")))?;
         let ap_path = zbus::zvariant::ObjectPath::try_from(network.ap_path.as_str())
@@ -179,7 +423,8 @@ impl WifiManager {
             log::info!("Activating saved connection for '{}'", network.ssid);
             let active = nm
                 .activate_connection(&conn_path, &device_path, &ap_path)
-                .await?;
+                .await
+                .map_err(polkit::map_denied)?;
             return Ok(active.to_string());
         }
 
@@ -203,6 +448,13 @@ impl WifiManager {
                 log::info!("Connecting to WPA3 network '{}'", network.ssid);
                 super::connection::build_wpa3_settings(&network.ssid, psk)
             }
+            SecurityType::WEP => {
+                let key = password.ok_or_else(|| {
+                    zbus::Error::Failure("Key required for WEP network".into())
+                })?;
+                log::warn!("Connecting to legacy WEP network '{}'", network.ssid);
+                super::connection::build_wep_settings(&network.ssid, key)
+            }
             SecurityType::Enterprise => {
                 return Err(zbus::Error::Failure(
                     "Enterprise (802.1X) networks are not yet supported".into(),
@@ -212,53 +464,210 @@ impl WifiManager {
 
         let (_, active) = nm
             .add_and_activate_connection(settings, &device_path, &ap_path)
-            .await?;
+            .await
+            .map_err(polkit::map_denied)?;
         Ok(active.to_string())
     }
 
-    /// Disconnect from the current WiFi network.
-    pub async fn disconnect(&self) -> zbus::Result<()> {
-        let device = DeviceProxy::builder(&self.connection)
-            .path(self.wifi_device_path.clone())?
+    /// Connect to a hidden network by name; `password` selects WPA-PSK.
+    ///
+    /// Returns the active connection path on success.
+    pub async fn connect_hidden(&self, ssid: &str, password: Option<&str>) -> zbus::Result<String> {
+        let device_path = zbus::zvariant::ObjectPath::try_from(self.wifi_device_path.as_str())
+            .map_err(|e| zbus::Error::Failure(format!("Invalid device path: {e}")))?;
+        let root = zbus::zvariant::ObjectPath::try_from("/")
+            .map_err(|e| zbus::Error::Failure(format!("Invalid root path: {e}")))?;
+
+        log::info!("Connecting to hidden network '{ssid}'");
+        let settings = super::connection::build_hidden_settings(ssid, password);
+        let (_, active) = self
+            .nm
+            .add_and_activate_connection(settings, &device_path, &root)
+            .await
+            .map_err(polkit::map_denied)?;
+        Ok(active.to_string())
+    }
+
+    /// Wait until the given active connection is activated or fails.
+    ///
+    /// `active_path` is the path returned by `connect_to_network`; gives up
+    /// with `TimedOut` when the caller's `timeout` future fires.
+    pub async fn wait_for_activation(
+        &self,
+        active_path: &str,
+        timeout: impl Future<Output = ()>,
+    ) -> zbus::Result<ActivationOutcome> {
+        let active = ActiveConnectionProxy::builder(&self.connection)
+            .path(active_path)?
             .build()
             .await?;
+        let signals =
+            active_connection_signals::ActiveConnectionSignalsProxy::builder(&self.connection)
+                .path(active_path)?
+                .build()
+                .await?;
+        let mut changes = signals.receive_state_changed().await?;
+
+        // Activation may have settled before we subscribed; NM also drops
+        // the object once it is deactivated, which makes the read fail.
+        match active.state().await {
+            Ok(NM_ACTIVE_CONNECTION_STATE_ACTIVATED) => return Ok(ActivationOutcome::Activated),
+            Ok(NM_ACTIVE_CONNECTION_STATE_DEACTIVATED) | Err(_) => {
+                return Ok(ActivationOutcome::Failed(activation_failure_reason(0)));
+            }
+            Ok(_) => {}
+        }
 
-        let active_conn_path = device.active_connection().await?;
+        let settled = async {
+            while let Some(signal) = changes.next().await {
+                let Ok(args) = signal.args() else {
+                    continue;
+                };
+                match args.state {
+                    NM_ACTIVE_CONNECTION_STATE_ACTIVATED => return ActivationOutcome::Activated,
+                    NM_ACTIVE_CONNECTION_STATE_DEACTIVATED => {
+                        return ActivationOutcome::Failed(activation_failure_reason(args.reason));
+                    }
+                    _ => {}
+                }
+            }
+            ActivationOutcome::Failed(activation_failure_reason(0))
+        };
+        futures_util::pin_mut!(settled, timeout);
+
+        match future::select(settled, timeout).await {
+            Either::Left((outcome, _)) => Ok(outcome),
+            Either::Right(_) => Ok(ActivationOutcome::TimedOut),
+        }
+    }
+
+    /// Abort an in-flight activation (DeactivateConnection on its path).
+    pub async fn cancel_activation(&self, active_path: &str) -> zbus::Result<()> {
+        let path = zbus::zvariant::ObjectPath::try_from(active_path)
+            .map_err(|e| zbus::Error::Failure(format!("Invalid active path: {e}")))?;
+        log::info!("Cancelling activation {active_path}");
+        self.nm
+            .deactivate_connection(&path)
+            .await
+            .map_err(polkit::map_denied)
+    }
+
+    /// Disconnect from the current WiFi network.
+    pub async fn disconnect(&self) -> zbus::Result<()> {
+        let active_conn_path = self.device.active_connection().await?;
         if active_conn_path.as_str() == "/" {
             return Err(zbus::Error::Failure("Not connected to any network".into()));
         }
 
-        let nm = NetworkManagerProxy::new(&self.connection).await?;
         let path = zbus::zvariant::ObjectPath::try_from(active_conn_path.as_str())
             .map_err(|e| zbus::Error::Failure(format!("Invalid active connection path: {e}")))?;
-        nm.deactivate_connection(&path).await?;
+        self.nm
+            .deactivate_connection(&path)
+            .await
+            .map_err(polkit::map_denied)?;
 
         log::info!("Disconnected from WiFi");
         Ok(())
     }
 
     /// Enable or disable WiFi radio.
+    ///
+    /// May prompt through the polkit agent where changing the radio needs
+    /// authorization.
     pub async fn set_wifi_enabled(&self, enabled: bool) -> zbus::Result<()> {
-        let nm = NetworkManagerProxy::new(&self.connection).await?;
-        nm.set_wireless_enabled(enabled).await?;
+        polkit::set_property(
+            &self.connection,
+            NM_SERVICE,
+            NM_PATH,
+            NM_INTERFACE,
+            "WirelessEnabled",
+            Value::from(enabled),
+        )
+        .await?;
         log::info!("WiFi {}", if enabled { "enabled" } else { "disabled" });
         Ok(())
     }
 
     /// Check if WiFi radio is currently enabled.
     pub async fn is_wifi_enabled(&self) -> zbus::Result<bool> {
-        let nm = NetworkManagerProxy::new(&self.connection).await?;
-        nm.wireless_enabled().await
+        self.nm.wireless_enabled().await
+    }
+
+    /// Current upstream connectivity as last checked by NM.
+    pub async fn connectivity(&self) -> zbus::Result<Connectivity> {
+        Ok(self.nm.connectivity().await?.into())
+    }
+
+    /// SSID of the network the device is currently connected to, if any.
+    pub async fn active_ssid(&self) -> zbus::Result<Option<String>> {
+        let Ok(ap_path) = self.get_active_ap_path().await else {
+            return Ok(None);
+        };
+        let ap = AccessPointProxy::builder(&self.connection)
+            .path(ap_path.as_str())?
+            .build()
+            .await?;
+        let ssid_bytes = ap.ssid().await?;
+        Ok(Some(String::from_utf8_lossy(&ssid_bytes).to_string()))
+    }
+
+    /// Read signal, link rate and traffic counters for the active connection.
+    pub async fn get_link_stats(&self) -> zbus::Result<LinkStats> {
+        let ap_path = self.get_active_ap_path().await?;
+        let ap = AccessPointProxy::builder(&self.connection)
+            .path(ap_path.as_str())?
+            .build()
+            .await?;
+        let strength = ap.strength().await?;
+
+        let bitrate_kbps = self.wireless.bitrate().await.unwrap_or(0);
+
+        let stats = &self.stats;
+
+        // NM only updates the byte counters while a refresh rate is set
+        if stats.refresh_rate_ms().await.unwrap_or(0) == 0
+            && let Err(e) = stats.set_refresh_rate_ms(STATS_REFRESH_RATE_MS).await
+        {
+            log::debug!("Could not enable device statistics: {e}");
+        }
+
+        Ok(LinkStats {
+            strength,
+            bitrate_kbps,
+            rx_bytes: stats.rx_bytes().await.unwrap_or(0),
+            tx_bytes: stats.tx_bytes().await.unwrap_or(0),
+        })
     }
+
+    /// Read the IPv4 address and gateway of the active connection.
+    pub async fn ipv4_info(&self) -> zbus::Result<Ipv4Info> {
+        let config_path = self.device.ip4_config().await?;
+        if config_path.as_str() == "/" {
+            return Ok(Ipv4Info::default());
+        }
+        let config = IP4ConfigProxy::builder(&self.connection)
+            .path(config_path.as_str())?
+            .build()
+            .await?;
+
+        let address = config.address_data().await?.into_iter().find_map(|data| {
+            let address: String = ap_prop(&data, "address")?;
+            let prefix: u32 = ap_prop(&data, "prefix")?;
+            Some(format!("{address}/{prefix}"))
+        });
+        let gateway = config.gateway().await.ok().filter(|g| !g.is_empty());
+        Ok(Ipv4Info { address, gateway })
+    }
+
     /// Forget (delete) a saved network by its SSID.
     pub async fn forget_network(&self, ssid: &str) -> zbus::Result<()> {
         let saved = self.get_saved_wifi_ssids().await?;
-        if let Some(conn_path) = saved.get(ssid) {
+        if let Some(profile) = saved.get(ssid) {
             let conn = SettingsConnectionProxy::builder(&self.connection)
-                .path(conn_path.as_str())?
+                .path(profile.path.as_str())?
                 .build()
                 .await?;
-            conn.delete().await?;
+            conn.delete().await.map_err(polkit::map_denied)?;
             log::info!("Forgot network: {ssid}");
             Ok(())
         } else {
@@ -269,18 +678,132 @@ impl WifiManager {
         }
     }
 
+    /// Read the editable options (proxy, ...) of a saved profile.
+    pub async fn profile_options(&self, conn_path: &str) -> zbus::Result<ProfileOptions> {
+        let conn = self.settings_connection(conn_path).await?;
+        Ok(ProfileOptions::from_settings(&conn.get_settings().await?))
+    }
+
+    /// Write `options` into a saved profile; applies on its next activation.
+    pub async fn set_profile_options(
+        &self,
+        conn_path: &str,
+        options: &ProfileOptions,
+    ) -> zbus::Result<()> {
+        let conn = self.settings_connection(conn_path).await?;
+        let mut settings = read_profile(&conn).await?;
+        options.apply(&mut settings);
+        conn.update(settings).await.map_err(polkit::map_denied)?;
+        log::info!("Updated profile options for {conn_path}");
+        Ok(())
+    }
+
+    /// Whether systemd-resolved is running, i.e. `dns-over-tls` has any effect.
+    pub async fn has_systemd_resolved(&self) -> bool {
+        let dbus = match zbus::fdo::DBusProxy::new(&self.connection).await {
+            Ok(proxy) => proxy,
+            Err(e) => {
+                log::debug!("DBus proxy unavailable: {e}");
+                return false;
+            }
+        };
+        match zbus::names::BusName::try_from("org.freedesktop.resolve1") {
+            Ok(name) => dbus.name_has_owner(name).await.unwrap_or(false),
+            Err(_) => false,
+        }
+    }
+
+    /// SSID of the given AP if it matches a saved Wi-Fi profile.
+    pub async fn saved_ssid_for_ap(&self, ap_path: &str) -> zbus::Result<Option<String>> {
+        let ap = AccessPointProxy::builder(&self.connection)
+            .path(ap_path)?
+            .build()
+            .await?;
+        let ssid = String::from_utf8_lossy(&ap.ssid().await?).to_string();
+        if ssid.is_empty() {
+            return Ok(None);
+        }
+        let saved = self.get_saved_wifi_ssids().await?;
+        Ok(saved.contains_key(&ssid).then_some(ssid))
+    }
+
+    /// Pin a saved network to one AP by writing `802-11-wireless.bssid`.
+    ///
+    /// `None` removes the lock. If the network is connected it is reactivated
+    /// right away so the change takes effect without waiting for a roam.
+    pub async fn set_bssid_lock(&self, network: &Network, bssid: Option<&str>) -> zbus::Result<()> {
+        let conn_path = network.connection_path.as_deref().ok_or_else(|| {
+            zbus::Error::Failure(format!("No saved connection for '{}'", network.ssid))
+        })?;
+        let conn = self.settings_connection(conn_path).await?;
+        let mut settings = read_profile(&conn).await?;
+        let wireless = settings.entry("802-11-wireless".to_string()).or_default();
+        match bssid {
+            Some(bssid) => {
+                let bytes = access_point::parse_bssid(bssid).ok_or_else(|| {
+                    zbus::Error::Failure(format!("Invalid BSSID '{bssid}'"))
+                })?;
+                wireless.insert("bssid".to_string(), Value::from(bytes));
+                log::info!("Locking '{}' to {bssid}", network.ssid);
+            }
+            None => {
+                wireless.remove("bssid");
+                log::info!("Unlocking '{}' from its BSSID", network.ssid);
+            }
+        }
+        conn.update(settings).await.map_err(polkit::map_denied)?;
+
+        if network.is_connected {
+            let ap_path = bssid
+                .and_then(|b| network.access_points.iter().find(|ap| ap.bssid == b))
+                .map(|ap| ap.path.as_str())
+                .unwrap_or("/");
+            let conn_path = zbus::zvariant::ObjectPath::try_from(conn_path)
+                .map_err(|e| zbus::Error::Failure(format!("Invalid connection path: {e}")))?;
+            let device_path = zbus::zvariant::ObjectPath::try_from(self.wifi_device_path.as_str())
+                .map_err(|e| zbus::Error::Failure(format!("Invalid device path: {e}")))?;
+            let ap_path = zbus::zvariant::ObjectPath::try_from(ap_path)
+                .map_err(|e| zbus::Error::Failure(format!("Invalid AP path: {e}")))?;
+            self.nm
+                .activate_connection(&conn_path, &device_path, &ap_path)
+                .await
+                .map_err(polkit::map_denied)?;
+        }
+        Ok(())
+    }
+
     // ========================================================================
     // Private helpers
     // ========================================================================
 
-    /// Get the D-Bus path of the AP the device is currently connected to.
-    async fn get_active_ap_path(&self) -> zbus::Result<String> {
-        let device = DeviceProxy::builder(&self.connection)
-            .path(self.wifi_device_path.clone())?
+    /// Build a proxy for the saved profile at `conn_path`.
+    async fn settings_connection(
+        &self,
+        conn_path: &str,
+    ) -> zbus::Result<SettingsConnectionProxy<'static>> {
+        SettingsConnectionProxy::builder(&self.connection)
+            .path(conn_path.to_owned())?
+            .build()
+            .await
+    }
+
+    /// Read all properties of an access point with a single GetAll call.
+    async fn get_ap_properties(
+        &self,
+        ap_path: &OwnedObjectPath,
+    ) -> zbus::Result<HashMap<String, OwnedValue>> {
+        let props = zbus::fdo::PropertiesProxy::builder(&self.connection)
+            .destination(NM_SERVICE)?
+            .path(ap_path.clone())?
             .build()
             .await?;
+        let interface = InterfaceName::from_static_str_unchecked(NM_AP_INTERFACE);
+        Ok(props.get_all(interface).await?)
+    }
 
-        let active_conn_path = device.active_connection().await?;
+    /// Get the D-Bus path of the AP the device is currently connected to.
+    async fn get_active_ap_path(&self) -> zbus::Result<String> {
+        let active_conn_path = self.device.active_connection().await?;
         if active_conn_path.as_str() == "/" {
             return Err(zbus::Error::Failure("No active connection".into()));
         }
@@ -294,12 +817,11 @@ impl WifiManager {
         Ok(specific_object.to_string())
     }
 
-    /// Get a map of SSID → saved connection D-Bus path for WiFi connections.
-    async fn get_saved_wifi_ssids(&self) -> zbus::Result<HashMap<String, String>> {
-        let settings = SettingsProxy::new(&self.connection).await?;
-        let connections = settings.list_connections().await?;
+    /// Get a map of SSID → saved profile for WiFi connections.
+    async fn get_saved_wifi_ssids(&self) -> zbus::Result<HashMap<String, SavedWifi>> {
+        let connections = self.settings.list_connections().await?;
 
-        let mut ssid_map: HashMap<String, String> = HashMap::new();
+        let mut ssid_map: HashMap<String, SavedWifi> = HashMap::new();
 
         for conn_path in connections {
             let conn = SettingsConnectionProxy::builder(&self.connection)
@@ -325,7 +847,17 @@ impl WifiManager {
                         && let Ok(ssid_bytes) = <Vec<u8>>::try_from(ssid_val.clone()) {
                             let ssid = String::from_utf8_lossy(&ssid_bytes).to_string();
                             if !ssid.is_empty() {
-                                ssid_map.insert(ssid, conn_path.to_string());
+                                let bssid = wifi_settings
+                                    .get("bssid")
+                                    .and_then(|v| <Vec<u8>>::try_from(v.clone()).ok())
+                                    .and_then(|bytes| access_point::format_bssid(&bytes));
+                                ssid_map.insert(
+                                    ssid,
+                                    SavedWifi {
+                                        path: conn_path.to_string(),
+                                        bssid,
+                                    },
+                                );
                             }
                         }
             }
@@ -343,4 +875,95 @@ impl WifiManager {
     pub fn wifi_device_path(&self) -> &str {
         self.wifi_device_path.as_str()
     }
+
+    /// Wait until NM reports this manager's WiFi device as removed.
+    pub async fn wait_for_removal(&self) -> zbus::Result<()> {
+        let mut removed = self.nm.receive_device_removed().await?;
+        while let Some(signal) = removed.next().await {
+            if let Ok(args) = signal.args()
+                && args.device_path == self.wifi_device_path
+            {
+                return Ok(());
+            }
+        }
+        Err(zbus::Error::Failure("DeviceRemoved stream ended".into()))
+    }
+}
+
+/// Find the first NM device of type WiFi.
+async fn find_wifi_device(
+    connection: &zbus::Connection,
+    nm: &NetworkManagerProxy<'_>,
+) -> zbus::Result<Option<OwnedObjectPath>> {
+    for device_path in nm.get_devices().await? {
+        let device = DeviceProxy::builder(connection)
+            .path(device_path.clone())?
+            .build()
+            .await?;
+
+        if device.device_type().await? == NM_DEVICE_TYPE_WIFI {
+            return Ok(Some(device_path));
+        }
+    }
+    Ok(None)
+}
+
+/// NM settings that can carry secrets.
+const SECRET_SETTINGS: [&str; 8] = [
+    "802-11-wireless-security",
+    "802-1x",
+    "vpn",
+    "wireguard",
+    "gsm",
+    "cdma",
+    "pppoe",
+    "macsec",
+];
+
+/// Read a profile's settings in the shape `Update` expects.
+///
+/// `Update` replaces everything, so the stored secrets of every setting the
+/// profile has are merged in; otherwise saving an unrelated option would
+/// wipe the Wi-Fi password, the 802.1X password or a VPN's keys.
+async fn read_profile(
+    conn: &SettingsConnectionProxy<'_>,
+) -> zbus::Result<HashMap<String, HashMap<String, Value<'static>>>> {
+    let mut settings = conn.get_settings().await?;
+    for name in SECRET_SETTINGS {
+        if !settings.contains_key(name) {
+            continue;
+        }
+        // Agent-owned or unsaved secrets come back as NoSecrets; nothing to keep
+        match conn.get_secrets(name).await {
+            Ok(secrets) => {
+                for (setting, values) in secrets {
+                    settings.entry(setting).or_default().extend(values);
+                }
+            }
+            Err(e) => log::debug!("No stored {name} secrets: {e}"),
+        }
+    }
+
+    Ok(settings
+        .into_iter()
+        .map(|(setting, values)| {
+            let values = values.into_iter().map(|(k, v)| (k, v.into())).collect();
+            (setting, values)
+        })
+        .collect())
+}
+
+/// Milliseconds since boot including suspend — the clock NM uses for `LastScan`.
+fn boottime_ms() -> Option<u64> {
+    let uptime = std::fs::read_to_string("/proc/uptime").ok()?;
+    let secs: f64 = uptime.split_whitespace().next()?.parse().ok()?;
+    Some((secs * 1000.0) as u64)
+}
+
+/// Extract a typed value from a GetAll property map.
+fn ap_prop<T>(props: &HashMap<String, OwnedValue>, key: &str) -> Option<T>
+where
+    T: TryFrom<OwnedValue>,
+{
+    props.get(key).and_then(|v| T::try_from(v.clone()).ok())
 }
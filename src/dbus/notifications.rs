@@ -0,0 +1,123 @@
+//! Desktop notifications over org.freedesktop.Notifications (session bus).
+
+use std::collections::HashMap;
+
+use futures_util::StreamExt;
+use zbus::proxy;
+
+/// Proxy for org.freedesktop.Notifications
+#[proxy(
+    interface = "org.freedesktop.Notifications",
+    default_service = "org.freedesktop.Notifications",
+    default_path = "/org/freedesktop/Notifications"
+)]
+pub(crate) trait Notifications {
+    /// Show (or replace) a notification; returns its id
+    #[allow(clippy::too_many_arguments)]
+    fn notify(
+        &self,
+        app_name: &str,
+        replaces_id: u32,
+        app_icon: &str,
+        summary: &str,
+        body: &str,
+        actions: Vec<&str>,
+        hints: HashMap<&str, zbus::zvariant::Value<'_>>,
+        expire_timeout: i32,
+    ) -> zbus::Result<u32>;
+
+    /// The user picked one of the notification's actions
+    #[zbus(signal)]
+    fn action_invoked(&self, id: u32, action_key: String) -> zbus::Result<()>;
+
+    /// The notification expired or was dismissed
+    #[zbus(signal)]
+    fn notification_closed(&self, id: u32, reason: u32) -> zbus::Result<()>;
+}
+
+/// Application name shown by the notification daemon.
+const APP_NAME: &str = "wifi-manager";
+
+/// Action key most daemons bind to clicking the notification body.
+const DEFAULT_ACTION: &str = "default";
+
+/// Thin wrapper around the session-bus Notifications proxy.
+#[derive(Clone)]
+pub struct Notifier {
+    proxy: NotificationsProxy<'static>,
+}
+
+impl Notifier {
+    /// Connect to the session bus notification daemon.
+    pub async fn new() -> zbus::Result<Self> {
+        let connection = zbus::Connection::session().await?;
+        let proxy = NotificationsProxy::new(&connection).await?;
+        Ok(Self { proxy })
+    }
+
+    /// Show a plain notification; returns its id.
+    pub async fn notify(&self, icon: &str, summary: &str, body: &str) -> zbus::Result<u32> {
+        self.proxy
+            .notify(APP_NAME, 0, icon, summary, body, Vec::new(), HashMap::new(), -1)
+            .await
+    }
+
+    /// Show a notification with one action and wait until it is used or closed.
+    ///
+    /// Returns `true` if the user clicked the notification or its action.
+    pub async fn notify_with_action(
+        &self,
+        icon: &str,
+        summary: &str,
+        body: &str,
+        action_label: &str,
+    ) -> zbus::Result<bool> {
+        let picked = self
+            .notify_with_actions(icon, summary, body, &[(DEFAULT_ACTION, action_label)])
+            .await?;
+        Ok(picked.is_some())
+    }
+
+    /// Show a notification with `(key, label)` actions and wait for the answer.
+    ///
+    /// Returns the key of the action used, or `None` if the notification was
+    /// dismissed or expired.
+    pub async fn notify_with_actions(
+        &self,
+        icon: &str,
+        summary: &str,
+        body: &str,
+        actions: &[(&str, &str)],
+    ) -> zbus::Result<Option<String>> {
+        // Subscribe first so a quick click can't slip past us
+        let invoked = self
+            .proxy
+            .receive_action_invoked()
+            .await?
+            .filter_map(|signal| async move {
+                signal.args().ok().map(|a| (a.id, Some(a.action_key)))
+            });
+        let closed = self
+            .proxy
+            .receive_notification_closed()
+            .await?
+            .filter_map(|signal| async move { signal.args().ok().map(|a| (a.id, None)) });
+        let mut events = std::pin::pin!(futures_util::stream::select(invoked, closed));
+
+        let actions = actions
+            .iter()
+            .flat_map(|(key, label)| [*key, *label])
+            .collect();
+        let id = self
+            .proxy
+            .notify(APP_NAME, 0, icon, summary, body, actions, HashMap::new(), -1)
+            .await?;
+
+        while let Some((event_id, picked)) = events.next().await {
+            if event_id == id {
+                return Ok(picked);
+            }
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,283 @@
+//! Bluetooth file transfer over BlueZ OBEX (org.bluez.obex, session bus).
+//!
+//! obexd runs per user, so unlike the rest of BlueZ it lives on the session
+//! bus. Sending opens an Object Push session to the device, queues the file,
+//! and hands back the transfer object to watch. Receiving goes through the
+//! agent in `obex_agent`, which obexd asks before accepting a push.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use zbus::proxy;
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Value};
+
+/// Proxy for org.bluez.obex.Client1
+///
+/// Creates and removes OBEX sessions to remote devices.
+#[proxy(
+    interface = "org.bluez.obex.Client1",
+    default_service = "org.bluez.obex",
+    default_path = "/org/bluez/obex"
+)]
+pub(crate) trait ObexClient1 {
+    /// Open a session to `destination` (device address); `Target` picks the profile.
+    fn create_session(
+        &self,
+        destination: &str,
+        args: HashMap<&str, Value<'_>>,
+    ) -> zbus::Result<OwnedObjectPath>;
+
+    /// Close a session and cancel anything still queued on it.
+    fn remove_session(&self, session: &ObjectPath<'_>) -> zbus::Result<()>;
+}
+
+/// Proxy for org.bluez.obex.AgentManager1
+///
+/// Registers the agent that authorizes incoming Object Push transfers.
+#[proxy(
+    interface = "org.bluez.obex.AgentManager1",
+    default_service = "org.bluez.obex",
+    default_path = "/org/bluez/obex"
+)]
+pub(crate) trait ObexAgentManager1 {
+    /// Register an agent object.
+    fn register_agent(&self, agent: &ObjectPath<'_>) -> zbus::Result<()>;
+
+    /// Unregister a previously registered agent.
+    fn unregister_agent(&self, agent: &ObjectPath<'_>) -> zbus::Result<()>;
+}
+
+/// Proxy for org.bluez.obex.ObjectPush1
+///
+/// Implemented by Object Push sessions.
+#[proxy(
+    interface = "org.bluez.obex.ObjectPush1",
+    default_service = "org.bluez.obex"
+)]
+pub(crate) trait ObjectPush1 {
+    /// Queue a local file; returns the transfer object and its properties.
+    fn send_file(
+        &self,
+        sourcefile: &str,
+    ) -> zbus::Result<(OwnedObjectPath, HashMap<String, OwnedValue>)>;
+}
+
+/// Proxy for org.bluez.obex.Transfer1
+///
+/// One file in flight. obexd removes the object shortly after it finishes.
+#[proxy(
+    interface = "org.bluez.obex.Transfer1",
+    default_service = "org.bluez.obex"
+)]
+pub(crate) trait Transfer1 {
+    /// Abort the transfer.
+    fn cancel(&self) -> zbus::Result<()>;
+
+    /// "queued", "active", "suspended", "complete" or "error".
+    #[zbus(property)]
+    fn status(&self) -> zbus::Result<String>;
+
+    /// Total size in bytes (0 if unknown).
+    #[zbus(property)]
+    fn size(&self) -> zbus::Result<u64>;
+
+    /// Bytes sent or received so far; only present once active.
+    #[zbus(property)]
+    fn transferred(&self) -> zbus::Result<u64>;
+
+    /// File name as announced to / by the remote side.
+    #[zbus(property)]
+    fn name(&self) -> zbus::Result<String>;
+
+    /// Full local path of the file being sent or received.
+    #[zbus(property)]
+    fn filename(&self) -> zbus::Result<String>;
+
+    /// Session the transfer belongs to.
+    #[zbus(property)]
+    fn session(&self) -> zbus::Result<OwnedObjectPath>;
+}
+
+/// Proxy for org.bluez.obex.Session1
+#[proxy(
+    interface = "org.bluez.obex.Session1",
+    default_service = "org.bluez.obex"
+)]
+pub(crate) trait ObexSession1 {
+    /// Address of the remote device.
+    #[zbus(property)]
+    fn destination(&self) -> zbus::Result<String>;
+}
+
+/// State of an OBEX transfer as reported by obexd.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferStatus {
+    Queued,
+    Active,
+    Suspended,
+    Complete,
+    Error,
+}
+
+impl TransferStatus {
+    fn from_obex(status: &str) -> Self {
+        match status {
+            "active" => TransferStatus::Active,
+            "suspended" => TransferStatus::Suspended,
+            "complete" => TransferStatus::Complete,
+            "error" => TransferStatus::Error,
+            _ => TransferStatus::Queued,
+        }
+    }
+
+    /// Whether the transfer has ended, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        matches!(self, TransferStatus::Complete | TransferStatus::Error)
+    }
+}
+
+/// Snapshot of a transfer's progress.
+#[derive(Debug, Clone, Copy)]
+pub struct TransferProgress {
+    pub status: TransferStatus,
+    pub transferred: u64,
+    pub size: u64,
+}
+
+impl TransferProgress {
+    /// Completed fraction (0.0–1.0); 0 while the size is unknown.
+    pub fn fraction(&self) -> f64 {
+        if self.size == 0 {
+            return 0.0;
+        }
+        (self.transferred as f64 / self.size as f64).clamp(0.0, 1.0)
+    }
+}
+
+/// Read the progress of a transfer.
+///
+/// Values come from the proxy's property cache, which obexd keeps current via
+/// PropertiesChanged; it outlives the transfer object, so the final status is
+/// still readable after obexd removes it.
+async fn read_progress(transfer: &Transfer1Proxy<'static>) -> zbus::Result<TransferProgress> {
+    let status = TransferStatus::from_obex(&transfer.status().await?);
+    Ok(TransferProgress {
+        status,
+        transferred: transfer.transferred().await.unwrap_or(0),
+        size: transfer.size().await.unwrap_or(0),
+    })
+}
+
+/// Thin wrapper around the session-bus OBEX client.
+#[derive(Clone)]
+pub struct ObexManager {
+    connection: zbus::Connection,
+}
+
+impl ObexManager {
+    /// Connect to the session bus; obexd itself is started on first use.
+    pub async fn new() -> zbus::Result<Self> {
+        let connection = zbus::Connection::session().await?;
+        Ok(Self { connection })
+    }
+
+    /// Register the Object Push agent; incoming pushes arrive on the receiver.
+    pub async fn register_agent(
+        &self,
+    ) -> zbus::Result<async_channel::Receiver<super::obex_agent::PushRequest>> {
+        super::obex_agent::register(&self.connection).await
+    }
+
+    /// Follow a push accepted through the agent.
+    pub async fn incoming_transfer(&self, path: &str) -> zbus::Result<IncomingTransfer> {
+        let transfer = Transfer1Proxy::builder(&self.connection)
+            .path(path.to_string())?
+            .build()
+            .await?;
+        Ok(IncomingTransfer { transfer })
+    }
+
+    /// Push `path` to the device at `address` over Object Push.
+    ///
+    /// The device may ask its user to accept first; the transfer stays
+    /// queued until then.
+    pub async fn send_file(&self, address: &str, path: &Path) -> zbus::Result<OutgoingTransfer> {
+        let source = path
+            .to_str()
+            .ok_or_else(|| zbus::Error::Failure("File path is not valid UTF-8".into()))?;
+
+        let client = ObexClient1Proxy::new(&self.connection).await?;
+        let mut args = HashMap::new();
+        args.insert("Target", Value::from("opp"));
+        let session = client.create_session(address, args).await?;
+
+        let push = ObjectPush1Proxy::builder(&self.connection)
+            .path(session.clone())?
+            .build()
+            .await?;
+        let (transfer_path, _) = match push.send_file(source).await {
+            Ok(reply) => reply,
+            Err(e) => {
+                let _ = client.remove_session(&session).await;
+                return Err(e);
+            }
+        };
+        let transfer = Transfer1Proxy::builder(&self.connection)
+            .path(transfer_path)?
+            .build()
+            .await?;
+
+        log::info!("OBEX: sending {source} to {address}");
+        Ok(OutgoingTransfer {
+            client,
+            session,
+            transfer,
+        })
+    }
+}
+
+/// A file being pushed to a device; `close` it once finished.
+pub struct OutgoingTransfer {
+    client: ObexClient1Proxy<'static>,
+    session: OwnedObjectPath,
+    transfer: Transfer1Proxy<'static>,
+}
+
+impl OutgoingTransfer {
+    /// Current progress of the transfer.
+    pub async fn progress(&self) -> zbus::Result<TransferProgress> {
+        read_progress(&self.transfer).await
+    }
+
+    /// Abort the transfer; the device sees it as cancelled.
+    pub async fn cancel(&self) -> zbus::Result<()> {
+        self.transfer.cancel().await
+    }
+
+    /// Close the OBEX session.
+    pub async fn close(self) {
+        if let Err(e) = self.client.remove_session(&self.session).await {
+            log::debug!(
+                "OBEX: failed to remove session {}: {e}",
+                self.session.as_str()
+            );
+        }
+    }
+}
+
+/// A file being received from a device.
+pub struct IncomingTransfer {
+    transfer: Transfer1Proxy<'static>,
+}
+
+impl IncomingTransfer {
+    /// Current progress of the transfer.
+    pub async fn progress(&self) -> zbus::Result<TransferProgress> {
+        read_progress(&self.transfer).await
+    }
+
+    /// Where obexd is writing the file (inside its own root folder).
+    pub async fn filename(&self) -> zbus::Result<String> {
+        self.transfer.filename().await
+    }
+}
@@ -0,0 +1,136 @@
+//! OBEX Object Push agent — lets the user accept or decline incoming files.
+//!
+//! obexd asks the agent before it writes a pushed file. Requests are handed
+//! to the GTK side over a channel, the same way `bluez_agent` does it.
+
+use zbus::interface;
+use zbus::zvariant::OwnedObjectPath;
+
+use super::obex::{ObexSession1Proxy, Transfer1Proxy};
+
+/// Object path the agent is served at on the session bus.
+pub const OBEX_AGENT_PATH: &str = "/com/github/wifi_manager/ObexAgent";
+
+/// One incoming file waiting for the user; answer it with `respond`.
+pub struct PushRequest {
+    pub transfer_path: String,
+    /// Address of the sending device, if obexd reported it
+    pub address: Option<String>,
+    pub file_name: String,
+    /// Announced size in bytes (0 if unknown)
+    pub size: u64,
+    reply: async_channel::Sender<bool>,
+}
+
+impl PushRequest {
+    /// Accept or decline the push.
+    pub fn respond(self, accept: bool) {
+        // obexd may have given up already; nothing left to tell it then
+        let _ = self.reply.try_send(accept);
+    }
+}
+
+/// Errors returned to obexd, mapped to the names it expects.
+#[derive(Debug, zbus::DBusError)]
+#[zbus(prefix = "org.bluez.obex.Error")]
+enum ObexAgentError {
+    Rejected(String),
+}
+
+/// The org.bluez.obex.Agent1 object.
+struct ObexAgent {
+    requests: async_channel::Sender<PushRequest>,
+}
+
+#[interface(name = "org.bluez.obex.Agent1")]
+impl ObexAgent {
+    /// obexd unregistered the agent.
+    fn release(&self) {
+        log::info!("OBEX agent released");
+    }
+
+    /// Ask the user about an incoming push.
+    ///
+    /// Returns the name to store the file under; obexd writes it into its
+    /// own root folder.
+    async fn authorize_push(
+        &self,
+        transfer: OwnedObjectPath,
+        #[zbus(connection)] connection: &zbus::Connection,
+    ) -> Result<String, ObexAgentError> {
+        let proxy = Transfer1Proxy::builder(connection)
+            .path(transfer.clone())
+            .map_err(|e| ObexAgentError::Rejected(e.to_string()))?
+            .build()
+            .await
+            .map_err(|e| ObexAgentError::Rejected(e.to_string()))?;
+        let name = proxy.name().await.unwrap_or_default();
+        // Never let the sender pick a path outside obexd's folder
+        let file_name = std::path::Path::new(&name)
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .filter(|n| !n.is_empty())
+            .unwrap_or_else(|| "received-file".to_string());
+        let size = proxy.size().await.unwrap_or(0);
+        let address = match proxy.session().await {
+            Ok(session) => session_address(connection, session).await,
+            Err(_) => None,
+        };
+
+        log::info!("OBEX agent: incoming push of {file_name} ({size} bytes)");
+        let (reply, answer) = async_channel::bounded(1);
+        let request = PushRequest {
+            transfer_path: transfer.to_string(),
+            address,
+            file_name: file_name.clone(),
+            size,
+            reply,
+        };
+        self.requests
+            .send(request)
+            .await
+            .map_err(|_| ObexAgentError::Rejected("Panel is not running".into()))?;
+        match answer.recv().await {
+            Ok(true) => Ok(file_name),
+            _ => Err(ObexAgentError::Rejected("Transfer declined".into())),
+        }
+    }
+
+    /// The outstanding request timed out or was cancelled remotely.
+    fn cancel(&self) {
+        log::info!("OBEX agent: request cancelled");
+    }
+}
+
+/// Address of the device on the other end of `session`.
+async fn session_address(
+    connection: &zbus::Connection,
+    session: OwnedObjectPath,
+) -> Option<String> {
+    let proxy = ObexSession1Proxy::builder(connection)
+        .path(session)
+        .ok()?
+        .build()
+        .await
+        .ok()?;
+    proxy.destination().await.ok()
+}
+
+/// Serve the agent on `connection` and register it with obexd.
+pub(crate) async fn register(
+    connection: &zbus::Connection,
+) -> zbus::Result<async_channel::Receiver<PushRequest>> {
+    let (requests, receiver) = async_channel::unbounded();
+    connection
+        .object_server()
+        .at(OBEX_AGENT_PATH, ObexAgent { requests })
+        .await?;
+
+    let path = zbus::zvariant::ObjectPath::try_from(OBEX_AGENT_PATH)
+        .map_err(|e| zbus::Error::Failure(format!("Invalid agent path: {e}")))?;
+    let manager = super::obex::ObexAgentManager1Proxy::new(connection).await?;
+    manager.register_agent(&path).await?;
+
+    log::info!("OBEX Object Push agent registered");
+    Ok(receiver)
+}
@@ -0,0 +1,80 @@
+//! polkit-aware helpers for privileged NetworkManager and BlueZ calls.
+//!
+//! Changing system-wide profiles or radio state may need authorization.
+//! Those calls carry the ALLOW_INTERACTIVE_AUTHORIZATION flag so the
+//! service asks the session's polkit agent for a password instead of
+//! refusing outright, and a refusal is reported as a plain "Not authorized"
+//! rather than the raw D-Bus error.
+
+use zbus::proxy::MethodFlags;
+use zbus::zvariant::Value;
+
+/// Error text for a call polkit (or the service itself) refused.
+pub const NOT_AUTHORIZED: &str = "Not authorized";
+
+/// Whether `err` means the caller isn't allowed to do this.
+///
+/// NM answers with `*.PermissionDenied` (per interface), BlueZ with
+/// `org.bluez.Error.NotAuthorized`; the bus-level names cover a missing or
+/// dismissed agent prompt. Errors already passed through `map_denied`
+/// count too.
+pub fn is_not_authorized(err: &zbus::Error) -> bool {
+    match err {
+        zbus::Error::Failure(message) => message == NOT_AUTHORIZED,
+        zbus::Error::MethodError(name, _, _) => {
+            let name = name.as_str();
+            (name.starts_with("org.freedesktop.NetworkManager")
+                && name.ends_with(".PermissionDenied"))
+                || name == "org.bluez.Error.NotAuthorized"
+                || name == "org.freedesktop.DBus.Error.AccessDenied"
+                || name == "org.freedesktop.DBus.Error.InteractiveAuthorizationRequired"
+        }
+        zbus::Error::FDO(e) => matches!(
+            **e,
+            zbus::fdo::Error::AccessDenied(_)
+                | zbus::fdo::Error::InteractiveAuthorizationRequired(_)
+        ),
+        _ => false,
+    }
+}
+
+/// Turn an authorization failure into `NOT_AUTHORIZED`; other errors pass
+/// through unchanged.
+pub fn map_denied(err: zbus::Error) -> zbus::Error {
+    if is_not_authorized(&err) {
+        log::warn!("Not authorized: {err}");
+        zbus::Error::Failure(NOT_AUTHORIZED.into())
+    } else {
+        err
+    }
+}
+
+/// Set a D-Bus property, letting the service prompt for authorization.
+///
+/// Generated property setters can't carry method flags, so this calls
+/// `org.freedesktop.DBus.Properties.Set` directly.
+pub async fn set_property(
+    connection: &zbus::Connection,
+    destination: &str,
+    path: &str,
+    interface: &str,
+    name: &str,
+    value: Value<'_>,
+) -> zbus::Result<()> {
+    let proxy = zbus::Proxy::new(
+        connection,
+        destination.to_owned(),
+        path.to_owned(),
+        "org.freedesktop.DBus.Properties",
+    )
+    .await?;
+    proxy
+        .call_with_flags::<_, _, ()>(
+            "Set",
+            MethodFlags::AllowInteractiveAuth.into(),
+            &(interface, name, value),
+        )
+        .await
+        .map_err(map_denied)?;
+    Ok(())
+}
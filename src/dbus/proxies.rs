@@ -23,6 +23,7 @@ pub(crate) trait NetworkManager {
     fn get_devices(&self) -> zbus::Result<Vec<OwnedObjectPath>>;
 
     /// Activate an existing saved connection
+    #[zbus(allow_interactive_auth)]
     fn activate_connection(
         &self,
         connection: &zbus::zvariant::ObjectPath<'_>,
@@ -31,6 +32,7 @@ pub(crate) trait NetworkManager {
     ) -> zbus::Result<OwnedObjectPath>;
 
     /// Add a new connection and activate it (for connecting to new networks)
+    #[zbus(allow_interactive_auth)]
     fn add_and_activate_connection(
         &self,
         connection: HashMap<String, HashMap<String, zbus::zvariant::Value<'_>>>,
@@ -39,6 +41,7 @@ pub(crate) trait NetworkManager {
     ) -> zbus::Result<(OwnedObjectPath, OwnedObjectPath)>;
 
     /// Deactivate an active connection
+    #[zbus(allow_interactive_auth)]
     fn deactivate_connection(
         &self,
         active_connection: &zbus::zvariant::ObjectPath<'_>,
@@ -55,6 +58,18 @@ pub(crate) trait NetworkManager {
     /// List of active connections
     #[zbus(property)]
     fn active_connections(&self) -> zbus::Result<Vec<OwnedObjectPath>>;
+
+    /// Network connectivity state (1=none, 2=portal, 3=limited, 4=full)
+    #[zbus(property)]
+    fn connectivity(&self) -> zbus::Result<u32>;
+
+    /// Signal: a network device appeared (e.g. USB adapter plugged in)
+    #[zbus(signal)]
+    fn device_added(&self, device_path: OwnedObjectPath) -> zbus::Result<()>;
+
+    /// Signal: a network device disappeared
+    #[zbus(signal)]
+    fn device_removed(&self, device_path: OwnedObjectPath) -> zbus::Result<()>;
 }
 
 /// Proxy for org.freedesktop.NetworkManager.Device
@@ -71,6 +86,10 @@ pub(crate) trait Device {
     #[zbus(property)]
     fn active_connection(&self) -> zbus::Result<OwnedObjectPath>;
 
+    /// Current IPv4 configuration ("/" when not configured)
+    #[zbus(property, name = "Ip4Config")]
+    fn ip4_config(&self) -> zbus::Result<OwnedObjectPath>;
+
     /// Device state changed (new_state, old_state, reason)
     #[zbus(signal)]
     fn state_changed(&self, new_state: u32, old_state: u32, reason: u32) -> zbus::Result<()>;
@@ -90,6 +109,14 @@ pub(crate) trait Wireless {
     #[zbus(property)]
     fn access_points(&self) -> zbus::Result<Vec<OwnedObjectPath>>;
 
+    /// CLOCK_BOOTTIME timestamp (ms) of the last finished scan, -1 if never scanned
+    #[zbus(property)]
+    fn last_scan(&self) -> zbus::Result<i64>;
+
+    /// Negotiated link rate of the current connection in kbit/s
+    #[zbus(property)]
+    fn bitrate(&self) -> zbus::Result<u32>;
+
     /// Signal: a new access point appeared
     #[zbus(signal)]
     fn access_point_added(&self, access_point: OwnedObjectPath) -> zbus::Result<()>;
@@ -99,6 +126,29 @@ pub(crate) trait Wireless {
     fn access_point_removed(&self, access_point: OwnedObjectPath) -> zbus::Result<()>;
 }
 
+/// Proxy for org.freedesktop.NetworkManager.Device.Statistics
+#[proxy(
+    interface = "org.freedesktop.NetworkManager.Device.Statistics",
+    default_service = "org.freedesktop.NetworkManager"
+)]
+pub(crate) trait Statistics {
+    /// Counter refresh interval in ms (0 = statistics disabled)
+    #[zbus(property)]
+    fn refresh_rate_ms(&self) -> zbus::Result<u32>;
+
+    /// Set the counter refresh interval
+    #[zbus(property)]
+    fn set_refresh_rate_ms(&self, rate: u32) -> zbus::Result<()>;
+
+    /// Total bytes transmitted on the device
+    #[zbus(property)]
+    fn tx_bytes(&self) -> zbus::Result<u64>;
+
+    /// Total bytes received on the device
+    #[zbus(property)]
+    fn rx_bytes(&self) -> zbus::Result<u64>;
+}
+
 /// Proxy for org.freedesktop.NetworkManager.AccessPoint
 #[proxy(
     interface = "org.freedesktop.NetworkManager.AccessPoint",
@@ -125,6 +175,10 @@ pub(crate) trait AccessPoint {
 
     #[zbus(property)]
     fn hw_address(&self) -> zbus::Result<String>;
+
+    /// Channel width in MHz (NM >= 1.46)
+    #[zbus(property)]
+    fn bandwidth(&self) -> zbus::Result<u32>;
 }
 
 /// Proxy for org.freedesktop.NetworkManager.Connection.Active
@@ -148,6 +202,41 @@ pub(crate) trait ActiveConnection {
     fn connection_type(&self) -> zbus::Result<String>;
 }
 
+/// `StateChanged` signal of org.freedesktop.NetworkManager.Connection.Active.
+///
+/// Lives in its own proxy and module: on `ActiveConnection` it would clash with
+/// the `State` property stream, and at this level with `Device`'s signal types.
+pub(crate) mod active_connection_signals {
+    use zbus::proxy;
+
+    #[proxy(
+        interface = "org.freedesktop.NetworkManager.Connection.Active",
+        default_service = "org.freedesktop.NetworkManager"
+    )]
+    pub(crate) trait ActiveConnectionSignals {
+        /// Activation state changed (state, reason)
+        #[zbus(signal)]
+        fn state_changed(&self, state: u32, reason: u32) -> zbus::Result<()>;
+    }
+}
+
+/// Proxy for org.freedesktop.NetworkManager.IP4Config
+#[proxy(
+    interface = "org.freedesktop.NetworkManager.IP4Config",
+    default_service = "org.freedesktop.NetworkManager"
+)]
+pub(crate) trait IP4Config {
+    /// Addresses as dicts with "address" and "prefix"
+    #[zbus(property)]
+    fn address_data(
+        &self,
+    ) -> zbus::Result<Vec<HashMap<String, zbus::zvariant::OwnedValue>>>;
+
+    /// Default gateway ("" if none)
+    #[zbus(property)]
+    fn gateway(&self) -> zbus::Result<String>;
+}
+
 /// Proxy for org.freedesktop.NetworkManager.Settings
 #[proxy(
     interface = "org.freedesktop.NetworkManager.Settings",
@@ -170,6 +259,20 @@ pub(crate) trait SettingsConnection {
         &self,
     ) -> zbus::Result<HashMap<String, HashMap<String, zbus::zvariant::OwnedValue>>>;
 
+    /// Get the secrets of one setting (e.g. "802-11-wireless-security")
+    fn get_secrets(
+        &self,
+        setting_name: &str,
+    ) -> zbus::Result<HashMap<String, HashMap<String, zbus::zvariant::OwnedValue>>>;
+
+    /// Replace the connection settings (secrets omitted here are cleared)
+    #[zbus(allow_interactive_auth)]
+    fn update(
+        &self,
+        properties: HashMap<String, HashMap<String, zbus::zvariant::Value<'_>>>,
+    ) -> zbus::Result<()>;
+
     /// Delete this connection profile
+    #[zbus(allow_interactive_auth)]
     fn delete(&self) -> zbus::Result<()>;
 }
@@ -0,0 +1,402 @@
+//! StatusNotifierItem tray icon with a DBusMenu context menu.
+//!
+//! Served on its own session-bus connection under an
+//! `org.kde.StatusNotifierItem-<pid>-1` name and registered with the bar's
+//! StatusNotifierWatcher. Like the pairing agent, clicks and menu choices
+//! are handed to the GTK side over a channel.
+
+use std::collections::HashMap;
+
+use futures_util::StreamExt;
+use zbus::interface;
+use zbus::object_server::SignalEmitter;
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, Str, Structure, Value};
+
+/// Object path of the StatusNotifierItem.
+pub const ITEM_PATH: &str = "/StatusNotifierItem";
+/// Object path of the com.canonical.dbusmenu menu.
+pub const MENU_PATH: &str = "/MenuBar";
+
+const WATCHER_NAME: &str = "org.kde.StatusNotifierWatcher";
+const WATCHER_PATH: &str = "/StatusNotifierWatcher";
+
+/// Menu item IDs; 0 is the root.
+const MENU_TOGGLE_PANEL: i32 = 1;
+const MENU_TOGGLE_WIFI: i32 = 2;
+
+/// Events delivered to the panel by the tray icon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayEvent {
+    /// Icon clicked or "Show/hide panel" chosen
+    TogglePanel,
+    /// "Turn Wi-Fi on/off" chosen
+    ToggleWifi,
+}
+
+/// What the icon shows, pushed by the panel with `Tray::update`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrayStatus {
+    pub wifi_enabled: bool,
+    /// SSID of the connected network
+    pub ssid: Option<String>,
+    /// Signal strength of the connected network (0–100)
+    pub strength: u8,
+}
+
+impl TrayStatus {
+    /// Freedesktop icon name, following nm-applet's strength steps.
+    fn icon_name(&self) -> &'static str {
+        if !self.wifi_enabled {
+            return "network-wireless-disabled-symbolic";
+        }
+        if self.ssid.is_none() {
+            return "network-wireless-offline-symbolic";
+        }
+        match self.strength {
+            81.. => "network-wireless-signal-excellent-symbolic",
+            56..=80 => "network-wireless-signal-good-symbolic",
+            31..=55 => "network-wireless-signal-ok-symbolic",
+            6..=30 => "network-wireless-signal-weak-symbolic",
+            _ => "network-wireless-signal-none-symbolic",
+        }
+    }
+
+    /// One-line summary used as the title and tooltip.
+    fn summary(&self) -> String {
+        match (&self.ssid, self.wifi_enabled) {
+            (_, false) => "Wi-Fi off".to_string(),
+            (Some(ssid), true) => format!("{ssid} · {}%", self.strength),
+            (None, true) => "Not connected".to_string(),
+        }
+    }
+}
+
+/// SNI tooltip: (icon name, icon pixmaps as (width, height, ARGB), title, text).
+type ToolTip = (String, Vec<(i32, i32, Vec<u8>)>, String, String);
+
+/// The org.kde.StatusNotifierItem object.
+struct StatusNotifierItem {
+    status: TrayStatus,
+    events: async_channel::Sender<TrayEvent>,
+}
+
+#[interface(name = "org.kde.StatusNotifierItem")]
+impl StatusNotifierItem {
+    /// Primary click on the icon.
+    async fn activate(&self, _x: i32, _y: i32) {
+        let _ = self.events.send(TrayEvent::TogglePanel).await;
+    }
+
+    /// Middle click; nothing to do.
+    fn secondary_activate(&self, _x: i32, _y: i32) {}
+
+    /// Only called by hosts that don't read `Menu`; nothing to do.
+    fn context_menu(&self, _x: i32, _y: i32) {}
+
+    /// Scroll over the icon; nothing to do.
+    fn scroll(&self, _delta: i32, _orientation: String) {}
+
+    #[zbus(signal)]
+    async fn new_icon(emitter: &SignalEmitter<'_>) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn new_title(emitter: &SignalEmitter<'_>) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn new_tool_tip(emitter: &SignalEmitter<'_>) -> zbus::Result<()>;
+
+    #[zbus(property)]
+    fn category(&self) -> &str {
+        "Hardware"
+    }
+
+    #[zbus(property)]
+    fn id(&self) -> &str {
+        "wifi-manager"
+    }
+
+    #[zbus(property)]
+    fn title(&self) -> String {
+        self.status.summary()
+    }
+
+    #[zbus(property)]
+    fn status(&self) -> &str {
+        "Active"
+    }
+
+    #[zbus(property)]
+    fn icon_name(&self) -> &str {
+        self.status.icon_name()
+    }
+
+    #[zbus(property)]
+    fn tool_tip(&self) -> ToolTip {
+        (
+            self.status.icon_name().to_string(),
+            Vec::new(),
+            "Wi-Fi".to_string(),
+            self.status.summary(),
+        )
+    }
+
+    #[zbus(property)]
+    fn menu(&self) -> OwnedObjectPath {
+        OwnedObjectPath::try_from(MENU_PATH).expect("MENU_PATH is a valid object path")
+    }
+
+    #[zbus(property)]
+    fn item_is_menu(&self) -> bool {
+        false
+    }
+}
+
+/// A menu item's properties, keyed by DBusMenu property name.
+type MenuProps = HashMap<String, OwnedValue>;
+/// A menu node: (id, properties, children wrapped in variants).
+type MenuLayout = (i32, MenuProps, Vec<OwnedValue>);
+
+/// The com.canonical.dbusmenu object behind the icon's context menu.
+struct TrayMenu {
+    wifi_enabled: bool,
+    /// Bumped whenever the labels change, as LayoutUpdated requires
+    revision: u32,
+    events: async_channel::Sender<TrayEvent>,
+}
+
+impl TrayMenu {
+    fn items(&self) -> Vec<(i32, MenuProps)> {
+        let wifi_label = if self.wifi_enabled {
+            "Turn Wi-Fi off"
+        } else {
+            "Turn Wi-Fi on"
+        };
+        vec![
+            (MENU_TOGGLE_PANEL, label("Show/hide panel")),
+            (MENU_TOGGLE_WIFI, label(wifi_label)),
+        ]
+    }
+
+    fn props(&self, id: i32) -> Option<MenuProps> {
+        if id == 0 {
+            let mut props = MenuProps::new();
+            props.insert("children-display".into(), Str::from("submenu").into());
+            return Some(props);
+        }
+        self.items()
+            .into_iter()
+            .find(|(item, _)| *item == id)
+            .map(|(_, props)| props)
+    }
+}
+
+fn label(text: &str) -> MenuProps {
+    let mut props = MenuProps::new();
+    props.insert("label".into(), Str::from(text.to_string()).into());
+    props
+}
+
+#[interface(name = "com.canonical.dbusmenu")]
+impl TrayMenu {
+    /// The menu tree: a root with one level of plain items.
+    fn get_layout(
+        &self,
+        parent_id: i32,
+        _recursion_depth: i32,
+        _property_names: Vec<String>,
+    ) -> zbus::fdo::Result<(u32, MenuLayout)> {
+        let props = self
+            .props(parent_id)
+            .ok_or_else(|| zbus::fdo::Error::InvalidArgs(format!("No menu item {parent_id}")))?;
+        let mut children = Vec::new();
+        if parent_id == 0 {
+            for (id, props) in self.items() {
+                let item = Structure::from((id, props, Vec::<OwnedValue>::new()));
+                let item = OwnedValue::try_from(Value::from(item))
+                    .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+                children.push(item);
+            }
+        }
+        Ok((self.revision, (parent_id, props, children)))
+    }
+
+    fn get_group_properties(
+        &self,
+        ids: Vec<i32>,
+        _property_names: Vec<String>,
+    ) -> Vec<(i32, MenuProps)> {
+        ids.into_iter()
+            .filter_map(|id| self.props(id).map(|props| (id, props)))
+            .collect()
+    }
+
+    fn get_property(&self, id: i32, name: String) -> zbus::fdo::Result<OwnedValue> {
+        self.props(id)
+            .and_then(|mut props| props.remove(&name))
+            .ok_or_else(|| zbus::fdo::Error::InvalidArgs(format!("No property {name} on {id}")))
+    }
+
+    async fn event(&self, id: i32, event_id: String, _data: OwnedValue, _timestamp: u32) {
+        if event_id != "clicked" {
+            return;
+        }
+        let event = match id {
+            MENU_TOGGLE_PANEL => TrayEvent::TogglePanel,
+            MENU_TOGGLE_WIFI => TrayEvent::ToggleWifi,
+            _ => return,
+        };
+        let _ = self.events.send(event).await;
+    }
+
+    /// Returns the IDs that weren't found.
+    async fn event_group(&self, events: Vec<(i32, String, OwnedValue, u32)>) -> Vec<i32> {
+        let mut not_found = Vec::new();
+        for (id, event_id, data, timestamp) in events {
+            if self.props(id).is_none() {
+                not_found.push(id);
+                continue;
+            }
+            self.event(id, event_id, data, timestamp).await;
+        }
+        not_found
+    }
+
+    /// The menu never changes on open.
+    fn about_to_show(&self, _id: i32) -> bool {
+        false
+    }
+
+    fn about_to_show_group(&self, _ids: Vec<i32>) -> (Vec<i32>, Vec<i32>) {
+        (Vec::new(), Vec::new())
+    }
+
+    #[zbus(signal)]
+    async fn layout_updated(
+        emitter: &SignalEmitter<'_>,
+        revision: u32,
+        parent: i32,
+    ) -> zbus::Result<()>;
+
+    #[zbus(property)]
+    fn version(&self) -> u32 {
+        3
+    }
+
+    #[zbus(property)]
+    fn text_direction(&self) -> &str {
+        "ltr"
+    }
+
+    #[zbus(property)]
+    fn status(&self) -> &str {
+        "normal"
+    }
+
+    #[zbus(property)]
+    fn icon_theme_path(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Handle to the registered tray icon.
+#[derive(Clone)]
+pub struct Tray {
+    conn: zbus::Connection,
+    /// Bus name the item is served under
+    name: String,
+}
+
+impl Tray {
+    /// Serve the icon and its menu and register them with the tray host.
+    ///
+    /// A missing StatusNotifierWatcher (no bar with a tray yet) isn't an
+    /// error; `keep_registered` registers once one appears.
+    pub async fn register() -> zbus::Result<(Self, async_channel::Receiver<TrayEvent>)> {
+        let (events, receiver) = async_channel::unbounded();
+        let name = format!("org.kde.StatusNotifierItem-{}-1", std::process::id());
+        let item = StatusNotifierItem {
+            status: TrayStatus::default(),
+            events: events.clone(),
+        };
+        let menu = TrayMenu {
+            wifi_enabled: false,
+            revision: 0,
+            events,
+        };
+
+        let conn = zbus::connection::Builder::session()?
+            .name(name.as_str())?
+            .serve_at(ITEM_PATH, item)?
+            .serve_at(MENU_PATH, menu)?
+            .build()
+            .await?;
+
+        let tray = Self { conn, name };
+        if let Err(e) = tray.register_with_watcher().await {
+            log::warn!("No tray (StatusNotifierWatcher) yet, waiting for one: {e}");
+        }
+        Ok((tray, receiver))
+    }
+
+    /// Register again whenever a StatusNotifierWatcher takes its name.
+    ///
+    /// The watcher forgets its items when the bar restarts, and the bar may
+    /// start after the daemon. Runs until the bus connection closes.
+    pub async fn keep_registered(&self) -> zbus::Result<()> {
+        let dbus = zbus::fdo::DBusProxy::new(&self.conn).await?;
+        let mut owner_changes = dbus
+            .receive_name_owner_changed_with_args(&[(0, WATCHER_NAME)])
+            .await?;
+        while let Some(signal) = owner_changes.next().await {
+            let Ok(args) = signal.args() else {
+                continue;
+            };
+            if args.new_owner().is_none() {
+                continue;
+            }
+            if let Err(e) = self.register_with_watcher().await {
+                log::warn!("Failed to register the tray icon with the new watcher: {e}");
+            }
+        }
+        Ok(())
+    }
+
+    async fn register_with_watcher(&self) -> zbus::Result<()> {
+        self.conn
+            .call_method(
+                Some(WATCHER_NAME),
+                WATCHER_PATH,
+                Some(WATCHER_NAME),
+                "RegisterStatusNotifierItem",
+                &(self.name.as_str(),),
+            )
+            .await?;
+        log::info!("Tray icon registered as {}", self.name);
+        Ok(())
+    }
+
+    /// Show `status` on the icon, its tooltip and the menu labels.
+    pub async fn update(&self, status: TrayStatus) -> zbus::Result<()> {
+        let object_server = self.conn.object_server();
+
+        let menu_ref = object_server.interface::<_, TrayMenu>(MENU_PATH).await?;
+        {
+            let mut menu = menu_ref.get_mut().await;
+            if menu.wifi_enabled != status.wifi_enabled {
+                menu.wifi_enabled = status.wifi_enabled;
+                menu.revision += 1;
+                TrayMenu::layout_updated(menu_ref.signal_emitter(), menu.revision, 0).await?;
+            }
+        }
+
+        let item_ref = object_server
+            .interface::<_, StatusNotifierItem>(ITEM_PATH)
+            .await?;
+        item_ref.get_mut().await.status = status;
+        let emitter = item_ref.signal_emitter();
+        StatusNotifierItem::new_icon(emitter).await?;
+        StatusNotifierItem::new_title(emitter).await?;
+        StatusNotifierItem::new_tool_tip(emitter).await?;
+        Ok(())
+    }
+}
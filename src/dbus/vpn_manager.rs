@@ -7,6 +7,7 @@ use std::collections::HashMap;
 
 use zbus::zvariant::{ObjectPath, OwnedObjectPath};
 
+use super::polkit;
 use super::proxies::{
     ActiveConnectionProxy, NetworkManagerProxy, SettingsConnectionProxy, SettingsProxy,
 };
@@ -137,14 +138,18 @@ impl VpnManager {
         let root = ObjectPath::try_from("/")
             .map_err(|e| zbus::Error::Failure(format!("Invalid root path: {e}")))?;
 
-        nm.activate_connection(&conn_path, &root, &root).await
+        nm.activate_connection(&conn_path, &root, &root)
+            .await
+            .map_err(polkit::map_denied)
     }
 
     pub async fn disconnect(&self, active_path: &str) -> zbus::Result<()> {
         let nm = NetworkManagerProxy::new(&self.conn).await?;
         let act_path = ObjectPath::try_from(active_path)
             .map_err(|e| zbus::Error::Failure(format!("Invalid active path: {e}")))?;
-        nm.deactivate_connection(&act_path).await
+        nm.deactivate_connection(&act_path)
+            .await
+            .map_err(polkit::map_denied)
     }
 
     pub async fn delete_profile(&self, connection_path: &str) -> zbus::Result<()> {
@@ -154,6 +159,6 @@ impl VpnManager {
             .path(conn_path)?
             .build()
             .await?;
-        conn.delete().await
+        conn.delete().await.map_err(polkit::map_denied)
     }
 }
@@ -1,4 +1,5 @@
 mod app;
+mod cli;
 mod config;
 mod controls;
 mod daemon;
@@ -6,22 +7,52 @@ mod dbus;
 mod state;
 mod ui;
 
+use std::cell::OnceCell;
+use std::rc::Rc;
+use std::sync::atomic::Ordering;
+
 use clap::Parser;
 use gtk4::Application;
-use gtk4::glib;
 use gtk4::prelude::*;
+use gtk4::{gio, glib};
 
 /// A floating WiFi manager for Wayland compositors (Hyprland/Sway)
 #[derive(Parser, Debug)]
 #[command(name = "wifi-manager", version, about)]
 struct Args {
-    /// Toggle the panel visibility (sends signal to running daemon)
-    #[arg(long)]
-    toggle: bool,
+    /// Toggle the panel visibility of the running instance;
+    /// with a tab, the panel opens on that tab
+    #[arg(
+        long,
+        value_name = "TAB",
+        num_args = 0..=1,
+        value_parser = daemon::PanelTab::NAMES
+    )]
+    toggle: Option<Option<String>>,
+
+    /// Show the panel of the running instance
+    #[arg(long, conflicts_with_all = ["toggle", "hide"])]
+    show: bool,
+
+    /// Hide the panel of the running instance
+    #[arg(long, conflicts_with = "toggle")]
+    hide: bool,
 
-    /// Reload config and CSS (sends signal to running daemon)
+    /// Reload config and CSS of the running instance
     #[arg(long)]
     reload: bool,
+
+    /// Replace a broken config.toml with the last good copy (config.toml.bak)
+    #[arg(long)]
+    restore_config: bool,
+
+    /// Use this config file instead of ~/.config/wifi-manager/config.toml
+    /// (a style.css next to it replaces the user theme too)
+    #[arg(long, value_name = "PATH")]
+    config: Option<std::path::PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<cli::Command>,
 }
 
 const APP_ID: &str = "com.github.wifi_manager.WifiManager";
@@ -32,51 +63,51 @@ fn main() {
 
     let args = Args::parse();
 
-    if args.toggle {
-        // Send Toggle() to running daemon and exit
-        let rt = glib::MainContext::default();
-        rt.block_on(async {
-            if daemon::is_instance_running().await {
-                match daemon::send_toggle().await {
-                    Ok(_) => log::info!("Toggle sent to running instance"),
-                    Err(e) => {
-                        log::error!("Failed to send toggle: {e}");
-                        eprintln!("Error: could not toggle — is wifi-manager running?");
-                    }
-                }
-            } else {
-                eprintln!("No running instance found. Start with: wifi-manager");
-            }
-        });
+    if let Some(path) = &args.config {
+        config::Config::set_path(path.clone());
+    }
+
+    if let Some(command) = args.command {
+        // Headless: talk to NetworkManager and exit without starting GTK
+        cli::run(command);
         return;
     }
 
-    if args.reload {
-        // Send Reload() to running daemon and exit
-        let rt = glib::MainContext::default();
-        rt.block_on(async {
-            if daemon::is_instance_running().await {
-                match daemon::send_reload().await {
-                    Ok(_) => {
-                        log::info!("Reload sent to running instance");
-                        println!("Config and CSS reloaded");
-                    }
-                    Err(e) => {
-                        log::error!("Failed to send reload: {e}");
-                        eprintln!("Error: could not reload — is wifi-manager running?");
-                    }
-                }
-            } else {
-                eprintln!("No running instance found. Start with: wifi-manager");
+    if args.restore_config {
+        match config::Config::restore_backup() {
+            Ok(path) => println!("Restored last good config to {}", path.display()),
+            Err(e) => {
+                eprintln!("Error: could not restore config backup: {e}");
+                std::process::exit(1);
             }
-        });
+        }
         return;
     }
 
-    // Start the GTK application (daemon mode)
-    log::info!("Starting wifi-manager daemon");
+    // Start the GTK application (daemon mode). A second launch becomes a
+    // remote instance that forwards its arguments to the running one.
+    let app = Application::builder()
+        .application_id(APP_ID)
+        .flags(gio::ApplicationFlags::HANDLES_COMMAND_LINE)
+        .build();
 
-    let app = Application::builder().application_id(APP_ID).build();
+    if let Err(e) = app.register(gio::Cancellable::NONE) {
+        eprintln!("Error: could not register on the session bus: {e}");
+        std::process::exit(1);
+    }
+    if app.is_remote() {
+        // The running instance keeps its own config; don't act on a flag it ignores
+        if args.config.is_some() {
+            eprintln!("Error: wifi-manager is already running; quit it to use --config");
+            std::process::exit(1);
+        }
+    } else if args.toggle.is_some() || args.show || args.hide || args.reload {
+        eprintln!("No running instance found. Start with: wifi-manager");
+        return;
+    } else {
+        log::info!("Starting wifi-manager daemon");
+        config::Config::remember_good();
+    }
 
     // Catch kill signals to cleanly shut down GTK and drop hardware locks
     const SIGINT: i32 = 2;
@@ -96,7 +127,32 @@ fn main() {
         glib::ControlFlow::Break
     });
 
-    app.connect_activate(|app| {
+    // Set once the panel is built; later launches act on it
+    let panel_cell: Rc<OnceCell<daemon::PanelState>> = Rc::default();
+
+    let panel_for_cmdline = Rc::clone(&panel_cell);
+    app.connect_command_line(move |app, cmdline| {
+        if !cmdline.is_remote() {
+            // This process's own launch; its flags were handled in main()
+            app.activate();
+            return glib::ExitCode::SUCCESS;
+        }
+        let args = match Args::try_parse_from(cmdline.arguments()) {
+            Ok(args) => args,
+            Err(e) => {
+                log::warn!("Ignoring arguments from a second launch: {e}");
+                return glib::ExitCode::FAILURE;
+            }
+        };
+        let Some(panel_state) = panel_for_cmdline.get() else {
+            log::warn!("Second launch arrived before the panel was built");
+            return glib::ExitCode::FAILURE;
+        };
+        handle_remote_args(&args, panel_state);
+        glib::ExitCode::SUCCESS
+    });
+
+    app.connect_activate(move |app| {
         log::info!("Application activated");
 
         // Build the UI (starts hidden)
@@ -125,6 +181,42 @@ fn main() {
             });
         });
 
+        let _ = panel_cell.set(panel_state.clone());
+
+        // Tab switches for ShowTab(), dispatched the same way; queued before
+        // the present() from show(), so the panel opens on the right tab
+        let tab_refs: (
+            glib::SendWeakRef<gtk4::ToggleButton>,
+            glib::SendWeakRef<gtk4::ToggleButton>,
+            glib::SendWeakRef<gtk4::ToggleButton>,
+            glib::SendWeakRef<gtk4::ToggleButton>,
+        ) = {
+            use gtk4::glib::object::ObjectExt;
+            (
+                widgets.wifi_tab.downgrade().into(),
+                widgets.bt_tab.downgrade().into(),
+                widgets.wifi_networks_tab.downgrade().into(),
+                widgets.wifi_vpn_tab.downgrade().into(),
+            )
+        };
+        panel_state.set_tab_handler(move |tab| {
+            let tab_refs = tab_refs.clone();
+            glib::MainContext::default().invoke(move || {
+                let (wifi_tab, bt_tab, networks_tab, vpn_tab) = tab_refs;
+                let (main_tab, subtab) = match tab {
+                    daemon::PanelTab::Wifi => (wifi_tab, Some(networks_tab)),
+                    daemon::PanelTab::Vpn => (wifi_tab, Some(vpn_tab)),
+                    daemon::PanelTab::Bluetooth => (bt_tab, None),
+                };
+                if let Some(button) = main_tab.upgrade() {
+                    button.set_active(true);
+                }
+                if let Some(button) = subtab.and_then(|s| s.upgrade()) {
+                    button.set_active(true);
+                }
+            });
+        });
+
         // Register the D-Bus daemon service
         let panel_state_clone = panel_state.clone();
         glib::spawn_future_local(async move {
@@ -144,7 +236,21 @@ fn main() {
         // Connect to NetworkManager and set up the app controller
         let panel_state_for_app = panel_state.clone();
         glib::spawn_future_local(async move {
-            match dbus::network_manager::WifiManager::new().await {
+            use dbus::network_manager::{NO_WIFI_DEVICE, WifiManager};
+
+            let wifi = match WifiManager::new().await {
+                // NM is up but the adapter may be a dongle that isn't plugged in yet
+                Err(zbus::Error::Failure(msg)) if msg == NO_WIFI_DEVICE => {
+                    log::warn!("No WiFi device found, waiting for one to be added");
+                    widgets
+                        .status_label
+                        .set_text("Waiting for a Wi-Fi adapter...");
+                    WifiManager::wait_for_device().await
+                }
+                other => other,
+            };
+
+            match wifi {
                 Ok(wifi) => {
                     log::info!("NetworkManager D-Bus connection established");
                     let config = config::Config::load();
@@ -172,7 +278,17 @@ fn main() {
         });
     });
 
-    app.run();
+    let status = app.run();
+    if app.is_remote() {
+        if status != glib::ExitCode::SUCCESS {
+            eprintln!("Error: wifi-manager is already running but did not respond");
+            std::process::exit(1);
+        }
+        if args.reload {
+            println!("Config and CSS reloaded");
+        }
+        return;
+    }
     
     // Allow pending D-Bus responses and GTK callbacks to complete before process exit.
     // Iterating the main context processes the teardown events gracefully.
@@ -184,3 +300,25 @@ fn main() {
         ctx.iteration(false);
     }
 }
+
+/// Act on the flags of a second launch, forwarded by GApplication.
+///
+/// `--reload` can be combined with a visibility flag; a bare launch (e.g.
+/// from an app launcher) toggles the panel.
+fn handle_remote_args(args: &Args, panel_state: &daemon::PanelState) {
+    if args.reload {
+        log::info!("Reload requested by a second launch");
+        panel_state.reload_requested.store(true, Ordering::Relaxed);
+    }
+    if args.show {
+        panel_state.show();
+    } else if args.hide {
+        panel_state.hide();
+    } else if let Some(Some(tab)) = &args.toggle {
+        if let Some(tab) = daemon::PanelTab::from_name(tab) {
+            panel_state.toggle_tab(tab);
+        }
+    } else if args.toggle.is_some() || !args.reload {
+        panel_state.toggle();
+    }
+}
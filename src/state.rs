@@ -1,9 +1,10 @@
 //! Dynamic runtime state persisted to `~/.config/wifi-manager/state.toml`.
 //! This file is managed entirely by the application and is separate from
-//! the user's static `config.toml` which is never written to.
+//! the user's static `config.toml`, which is only written by `--restore-config`.
 
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
 /// Persisted state for the Night Mode control.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,11 +29,18 @@ impl Default for NightModeState {
 pub struct AppStateStore {
     #[serde(default)]
     pub night_mode: NightModeState,
+    /// "Don't ask again" was ticked on the unencrypted network warning.
+    #[serde(default)]
+    pub skip_open_network_warning: bool,
+    /// Address of the Bluetooth adapter picked in the adapter selector.
+    #[serde(default)]
+    pub bt_adapter: Option<String>,
 }
 
 impl AppStateStore {
     /// Load dynamic state from `~/.config/wifi-manager/state.toml`.
-    /// Falls back to defaults silently if the file is missing or malformed.
+    /// Falls back to `state.toml.bak`, then to defaults, if the file is
+    /// missing or malformed.
     pub fn load() -> Self {
         let Some(path) = state_file_path() else {
             return Self::default();
@@ -49,17 +57,45 @@ impl AppStateStore {
                     store
                 }
                 Err(e) => {
-                    log::warn!("Failed to parse state file: {e}, using defaults");
-                    Self::default()
+                    log::warn!("Failed to parse state file: {e}, trying backup");
+                    Self::load_backup(&path)
                 }
             },
             Err(e) => {
-                log::warn!("Failed to read state file: {e}, using defaults");
+                log::warn!("Failed to read state file: {e}, trying backup");
+                Self::load_backup(&path)
+            }
+        }
+    }
+
+    /// Load the `.bak` copy kept by `save`, or defaults if it's unusable too.
+    fn load_backup(path: &Path) -> Self {
+        let backup = backup_path(path);
+        let store = std::fs::read_to_string(&backup)
+            .ok()
+            .and_then(|contents| toml::from_str::<AppStateStore>(&contents).ok());
+        match store {
+            Some(store) => {
+                log::warn!("Restored state from {:?}", backup);
+                store
+            }
+            None => {
+                log::warn!("No usable state backup, using defaults");
                 Self::default()
             }
         }
     }
 
+    /// Re-read the file, apply `change` and save.
+    ///
+    /// Use this instead of `save()` on a long-lived copy so writers of
+    /// different fields don't overwrite each other with stale values.
+    pub fn update(change: impl FnOnce(&mut AppStateStore)) {
+        let mut store = Self::load();
+        change(&mut store);
+        store.save();
+    }
+
     /// Persist the current state to `~/.config/wifi-manager/state.toml`.
     /// Creates the directory if it doesn't exist. Does not touch `config.toml`.
     pub fn save(&self) {
@@ -77,7 +113,7 @@ impl AppStateStore {
 
         match toml::to_string_pretty(self) {
             Ok(contents) => {
-                if let Err(e) = std::fs::write(&path, contents) {
+                if let Err(e) = write_atomic(&path, &contents) {
                     log::warn!("Failed to write state file: {e}");
                 } else {
                     log::debug!("State saved to {:?}", path);
@@ -88,6 +124,47 @@ impl AppStateStore {
     }
 }
 
+/// Replace `path` with `contents` without ever leaving it half-written.
+///
+/// Writes a sibling temp file, syncs it, keeps the previous version as
+/// `<name>.bak`, then renames the temp file over `path`.
+pub fn write_atomic(path: &Path, contents: &str) -> std::io::Result<()> {
+    replace_file(path, contents, true)
+}
+
+/// Like `write_atomic`, without keeping the previous version (for files
+/// that are themselves backups).
+pub fn write_atomic_without_backup(path: &Path, contents: &str) -> std::io::Result<()> {
+    replace_file(path, contents, false)
+}
+
+fn replace_file(path: &Path, contents: &str, keep_backup: bool) -> std::io::Result<()> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp = path.with_file_name(tmp_name);
+
+    {
+        let mut file = std::fs::File::create(&tmp)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+    }
+
+    if keep_backup
+        && path.exists()
+        && let Err(e) = std::fs::copy(path, backup_path(path))
+    {
+        log::warn!("Failed to back up {:?}: {e}", path);
+    }
+    std::fs::rename(&tmp, path)
+}
+
+/// `<path>.bak` — the previous version kept by `write_atomic`.
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
 /// Returns the path to `~/.config/wifi-manager/state.toml`.
 fn state_file_path() -> Option<PathBuf> {
     let home = std::env::var("HOME").ok()?;
@@ -0,0 +1,217 @@
+//! Modal editor for the advanced options of a saved Wi-Fi profile.
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Button, DropDown, Entry, Label, Orientation, Window};
+
+use crate::dbus::access_point;
+use crate::dbus::connection::{DnsOverTls, MAC_ADDRESS_MODES, ProfileOptions, ProxyMode};
+
+/// Proxy choices in dropdown order.
+const PROXY_CHOICES: [&str; 4] = ["None", "Automatic (WPAD)", "PAC URL", "Manual"];
+
+/// DNS-over-TLS choices in dropdown order, matching `DNS_OVER_TLS_MODES`.
+const DNS_OVER_TLS_CHOICES: [&str; 4] = ["Default", "Off", "Opportunistic", "Strict"];
+const DNS_OVER_TLS_MODES: [DnsOverTls; 4] = [
+    DnsOverTls::Default,
+    DnsOverTls::Off,
+    DnsOverTls::Opportunistic,
+    DnsOverTls::Strict,
+];
+
+/// Show the editor for `ssid` over `parent`, prefilled with `options`.
+///
+/// `on_save` receives the edited options; cancelling calls nothing.
+/// The DNS-over-TLS selector is disabled unless `resolved` says
+/// systemd-resolved is there to honour it.
+pub fn show_connection_editor(
+    parent: &Window,
+    ssid: &str,
+    options: ProfileOptions,
+    resolved: bool,
+    on_save: impl Fn(ProfileOptions) + 'static,
+) {
+    let dialog = Window::builder()
+        .modal(true)
+        .transient_for(parent)
+        .title(format!("{ssid} settings"))
+        .resizable(false)
+        .build();
+    dialog.add_css_class("connection-editor");
+
+    let vbox = GtkBox::new(Orientation::Vertical, 8);
+    vbox.add_css_class("password-section");
+
+    let title = Label::new(Some(&format!("{ssid} settings")));
+    title.add_css_class("password-title");
+    title.set_halign(gtk4::Align::Start);
+    vbox.append(&title);
+
+    // ── Proxy ──
+    vbox.append(&section_label("Proxy"));
+    let proxy_dropdown = DropDown::from_strings(&PROXY_CHOICES);
+    proxy_dropdown.add_css_class("editor-dropdown");
+    vbox.append(&proxy_dropdown);
+
+    let proxy_entry = Entry::new();
+    proxy_entry.add_css_class("password-entry");
+    vbox.append(&proxy_entry);
+
+    let (selected, text) = match &options.proxy {
+        ProxyMode::None => (0, ""),
+        ProxyMode::Auto => (1, ""),
+        ProxyMode::PacUrl(url) => (2, url.as_str()),
+        ProxyMode::Manual(host_port) => (3, host_port.as_str()),
+    };
+    proxy_dropdown.set_selected(selected);
+    proxy_entry.set_text(text);
+    update_proxy_entry(&proxy_entry, selected);
+    {
+        let proxy_entry = proxy_entry.clone();
+        proxy_dropdown.connect_selected_notify(move |dropdown| {
+            update_proxy_entry(&proxy_entry, dropdown.selected());
+        });
+    }
+
+    // ── Advanced ──
+    vbox.append(&section_label("Advanced"));
+    let mtu_entry = Entry::new();
+    mtu_entry.add_css_class("password-entry");
+    mtu_entry.set_placeholder_text(Some("MTU (automatic)"));
+    mtu_entry.set_input_purpose(gtk4::InputPurpose::Digits);
+    if options.mtu > 0 {
+        mtu_entry.set_text(&options.mtu.to_string());
+    }
+    vbox.append(&mtu_entry);
+
+    let mac_entry = Entry::new();
+    mac_entry.add_css_class("password-entry");
+    mac_entry.set_placeholder_text(Some("Cloned MAC (default)"));
+    mac_entry.set_tooltip_text(Some(
+        "A MAC address, or permanent / preserve / random / stable",
+    ));
+    mac_entry.set_text(options.cloned_mac.as_deref().unwrap_or(""));
+    vbox.append(&mac_entry);
+
+    // ── DNS over TLS ──
+    vbox.append(&section_label("DNS over TLS"));
+    let dot_dropdown = DropDown::from_strings(&DNS_OVER_TLS_CHOICES);
+    dot_dropdown.add_css_class("editor-dropdown");
+    let dot_selected = DNS_OVER_TLS_MODES
+        .iter()
+        .position(|mode| *mode == options.dns_over_tls)
+        .unwrap_or(0);
+    dot_dropdown.set_selected(dot_selected as u32);
+    if !resolved {
+        dot_dropdown.set_sensitive(false);
+        dot_dropdown.set_tooltip_text(Some("Needs systemd-resolved"));
+    }
+    vbox.append(&dot_dropdown);
+
+    let error_label = Label::new(None);
+    error_label.add_css_class("error-label");
+    error_label.set_halign(gtk4::Align::Start);
+    error_label.set_visible(false);
+    vbox.append(&error_label);
+
+    let button_box = GtkBox::new(Orientation::Horizontal, 8);
+    button_box.add_css_class("password-buttons");
+    button_box.set_halign(gtk4::Align::End);
+
+    let cancel_button = Button::with_label("Cancel");
+    cancel_button.add_css_class("cancel-button");
+    let save_button = Button::with_label("Save");
+    save_button.add_css_class("connect-button");
+
+    button_box.append(&cancel_button);
+    button_box.append(&save_button);
+    vbox.append(&button_box);
+    dialog.set_child(Some(&vbox));
+
+    {
+        let dialog = dialog.clone();
+        cancel_button.connect_clicked(move |_| dialog.close());
+    }
+    {
+        let dialog = dialog.clone();
+        save_button.connect_clicked(move |_| {
+            let read = read_proxy(&proxy_dropdown, &proxy_entry)
+                .and_then(|proxy| Ok((proxy, read_mtu(&mtu_entry)?, read_cloned_mac(&mac_entry)?)));
+            let (proxy, mtu, cloned_mac) = match read {
+                Ok(fields) => fields,
+                Err(message) => {
+                    error_label.set_text(message);
+                    error_label.set_visible(true);
+                    return;
+                }
+            };
+            let mut edited = options.clone();
+            edited.proxy = proxy;
+            edited.mtu = mtu;
+            edited.cloned_mac = cloned_mac;
+            if let Some(mode) = DNS_OVER_TLS_MODES.get(dot_dropdown.selected() as usize) {
+                edited.dns_over_tls = *mode;
+            }
+            on_save(edited);
+            dialog.close();
+        });
+    }
+
+    dialog.present();
+}
+
+fn section_label(text: &str) -> Label {
+    let label = Label::new(Some(text));
+    label.add_css_class("editor-section");
+    label.set_halign(gtk4::Align::Start);
+    label
+}
+
+/// Show the proxy entry only for choices that take a value.
+fn update_proxy_entry(entry: &Entry, selected: u32) {
+    match selected {
+        2 => entry.set_placeholder_text(Some("http://example.com/proxy.pac")),
+        3 => entry.set_placeholder_text(Some("proxy.example.com:3128")),
+        _ => {}
+    }
+    entry.set_visible(selected >= 2);
+}
+
+/// Validate the proxy fields into a `ProxyMode`.
+fn read_proxy(dropdown: &DropDown, entry: &Entry) -> Result<ProxyMode, &'static str> {
+    let text = entry.text().trim().to_string();
+    match dropdown.selected() {
+        1 => Ok(ProxyMode::Auto),
+        2 if text.is_empty() => Err("PAC URL cannot be empty"),
+        2 => Ok(ProxyMode::PacUrl(text)),
+        3 => ProxyMode::manual(&text).ok_or("Enter the proxy as host:port"),
+        _ => Ok(ProxyMode::None),
+    }
+}
+
+/// Validate the MTU field; empty means automatic (0).
+fn read_mtu(entry: &Entry) -> Result<u32, &'static str> {
+    let text = entry.text().trim().to_string();
+    if text.is_empty() {
+        return Ok(0);
+    }
+    match text.parse::<u32>() {
+        Ok(mtu) if (576..=9000).contains(&mtu) => Ok(mtu),
+        _ => Err("MTU must be between 576 and 9000"),
+    }
+}
+
+/// Validate the cloned MAC field; empty keeps NM's default.
+fn read_cloned_mac(entry: &Entry) -> Result<Option<String>, &'static str> {
+    let text = entry.text().trim().to_string();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    let keyword = text.to_lowercase();
+    if MAC_ADDRESS_MODES.contains(&keyword.as_str()) {
+        return Ok(Some(keyword));
+    }
+    access_point::parse_bssid(&text)
+        .and_then(|bytes| access_point::format_bssid(&bytes))
+        .map(Some)
+        .ok_or("Enter a MAC address like 02:00:00:12:34:56")
+}
@@ -5,7 +5,7 @@ use gtk4::{Align, Label, ListBox, ListBoxRow, PolicyType, ScrolledWindow, Select
 use std::collections::HashMap;
 use crate::ui::window::{MIN_LIST_HEIGHT, MAX_LIST_HEIGHT};
 
-use super::device_row;
+use super::device_row::{self, DeviceRowCallbacks};
 use crate::dbus::bluetooth_device::BluetoothDevice;
 
 /// Build a scrollable device list.
@@ -34,8 +34,8 @@ pub fn populate_device_list(
     list_box: &ListBox,
     devices: &[BluetoothDevice],
     pending: &HashMap<String, String>,
-    on_remove: std::rc::Rc<dyn Fn(String)>,
-    on_menu_active: std::rc::Rc<dyn Fn(bool)>,
+    bt_icons: &HashMap<String, String>,
+    callbacks: &DeviceRowCallbacks,
 ) -> Vec<Option<String>> {
     // Remove all existing rows
     while let Some(row) = list_box.first_child() {
@@ -49,31 +49,19 @@ pub fn populate_device_list(
         return Vec::new();
     }
 
-    let has_paired = devices.iter().any(|d| d.paired);
-    let mut inserted_separator = false;
+    // Devices arrive sorted by section; a header starts each one
+    let mut section = None;
     let mut row_paths: Vec<Option<String>> = Vec::new();
 
     for device in devices {
-        if has_paired && !device.paired && !inserted_separator {
-            list_box.append(&build_separator_row("Available devices"));
-            inserted_separator = true;
+        if section != Some(device.section()) {
+            section = Some(device.section());
+            list_box.append(&build_separator_row(&device.section().to_string()));
             row_paths.push(None);
         }
 
-        let on_remove = on_remove.clone();
-        let on_menu_active = on_menu_active.clone();
-
         let pending_label = pending.get(&device.device_path).cloned();
-        let row = device_row::build_device_row(
-            device,
-            pending_label,
-            move |device_path| {
-                on_remove(device_path);
-            },
-            move |active| {
-                on_menu_active(active);
-            },
-        );
+        let row = device_row::build_device_row(device, bt_icons, pending_label, callbacks);
         list_box.append(&row);
         row_paths.push(Some(device.device_path.clone()));
     }
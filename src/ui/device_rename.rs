@@ -0,0 +1,41 @@
+//! Inline entry for renaming a Bluetooth device (its BlueZ alias).
+
+use gtk4::prelude::*;
+use gtk4::{Button, Entry, Label, Revealer};
+
+use super::inline_section::{build_inline_section, section_button};
+
+/// All widgets produced by the rename builder.
+#[derive(Clone)]
+pub struct DeviceRename {
+    pub revealer: Revealer,
+    pub title: Label,
+    pub entry: Entry,
+    pub save_button: Button,
+    pub cancel_button: Button,
+}
+
+/// Build the rename section; hidden until `revealer.set_reveal_child(true)`.
+pub fn build_device_rename() -> DeviceRename {
+    let entry = Entry::new();
+    entry.add_css_class("password-entry");
+
+    let cancel_button = section_button("Cancel", "cancel-button");
+    let save_button = section_button("Rename", "connect-button");
+
+    let section = build_inline_section(
+        "device-rename",
+        &[entry.upcast_ref()],
+        &[&cancel_button, &save_button],
+    );
+    // Title label (updated with the current device name)
+    section.title.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+
+    DeviceRename {
+        revealer: section.revealer,
+        title: section.title,
+        entry,
+        save_button,
+        cancel_button,
+    }
+}
@@ -2,17 +2,61 @@
 
 use gtk4::prelude::*;
 use gtk4::{Box as GtkBox, Label, ListBoxRow, Orientation};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::rc::Rc;
 
-use crate::dbus::bluetooth_device::BluetoothDevice;
+use crate::controls::audio_profile::AudioMode;
+use crate::dbus::bluetooth_device::{BluetoothDevice, DeviceCategory};
+use crate::dbus::bluetooth_manager::MAX_TRANSPORT_VOLUME;
+
+/// How long a dragged volume slider settles before the value is sent.
+const VOLUME_SEND_DELAY_MS: u64 = 100;
+
+/// Actions a device row can trigger; every row in a list shares one set.
+#[derive(Clone)]
+pub struct DeviceRowCallbacks {
+    pub on_remove: Rc<dyn Fn(String)>,
+    pub on_repair: Rc<dyn Fn(String)>,
+    pub on_audio_mode: Rc<dyn Fn(String, AudioMode)>,
+    pub on_volume: Rc<dyn Fn(String, u16)>,
+    pub on_connect_profile: Rc<dyn Fn(String, String)>,
+    pub on_cancel_pairing: Rc<dyn Fn(String)>,
+    pub on_send_file: Rc<dyn Fn(String)>,
+    pub on_tether: Rc<dyn Fn(String)>,
+    pub on_rename: Rc<dyn Fn(String)>,
+    pub on_details: Rc<dyn Fn(String)>,
+    pub on_menu_active: Rc<dyn Fn(bool)>,
+}
+
+/// Callbacks that do nothing (used when BT is off / the list is empty).
+impl Default for DeviceRowCallbacks {
+    fn default() -> Self {
+        let ignore: Rc<dyn Fn(String)> = Rc::new(|_| {});
+        Self {
+            on_remove: ignore.clone(),
+            on_repair: ignore.clone(),
+            on_audio_mode: Rc::new(|_, _| {}),
+            on_volume: Rc::new(|_, _| {}),
+            on_connect_profile: Rc::new(|_, _| {}),
+            on_cancel_pairing: ignore.clone(),
+            on_send_file: ignore.clone(),
+            on_tether: ignore.clone(),
+            on_rename: ignore.clone(),
+            on_details: ignore,
+            on_menu_active: Rc::new(|_| {}),
+        }
+    }
+}
 
 /// Build a `ListBoxRow` for a single Bluetooth device.
 ///
 /// Layout: [device_icon] [Name / Subtitle] [menu_btn]
 pub fn build_device_row(
     device: &BluetoothDevice,
+    bt_icons: &HashMap<String, String>,
     pending_label: Option<String>,
-    on_remove: impl Fn(String) + 'static,
-    on_menu_active: impl Fn(bool) + 'static,
+    callbacks: &DeviceRowCallbacks,
 ) -> ListBoxRow {
     let row = ListBoxRow::new();
     row.add_css_class("device-row");
@@ -24,10 +68,20 @@ pub fn build_device_row(
     }
     if pending_label.is_some() {
         row.add_css_class("pending");
+        // Already in flight; a second click would start it again
+        row.set_activatable(false);
     }
 
-    if let Some(cursor) = gtk4::gdk::Cursor::from_name("pointer", None) {
-        row.set_cursor(Some(&cursor));
+    // LE beacons and sensors have nothing to connect to
+    if device.offers_connect() {
+        if pending_label.is_none()
+            && let Some(cursor) = gtk4::gdk::Cursor::from_name("pointer", None)
+        {
+            row.set_cursor(Some(&cursor));
+        }
+    } else {
+        row.set_activatable(false);
+        row.set_tooltip_text(Some("Bluetooth LE device — nothing to connect"));
     }
 
     let hbox = GtkBox::new(Orientation::Horizontal, 12);
@@ -35,8 +89,8 @@ pub fn build_device_row(
     hbox.set_margin_top(4);
     hbox.set_margin_bottom(4);
 
-    // Device category icon
-    let icon_text = device.category.default_icon();
+    // Device icon (from the BlueZ icon hint, else the category)
+    let icon_text = device.glyph(bt_icons);
     let icon_label = Label::new(Some(icon_text));
     icon_label.add_css_class("device-icon");
     icon_label.set_valign(gtk4::Align::Center);
@@ -79,9 +133,85 @@ pub fn build_device_row(
     info_vbox.append(&name_row);
     info_vbox.append(&subtitle_label);
 
+    // Hovering the slider holds off list refreshes, like an open menu
+    let on_menu_active = callbacks.on_menu_active.clone();
+
+    // Absolute volume of a connected speaker / headset (AVRCP)
+    if device.connected
+        && let (Some(volume), Some(transport_path)) = (device.volume, device.transport_path.clone())
+    {
+        let scale = gtk4::Scale::with_range(
+            Orientation::Horizontal,
+            0.0,
+            f64::from(MAX_TRANSPORT_VOLUME),
+            1.0,
+        );
+        scale.add_css_class("device-volume");
+        scale.set_value(f64::from(volume));
+        scale.set_hexpand(true);
+        scale.set_tooltip_text(Some("Device volume"));
+        // A drag fires a step per pixel; send only the latest value
+        let on_volume = callbacks.on_volume.clone();
+        let latest = Rc::new(Cell::new(volume));
+        let scheduled = Rc::new(Cell::new(false));
+        scale.connect_value_changed(move |scale| {
+            latest.set(scale.value().round() as u16);
+            if scheduled.replace(true) {
+                return;
+            }
+            let on_volume = on_volume.clone();
+            let transport_path = transport_path.clone();
+            let latest = latest.clone();
+            let scheduled = scheduled.clone();
+            gtk4::glib::timeout_add_local_once(
+                std::time::Duration::from_millis(VOLUME_SEND_DELAY_MS),
+                move || {
+                    scheduled.set(false);
+                    on_volume(transport_path, latest.get());
+                },
+            );
+        });
+
+        let hover = gtk4::EventControllerMotion::new();
+        {
+            let on_menu_active = on_menu_active.clone();
+            hover.connect_enter(move |_, _, _| on_menu_active(true));
+        }
+        {
+            let on_menu_active = on_menu_active.clone();
+            hover.connect_leave(move |_| on_menu_active(false));
+        }
+        scale.add_controller(hover);
+        info_vbox.append(&scale);
+    }
+
     hbox.append(&icon_label);
     hbox.append(&info_vbox);
 
+    // In flight: spinner on this row rather than only the header status
+    if pending_label.is_some() {
+        let spinner = gtk4::Spinner::new();
+        spinner.add_css_class("row-spinner");
+        spinner.set_spinning(true);
+        spinner.set_valign(gtk4::Align::Center);
+        hbox.append(&spinner);
+    }
+
+    // Pairing can wait on a device that never answers; let it be aborted
+    if pending_label.as_deref() == Some("Pairing") {
+        let cancel_button = gtk4::Button::with_label("Cancel");
+        cancel_button.add_css_class("cancel-button");
+        cancel_button.add_css_class("device-cancel-button");
+        cancel_button.set_valign(gtk4::Align::Center);
+        if let Some(cursor) = gtk4::gdk::Cursor::from_name("pointer", None) {
+            cancel_button.set_cursor(Some(&cursor));
+        }
+        let device_path = device.device_path.clone();
+        let on_cancel_pairing = callbacks.on_cancel_pairing.clone();
+        cancel_button.connect_clicked(move |_| on_cancel_pairing(device_path.clone()));
+        hbox.append(&cancel_button);
+    }
+
     // Trusted icon (if trusted but not connected)
     if device.trusted && !device.connected {
         let trusted_label = Label::new(Some("󰄬"));
@@ -91,12 +221,54 @@ pub fn build_device_row(
         hbox.append(&trusted_label);
     }
 
-    // Menu button (for paired or connected devices)
-    if device.paired || device.connected {
+    // Menu button (Details for every device, actions for paired ones)
+    {
         use gtk4::{gio, MenuButton, PopoverMenu};
 
+        let known = device.paired || device.connected;
         let menu = gio::Menu::new();
-        menu.append(Some("Unpair"), Some("row.remove"));
+        menu.append(Some("Details"), Some("row.details"));
+        if device.paired {
+            menu.append(Some("Send file…"), Some("row.send-file"));
+        }
+        let tether = device.paired && device.offers_internet();
+        if tether {
+            menu.append(Some("Use phone's internet"), Some("row.tether"));
+        }
+        // Multi-profile devices that misbehave when everything connects
+        let profiles = if device.paired {
+            device.connectable_profiles()
+        } else {
+            Vec::new()
+        };
+        if profiles.len() > 1 {
+            let submenu = gio::Menu::new();
+            for (uuid, label) in &profiles {
+                let item = gio::MenuItem::new(Some(*label), None);
+                item.set_action_and_target_value(
+                    Some("row.connect-profile"),
+                    Some(&uuid.to_variant()),
+                );
+                submenu.append_item(&item);
+            }
+            menu.append_submenu(Some("Connect only"), &submenu);
+        }
+        // Headsets get stuck in the wrong profile; offer both directions
+        let audio = device.connected && device.category == DeviceCategory::Audio;
+        if audio {
+            menu.append(Some("High-quality audio (A2DP)"), Some("row.audio-a2dp"));
+            menu.append(Some("Headset mode (HFP)"), Some("row.audio-hfp"));
+        }
+        if known {
+            menu.append(Some("Rename…"), Some("row.rename"));
+        }
+        // Broken pairings (e.g. after the device was reset) need a fresh one
+        if device.paired {
+            menu.append(Some("Remove and re-pair"), Some("row.repair"));
+        }
+        if known {
+            menu.append(Some("Unpair"), Some("row.remove"));
+        }
 
         let popover = PopoverMenu::from_model(Some(&menu));
         popover.add_css_class("device-popover");
@@ -117,14 +289,91 @@ pub fn build_device_row(
             on_menu_active(btn.is_active());
         });
 
-        let action = gio::SimpleAction::new("remove", None);
+        let action_group = gio::SimpleActionGroup::new();
+
+        let details_action = gio::SimpleAction::new("details", None);
         let device_path = device.device_path.clone();
-        action.connect_activate(move |_, _| {
-            on_remove(device_path.clone());
+        let on_details = callbacks.on_details.clone();
+        details_action.connect_activate(move |_, _| {
+            on_details(device_path.clone());
         });
+        action_group.add_action(&details_action);
 
-        let action_group = gio::SimpleActionGroup::new();
-        action_group.add_action(&action);
+        if known {
+            let action = gio::SimpleAction::new("remove", None);
+            let device_path = device.device_path.clone();
+            let on_remove = callbacks.on_remove.clone();
+            action.connect_activate(move |_, _| {
+                on_remove(device_path.clone());
+            });
+            action_group.add_action(&action);
+
+            let rename_action = gio::SimpleAction::new("rename", None);
+            let device_path = device.device_path.clone();
+            let on_rename = callbacks.on_rename.clone();
+            rename_action.connect_activate(move |_, _| {
+                on_rename(device_path.clone());
+            });
+            action_group.add_action(&rename_action);
+        }
+
+        if device.paired {
+            let action = gio::SimpleAction::new("repair", None);
+            let device_path = device.device_path.clone();
+            let on_repair = callbacks.on_repair.clone();
+            action.connect_activate(move |_, _| {
+                on_repair(device_path.clone());
+            });
+            action_group.add_action(&action);
+        }
+
+        if profiles.len() > 1 {
+            let action =
+                gio::SimpleAction::new("connect-profile", Some(gtk4::glib::VariantTy::STRING));
+            let device_path = device.device_path.clone();
+            let on_connect_profile = callbacks.on_connect_profile.clone();
+            action.connect_activate(move |_, uuid| {
+                if let Some(uuid) = uuid.and_then(|v| v.get::<String>()) {
+                    on_connect_profile(device_path.clone(), uuid);
+                }
+            });
+            action_group.add_action(&action);
+        }
+
+        if device.paired {
+            let action = gio::SimpleAction::new("send-file", None);
+            let address = device.address.clone();
+            let on_send_file = callbacks.on_send_file.clone();
+            action.connect_activate(move |_, _| {
+                on_send_file(address.clone());
+            });
+            action_group.add_action(&action);
+        }
+
+        if tether {
+            let action = gio::SimpleAction::new("tether", None);
+            let device_path = device.device_path.clone();
+            let on_tether = callbacks.on_tether.clone();
+            action.connect_activate(move |_, _| {
+                on_tether(device_path.clone());
+            });
+            action_group.add_action(&action);
+        }
+
+        if audio {
+            for (name, mode) in [
+                ("audio-a2dp", AudioMode::HighQuality),
+                ("audio-hfp", AudioMode::Headset),
+            ] {
+                let action = gio::SimpleAction::new(name, None);
+                let on_audio_mode = callbacks.on_audio_mode.clone();
+                let address = device.address.clone();
+                action.connect_activate(move |_, _| {
+                    on_audio_mode(address.clone(), mode);
+                });
+                action_group.add_action(&action);
+            }
+        }
         row.insert_action_group("row", Some(&action_group));
 
         hbox.append(&menu_btn);
@@ -140,14 +389,27 @@ fn device_subtitle(device: &BluetoothDevice, pending: Option<&str>) -> String {
 
     parts.push(device.category.to_string());
 
+    if device.le_only {
+        parts.push("LE".to_string());
+    }
+
     if device.connected {
         parts.push("Connected".to_string());
     } else if device.paired {
         parts.push("Paired".to_string());
     }
 
+    // Signal helps tell which of several identical devices is the near one
+    if !device.paired && !device.connected && device.is_in_range() {
+        parts.push(format!("{} dBm", device.rssi));
+    }
+
+    if let Some(battery) = device.battery {
+        parts.push(format!("{battery}% battery"));
+    }
+
     if let Some(pending) = pending {
-        parts.push(pending.to_string());
+        parts.push(format!("{pending}…"));
     }
 
     parts.join(" · ")
@@ -12,12 +12,16 @@ pub struct HeaderWidgets {
     pub title_label: Label,
     pub status_label: Label,
     pub scan_button: Button,
+    pub search_button: ToggleButton,
+    pub bt_search_button: ToggleButton,
+    pub bt_filter_button: ToggleButton,
+    pub bt_discoverable_button: ToggleButton,
     pub wifi_tab: ToggleButton,
     pub bt_tab: ToggleButton,
 }
 
 /// Build the header containing:
-/// - Top row: toggle switch (left) + title/status (center) + scan button (right)
+/// - Top row: toggle switch (left) + title/status (center) + search/scan buttons (right)
 /// - Tab bar: Wi-Fi / Bluetooth toggle buttons
 pub fn build_header() -> HeaderWidgets {
     let container = GtkBox::new(Orientation::Vertical, 0);
@@ -63,8 +67,56 @@ pub fn build_header() -> HeaderWidgets {
         scan_button.set_cursor(Some(&cursor));
     }
 
+    // Search button (reveals the network filter entry)
+    let search_button = ToggleButton::new();
+    search_button.set_icon_name("system-search-symbolic");
+    search_button.add_css_class("scan-button");
+    search_button.add_css_class("search-button");
+    search_button.set_tooltip_text(Some("Search (Ctrl+F)"));
+    search_button.set_valign(gtk4::Align::Center);
+    if let Some(cursor) = gtk4::gdk::Cursor::from_name("pointer", None) {
+        search_button.set_cursor(Some(&cursor));
+    }
+
+    // Bluetooth search button (reveals the device filter entry)
+    let bt_search_button = ToggleButton::new();
+    bt_search_button.set_icon_name("system-search-symbolic");
+    bt_search_button.add_css_class("scan-button");
+    bt_search_button.add_css_class("search-button");
+    bt_search_button.set_tooltip_text(Some("Search devices (Ctrl+F)"));
+    bt_search_button.set_valign(gtk4::Align::Center);
+    if let Some(cursor) = gtk4::gdk::Cursor::from_name("pointer", None) {
+        bt_search_button.set_cursor(Some(&cursor));
+    }
+
+    // Bluetooth filter (hides devices that only show a MAC address)
+    let bt_filter_button = ToggleButton::new();
+    bt_filter_button.set_icon_name("view-conceal-symbolic");
+    bt_filter_button.add_css_class("scan-button");
+    bt_filter_button.add_css_class("bt-filter-button");
+    bt_filter_button.set_tooltip_text(Some("Hide unnamed devices"));
+    bt_filter_button.set_valign(gtk4::Align::Center);
+    if let Some(cursor) = gtk4::gdk::Cursor::from_name("pointer", None) {
+        bt_filter_button.set_cursor(Some(&cursor));
+    }
+
+    // Bluetooth discoverable (lets phones find and pair with this machine)
+    let bt_discoverable_button = ToggleButton::new();
+    bt_discoverable_button.set_icon_name("emblem-shared-symbolic");
+    bt_discoverable_button.add_css_class("scan-button");
+    bt_discoverable_button.add_css_class("bt-discoverable-button");
+    bt_discoverable_button.set_tooltip_text(Some("Make discoverable"));
+    bt_discoverable_button.set_valign(gtk4::Align::Center);
+    if let Some(cursor) = gtk4::gdk::Cursor::from_name("pointer", None) {
+        bt_discoverable_button.set_cursor(Some(&cursor));
+    }
+
     top_row.append(&toggle_switch);
     top_row.append(&info_box);
+    top_row.append(&search_button);
+    top_row.append(&bt_search_button);
+    top_row.append(&bt_filter_button);
+    top_row.append(&bt_discoverable_button);
     top_row.append(&scan_button);
 
     // ── Tab bar ──────────────────────────────────────────────────────
@@ -102,6 +154,10 @@ pub fn build_header() -> HeaderWidgets {
         title_label,
         status_label,
         scan_button,
+        search_button,
+        bt_search_button,
+        bt_filter_button,
+        bt_discoverable_button,
         wifi_tab,
         bt_tab,
     }
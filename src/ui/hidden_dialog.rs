@@ -0,0 +1,92 @@
+//! Modal prompt for joining a network that doesn't broadcast its SSID.
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Button, Entry, Label, Orientation, Window};
+
+/// Show the hidden network prompt over `parent`.
+///
+/// `on_submit` receives `(ssid, password)`; the password is `None` when left
+/// empty (open network). Cancelling or closing the dialog calls nothing.
+pub fn show_hidden_dialog(parent: &Window, on_submit: impl Fn(String, Option<String>) + 'static) {
+    let dialog = Window::builder()
+        .modal(true)
+        .transient_for(parent)
+        .title("Hidden network")
+        .resizable(false)
+        .build();
+    dialog.add_css_class("hidden-dialog");
+
+    let vbox = GtkBox::new(Orientation::Vertical, 8);
+    vbox.add_css_class("password-section");
+
+    let title = Label::new(Some("Connect to hidden network"));
+    title.add_css_class("password-title");
+    title.set_halign(gtk4::Align::Start);
+    vbox.append(&title);
+
+    let ssid_entry = Entry::new();
+    ssid_entry.add_css_class("password-entry");
+    ssid_entry.set_placeholder_text(Some("Network name (SSID)"));
+    vbox.append(&ssid_entry);
+
+    let password_entry = Entry::new();
+    password_entry.add_css_class("password-entry");
+    password_entry.set_placeholder_text(Some("Password (leave empty if open)"));
+    password_entry.set_visibility(false);
+    password_entry.set_input_purpose(gtk4::InputPurpose::Password);
+    vbox.append(&password_entry);
+
+    let error_label = Label::new(None);
+    error_label.add_css_class("error-label");
+    error_label.set_halign(gtk4::Align::Start);
+    error_label.set_visible(false);
+    vbox.append(&error_label);
+
+    let button_box = GtkBox::new(Orientation::Horizontal, 8);
+    button_box.add_css_class("password-buttons");
+    button_box.set_halign(gtk4::Align::End);
+
+    let cancel_button = Button::with_label("Cancel");
+    cancel_button.add_css_class("cancel-button");
+    let connect_button = Button::with_label("Connect");
+    connect_button.add_css_class("connect-button");
+
+    button_box.append(&cancel_button);
+    button_box.append(&connect_button);
+    vbox.append(&button_box);
+    dialog.set_child(Some(&vbox));
+
+    {
+        let dialog = dialog.clone();
+        cancel_button.connect_clicked(move |_| dialog.close());
+    }
+    {
+        let dialog = dialog.clone();
+        let ssid_entry = ssid_entry.clone();
+        let password_entry = password_entry.clone();
+        connect_button.connect_clicked(move |_| {
+            let ssid = ssid_entry.text().trim().to_string();
+            if ssid.is_empty() {
+                error_label.set_text("Network name cannot be empty");
+                error_label.set_visible(true);
+                return;
+            }
+            let password = password_entry.text().to_string();
+            on_submit(ssid, (!password.is_empty()).then_some(password));
+            dialog.close();
+        });
+    }
+    {
+        let password_entry = password_entry.clone();
+        ssid_entry.connect_activate(move |_| {
+            password_entry.grab_focus();
+        });
+    }
+    {
+        let connect_button = connect_button.clone();
+        password_entry.connect_activate(move |_| connect_button.emit_clicked());
+    }
+
+    dialog.present();
+    ssid_entry.grab_focus();
+}
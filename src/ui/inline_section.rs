@@ -0,0 +1,64 @@
+//! Shared frame of the inline sections that slide open under a list
+//! (pairing prompt, rename, open-network warning, transfer, details).
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Button, Label, Orientation, Revealer, RevealerTransitionType};
+
+/// Widgets every inline section has.
+pub struct InlineSection {
+    pub revealer: Revealer,
+    pub title: Label,
+}
+
+/// Build a section hidden until `revealer.set_reveal_child(true)`.
+///
+/// Layout: title, then `body`, then `buttons` right-aligned. `class` tags the
+/// section for styling; the title is left for the caller to fill in.
+pub fn build_inline_section(
+    class: &str,
+    body: &[&gtk4::Widget],
+    buttons: &[&Button],
+) -> InlineSection {
+    let revealer = Revealer::new();
+    revealer.add_css_class("password-revealer");
+    revealer.set_transition_type(RevealerTransitionType::SlideDown);
+    revealer.set_transition_duration(200);
+    revealer.set_reveal_child(false);
+
+    let vbox = GtkBox::new(Orientation::Vertical, 8);
+    vbox.add_css_class("password-section");
+    vbox.add_css_class(class);
+
+    let title = Label::new(None);
+    title.add_css_class("password-title");
+    title.set_halign(gtk4::Align::Start);
+    vbox.append(&title);
+
+    for widget in body {
+        vbox.append(*widget);
+    }
+
+    let button_box = GtkBox::new(Orientation::Horizontal, 8);
+    button_box.add_css_class("password-buttons");
+    button_box.set_halign(gtk4::Align::End);
+    button_box.set_margin_top(4);
+
+    for button in buttons {
+        button_box.append(*button);
+    }
+    vbox.append(&button_box);
+    revealer.set_child(Some(&vbox));
+
+    InlineSection { revealer, title }
+}
+
+/// A section button with a pointer cursor; `class` is `cancel-button` or
+/// `connect-button`.
+pub fn section_button(label: &str, class: &str) -> Button {
+    let button = Button::with_label(label);
+    button.add_css_class(class);
+    if let Some(cursor) = gtk4::gdk::Cursor::from_name("pointer", None) {
+        button.set_cursor(Some(&cursor));
+    }
+    button
+}
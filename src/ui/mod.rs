@@ -1,10 +1,20 @@
+pub mod connection_editor;
 pub mod controls_panel;
 pub mod device_list;
+pub mod device_rename;
 pub mod device_row;
 pub mod header;
+pub mod hidden_dialog;
+pub mod inline_section;
+pub mod network_details;
 pub mod network_list;
 pub mod network_row;
+pub mod open_warning;
+pub mod pairing_prompt;
 pub mod password_dialog;
+pub mod pin_dialog;
+pub mod quick_toggles;
+pub mod transfer_row;
 pub mod vpn_list;
 pub mod vpn_row;
 pub mod window;
@@ -0,0 +1,55 @@
+//! Inline details of the connected network, shown when its row is clicked.
+
+use gtk4::prelude::*;
+use gtk4::{Button, Grid, Label, Revealer};
+
+use super::inline_section::{build_inline_section, section_button};
+
+/// All widgets produced by the network details builder.
+#[derive(Clone)]
+pub struct NetworkDetails {
+    pub revealer: Revealer,
+    pub title: Label,
+    pub grid: Grid,
+    pub close_button: Button,
+}
+
+/// Build the details section; hidden until `revealer.set_reveal_child(true)`.
+pub fn build_network_details() -> NetworkDetails {
+    let grid = Grid::new();
+    grid.set_row_spacing(4);
+    grid.set_column_spacing(12);
+
+    let close_button = section_button("Close", "cancel-button");
+
+    // Title label is updated with the network name
+    let section = build_inline_section("network-details", &[grid.upcast_ref()], &[&close_button]);
+
+    NetworkDetails {
+        revealer: section.revealer,
+        title: section.title,
+        grid,
+        close_button,
+    }
+}
+
+/// Replace the key/value rows shown in the details grid.
+pub fn set_detail_rows(details: &NetworkDetails, rows: &[(&str, String)]) {
+    while let Some(child) = details.grid.first_child() {
+        details.grid.remove(&child);
+    }
+    for (i, (key, value)) in rows.iter().enumerate() {
+        let key_label = Label::new(Some(key));
+        key_label.add_css_class("details-key");
+        key_label.set_halign(gtk4::Align::Start);
+
+        let value_label = Label::new(Some(value));
+        value_label.add_css_class("details-value");
+        value_label.set_halign(gtk4::Align::Start);
+        value_label.set_selectable(true);
+        value_label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+
+        details.grid.attach(&key_label, 0, i as i32, 1, 1);
+        details.grid.attach(&value_label, 1, i as i32, 1, 1);
+    }
+}
@@ -5,7 +5,8 @@ use gtk4::{Align, Label, ListBox, ListBoxRow, PolicyType, ScrolledWindow, Select
 use crate::ui::window::{MIN_LIST_HEIGHT, MAX_LIST_HEIGHT};
 
 use super::network_row;
-use crate::dbus::access_point::Network;
+use crate::config::SortOrder;
+use crate::dbus::access_point::{Band, Network};
 
 /// Build a scrollable network list.
 ///
@@ -37,7 +38,12 @@ pub fn populate_network_list(
     _wifi: &crate::dbus::network_manager::WifiManager,
     _status: &gtk4::Label,
     pending: &std::collections::HashMap<String, String>,
+    pending_active: &std::collections::HashMap<String, String>,
     on_forget: std::rc::Rc<dyn Fn(String)>,
+    on_lock_bssid: std::rc::Rc<dyn Fn(String, String)>,
+    on_edit: std::rc::Rc<dyn Fn(String)>,
+    on_disconnect: std::rc::Rc<dyn Fn(String)>,
+    on_cancel: std::rc::Rc<dyn Fn(String)>,
 ) -> Vec<Option<String>> {
     use gtk4::prelude::*;
     
@@ -57,20 +63,23 @@ pub fn populate_network_list(
     let mut saved: Vec<Network> = Vec::new();
     let mut available: Vec<Network> = Vec::new();
 
+    // Only the default order splits saved and available networks into sections
+    let sectioned = config.sort_order == SortOrder::SavedFirst;
     for net in networks {
         if net.is_connected {
             connected.push(net.clone());
-        } else if net.is_saved {
+        } else if net.is_saved || !sectioned {
             saved.push(net.clone());
         } else {
             available.push(net.clone());
         }
     }
 
-    let by_name = |a: &Network, b: &Network| a.ssid.to_lowercase().cmp(&b.ssid.to_lowercase());
-    connected.sort_by(by_name);
-    saved.sort_by(by_name);
-    available.sort_by(by_name);
+    let order = config.sort_order;
+    let compare = |a: &Network, b: &Network| compare_networks(order, a, b);
+    connected.sort_by(compare);
+    saved.sort_by(compare);
+    available.sort_by(compare);
 
     let mut row_ssids: Vec<Option<String>> = Vec::new();
     let mut rendered_any = false;
@@ -79,9 +88,31 @@ pub fn populate_network_list(
                           row_ssids: &mut Vec<Option<String>>| {
         let pending_label = pending.get(&net.ssid).map(String::as_str);
         let on_forget = on_forget.clone();
-        let row = network_row::build_network_row(net, config, pending_label, move |ssid| {
-            on_forget(ssid);
-        });
+        let on_lock_bssid = on_lock_bssid.clone();
+        let on_edit = on_edit.clone();
+        let on_disconnect = on_disconnect.clone();
+        // Cancellable once NM has handed back the active connection
+        let on_cancel = pending_active
+            .contains_key(&net.ssid)
+            .then(|| on_cancel.clone());
+        let row = network_row::build_network_row(
+            net,
+            config,
+            pending_label,
+            move |ssid| {
+                on_forget(ssid);
+            },
+            move |ssid, bssid| {
+                on_lock_bssid(ssid, bssid);
+            },
+            move |ssid| {
+                on_edit(ssid);
+            },
+            move |ssid| {
+                on_disconnect(ssid);
+            },
+            on_cancel,
+        );
         list_box.append(&row);
         row_ssids.push(Some(net.ssid.clone()));
     };
@@ -107,6 +138,27 @@ pub fn populate_network_list(
     row_ssids
 }
 
+/// Compare two networks for the configured list order.
+fn compare_networks(order: SortOrder, a: &Network, b: &Network) -> std::cmp::Ordering {
+    let by_name = || a.ssid.to_lowercase().cmp(&b.ssid.to_lowercase());
+    match order {
+        SortOrder::SavedFirst | SortOrder::Alphabetical => by_name(),
+        SortOrder::Strength => b.strength.cmp(&a.strength).then_with(by_name),
+        SortOrder::BandFirst => band_rank(&b.band)
+            .cmp(&band_rank(&a.band))
+            .then(b.strength.cmp(&a.strength))
+            .then_with(by_name),
+    }
+}
+
+fn band_rank(band: &Band) -> u8 {
+    match band {
+        Band::TwoGhz => 0,
+        Band::FiveGhz => 1,
+        Band::SixGhz => 2,
+    }
+}
+
 fn build_separator_row(label: &str) -> ListBoxRow {
     let row = ListBoxRow::new();
     row.add_css_class("list-separator-row");
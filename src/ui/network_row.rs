@@ -3,7 +3,8 @@
 use gtk4::prelude::*;
 use gtk4::{Box as GtkBox, Label, ListBoxRow, Orientation};
 
-use crate::dbus::access_point::{Band, Network, SecurityType};
+use crate::config::SignalDisplay;
+use crate::dbus::access_point::{self, Network, SecurityType};
 
 /// Signal strength thresholds for icon selection.
 fn signal_icon(strength: u8, icons: &[String; 4]) -> (&str, &'static str) {
@@ -22,14 +23,36 @@ fn signal_icon(strength: u8, icons: &[String; 4]) -> (&str, &'static str) {
     (icon.as_str(), class)
 }
 
+/// Numeric signal readout for `signal_display` (None when icon-only).
+///
+/// NM derives the percentage linearly from -100 dBm (0%) to -40 dBm (100%),
+/// clamping outside that range, so the dBm figure is that mapping reversed —
+/// an estimate, not the driver's reading.
+fn signal_value(strength: u8, display: SignalDisplay) -> Option<String> {
+    match display {
+        SignalDisplay::Icon => None,
+        SignalDisplay::Percent => Some(format!("{strength}%")),
+        SignalDisplay::Dbm => {
+            let dbm = i32::from(strength.min(100)) * 60 / 100 - 100;
+            Some(format!("{dbm} dBm"))
+        }
+    }
+}
+
 /// Build a `ListBoxRow` for a single network.
 ///
 /// The row displays: signal bars | [SSID / Subtitle] | menu.
+/// With `on_cancel`, a spinner and cancel button replace the trailing icons
+/// while the connect is in flight.
 pub fn build_network_row(
     network: &Network,
     config: &crate::config::Config,
     pending_label: Option<&str>,
     on_forget: impl Fn(String) + 'static,
+    on_lock_bssid: impl Fn(String, String) + 'static,
+    on_edit: impl Fn(String) + 'static,
+    on_disconnect: impl Fn(String) + 'static,
+    on_cancel: Option<std::rc::Rc<dyn Fn(String)>>,
 ) -> ListBoxRow {
     let row = ListBoxRow::new();
     row.add_css_class("network-row");
@@ -39,6 +62,9 @@ pub fn build_network_row(
     } else if network.is_saved {
         row.add_css_class("saved");
     }
+    if network.is_hidden {
+        row.add_css_class("hidden");
+    }
 
     if let Some(cursor) = gtk4::gdk::Cursor::from_name("pointer", None) {
         row.set_cursor(Some(&cursor));
@@ -56,6 +82,14 @@ pub fn build_network_row(
     signal_label.add_css_class(signal_class);
     signal_label.set_valign(gtk4::Align::Center);
 
+    let signal_value = signal_value(network.strength, config.signal_display).map(|text| {
+        let label = Label::new(Some(&text));
+        label.add_css_class("signal-value");
+        label.add_css_class(signal_class);
+        label.set_valign(gtk4::Align::Center);
+        label
+    });
+
     // Info VBox (SSID + Subtitle)
     let info_vbox = GtkBox::new(Orientation::Vertical, 2);
     info_vbox.add_css_class("network-row-info");
@@ -63,21 +97,27 @@ pub fn build_network_row(
     info_vbox.set_valign(gtk4::Align::Center);
 
     // SSID name
-    let ssid_label = Label::new(Some(&network.ssid));
+    let ssid_label = Label::new(Some(network.display_name()));
     ssid_label.add_css_class("ssid-label");
     ssid_label.set_halign(gtk4::Align::Start);
     ssid_label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
 
-    // Subtitle line (Band · Connectivity · Pending)
+    // Subtitle line (Band · Generation · Connectivity · Pending)
     let mut subtitle_parts = Vec::new();
     
-    if network.band == Band::FiveGhz {
-        subtitle_parts.push("5G".to_string());
+    if let Some(badge) = network.band.badge() {
+        subtitle_parts.push(badge.to_string());
+    }
+    if let Some(generation) = generation(network) {
+        subtitle_parts.push(generation.to_string());
     }
 
     if network.is_connected {
         subtitle_parts.push("Connected".to_string());
     }
+    if network.locked_bssid.is_some() {
+        subtitle_parts.push("Pinned".to_string());
+    }
     if let Some(pending) = pending_label {
         subtitle_parts.push(pending.to_string());
     }
@@ -95,8 +135,45 @@ pub fn build_network_row(
     info_vbox.append(&subtitle_label);
 
     hbox.append(&signal_label);
+    if let Some(signal_value) = &signal_value {
+        hbox.append(signal_value);
+    }
     hbox.append(&info_vbox);
 
+    // Connecting: spinner + cancel instead of the usual icons
+    if let Some(on_cancel) = on_cancel {
+        let spinner = gtk4::Spinner::new();
+        spinner.add_css_class("row-spinner");
+        spinner.set_spinning(true);
+        spinner.set_valign(gtk4::Align::Center);
+        hbox.append(&spinner);
+
+        let cancel_btn = gtk4::Button::from_icon_name("process-stop-symbolic");
+        cancel_btn.add_css_class("row-cancel-btn");
+        cancel_btn.add_css_class("flat");
+        cancel_btn.set_tooltip_text(Some("Cancel"));
+        cancel_btn.set_valign(gtk4::Align::Center);
+        if let Some(cursor) = gtk4::gdk::Cursor::from_name("pointer", None) {
+            cancel_btn.set_cursor(Some(&cursor));
+        }
+        let ssid = network.ssid.clone();
+        cancel_btn.connect_clicked(move |_| on_cancel(ssid.clone()));
+        hbox.append(&cancel_btn);
+
+        row.set_tooltip_text(Some(&details_tooltip(network)));
+        row.set_child(Some(&hbox));
+        return row;
+    }
+
+    // WEP is broken crypto; flag it rather than showing a plain lock
+    if network.security == SecurityType::WEP {
+        let legacy_label = Label::new(Some("WEP"));
+        legacy_label.add_css_class("legacy-badge");
+        legacy_label.set_tooltip_text(Some("Legacy insecure network"));
+        legacy_label.set_valign(gtk4::Align::Center);
+        hbox.append(&legacy_label);
+    }
+
     // Lock icon (if secured)
     if network.security != SecurityType::Open {
         let lock_label = Label::new(Some(&config.lock_icon));
@@ -121,6 +198,18 @@ pub fn build_network_row(
         use gtk4::{gio, MenuButton, PopoverMenu};
 
         let menu = gio::Menu::new();
+        if network.is_connected {
+            menu.append(Some("Details"), Some("row.details"));
+        }
+        if let Some(ap_menu) = build_access_point_menu(network) {
+            menu.append_submenu(Some("Access point"), &ap_menu);
+        }
+        if network.is_saved {
+            menu.append(Some("Settings…"), Some("row.edit"));
+        }
+        if network.is_connected {
+            menu.append(Some("Disconnect"), Some("row.disconnect"));
+        }
         menu.append(Some("Forget"), Some("row.forget"));
 
         let popover = PopoverMenu::from_model(Some(&menu));
@@ -146,13 +235,114 @@ pub fn build_network_row(
             on_forget(ssid.clone());
         });
 
+        // Target is the BSSID to pin, or "" to allow any AP again
+        let lock_action = gio::SimpleAction::new("lock-bssid", Some(gtk4::glib::VariantTy::STRING));
+        let ssid = network.ssid.clone();
+        lock_action.connect_activate(move |_, param| {
+            if let Some(bssid) = param.and_then(|p| p.get::<String>()) {
+                on_lock_bssid(ssid.clone(), bssid);
+            }
+        });
+
+        let edit_action = gio::SimpleAction::new("edit", None);
+        let ssid = network.ssid.clone();
+        edit_action.connect_activate(move |_, _| {
+            on_edit(ssid.clone());
+        });
+
+        let disconnect_action = gio::SimpleAction::new("disconnect", None);
+        let ssid = network.ssid.clone();
+        disconnect_action.connect_activate(move |_, _| {
+            on_disconnect(ssid.clone());
+        });
+
+        // Same as clicking the row, which shows details when connected
+        let details_action = gio::SimpleAction::new("details", None);
+        let weak_row = row.downgrade();
+        details_action.connect_activate(move |_, _| {
+            if let Some(row) = weak_row.upgrade() {
+                row.activate();
+            }
+        });
+
         let action_group = gio::SimpleActionGroup::new();
         action_group.add_action(&action);
+        action_group.add_action(&lock_action);
+        action_group.add_action(&edit_action);
+        action_group.add_action(&disconnect_action);
+        action_group.add_action(&details_action);
         row.insert_action_group("row", Some(&action_group));
 
         hbox.append(&menu_btn);
     }
 
+    row.set_tooltip_text(Some(&details_tooltip(network)));
     row.set_child(Some(&hbox));
     row
 }
+
+/// "Access point" submenu listing each BSSID, for pinning the saved profile.
+///
+/// Hidden when there is nothing to choose between and no lock to remove.
+fn build_access_point_menu(network: &Network) -> Option<gtk4::gio::Menu> {
+    use gtk4::gio;
+    use gtk4::glib::ToVariant;
+
+    if network.access_points.len() < 2 && network.locked_bssid.is_none() {
+        return None;
+    }
+
+    let menu = gio::Menu::new();
+    for ap in &network.access_points {
+        let mark = if network.locked_bssid.as_deref() == Some(ap.bssid.as_str()) {
+            "✓ "
+        } else {
+            ""
+        };
+        let channel = access_point::channel_from_frequency(ap.frequency_mhz)
+            .map(|c| format!("Ch {c}"))
+            .unwrap_or_else(|| ap.band.to_string());
+        let label = format!("{mark}{} · {channel} · {}%", ap.bssid, ap.strength);
+        let item = gio::MenuItem::new(Some(&label), None);
+        item.set_action_and_target_value(Some("row.lock-bssid"), Some(&ap.bssid.to_variant()));
+        menu.append_item(&item);
+    }
+    if let Some(locked) = &network.locked_bssid {
+        // Pinned AP is out of range right now; still show what it is
+        if !network.access_points.iter().any(|ap| &ap.bssid == locked) {
+            let item = gio::MenuItem::new(Some(&format!("✓ {locked} · not in range")), None);
+            item.set_action_and_target_value(Some("row.lock-bssid"), Some(&locked.to_variant()));
+            menu.append_item(&item);
+        }
+        let item = gio::MenuItem::new(Some("Any access point"), None);
+        item.set_action_and_target_value(Some("row.lock-bssid"), Some(&"".to_variant()));
+        menu.append_item(&item);
+    }
+    Some(menu)
+}
+
+fn generation(network: &Network) -> Option<&'static str> {
+    access_point::generation_label(&network.band, network.bandwidth_mhz)
+}
+
+/// AP details shown on hover: band, channel width, generation and security.
+fn details_tooltip(network: &Network) -> String {
+    let mut parts = vec![network.band.to_string()];
+    if let Some(channel) = access_point::channel_from_frequency(network.frequency_mhz) {
+        parts.push(format!("Ch {channel}"));
+    }
+    if network.bandwidth_mhz > 0 {
+        parts.push(format!("{} MHz", network.bandwidth_mhz));
+    }
+    if network.max_bitrate_kbps > 0 {
+        parts.push(format!("up to {} Mbit/s", network.max_bitrate_kbps / 1000));
+    }
+    if let Some(generation) = generation(network) {
+        parts.push(generation.to_string());
+    }
+    parts.push(network.security.to_string());
+    if let Some(locked) = &network.locked_bssid {
+        parts.push(format!("Pinned to {locked}"));
+    }
+    parts.join(" · ")
+}
@@ -0,0 +1,48 @@
+//! Inline warning shown before joining a network without encryption.
+
+use gtk4::prelude::*;
+use gtk4::{Button, CheckButton, Label, Revealer};
+
+use super::inline_section::{build_inline_section, section_button};
+
+/// All widgets produced by the open network warning builder.
+#[derive(Clone)]
+pub struct OpenWarning {
+    pub revealer: Revealer,
+    pub title: Label,
+    pub dont_ask: CheckButton,
+    pub connect_button: Button,
+    pub cancel_button: Button,
+}
+
+/// Build the warning section; hidden until `revealer.set_reveal_child(true)`.
+pub fn build_open_warning() -> OpenWarning {
+    let body = Label::new(Some(
+        "Traffic is unencrypted. Others nearby can see the sites you visit.",
+    ));
+    body.add_css_class("open-warning-text");
+    body.set_halign(gtk4::Align::Start);
+    body.set_wrap(true);
+    body.set_xalign(0.0);
+
+    let dont_ask = CheckButton::with_label("Don't ask again");
+    dont_ask.add_css_class("open-warning-check");
+
+    let cancel_button = section_button("Cancel", "cancel-button");
+    let connect_button = section_button("Connect anyway", "connect-button");
+
+    // Title label is updated with the network name
+    let section = build_inline_section(
+        "open-warning",
+        &[body.upcast_ref(), dont_ask.upcast_ref()],
+        &[&cancel_button, &connect_button],
+    );
+
+    OpenWarning {
+        revealer: section.revealer,
+        title: section.title,
+        dont_ask,
+        connect_button,
+        cancel_button,
+    }
+}
@@ -0,0 +1,81 @@
+//! Inline PIN / passkey prompt shown while a Bluetooth device pairs.
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Button, Entry, Label, Orientation, Revealer};
+
+use super::inline_section::{build_inline_section, section_button};
+
+/// Fixed PINs that older speakers and car kits expect, offered one click away.
+pub const LEGACY_PINS: [&str; 2] = ["0000", "1234"];
+
+/// All widgets produced by the pairing prompt builder.
+#[derive(Clone)]
+pub struct PairingPrompt {
+    pub revealer: Revealer,
+    pub title: Label,
+    /// Passkey to compare or type on the device; hidden for PIN entry
+    pub code: Label,
+    pub entry: Entry,
+    /// Buttons filling in a common legacy PIN; shown for PIN entry only
+    pub pin_presets: GtkBox,
+    pub confirm_button: Button,
+    pub cancel_button: Button,
+}
+
+/// Build the pairing section; hidden until `revealer.set_reveal_child(true)`.
+pub fn build_pairing_prompt() -> PairingPrompt {
+    let code = Label::new(None);
+    code.add_css_class("pairing-code");
+    code.set_halign(gtk4::Align::Center);
+    code.set_selectable(true);
+    code.set_visible(false);
+
+    let entry = Entry::new();
+    entry.add_css_class("password-entry");
+
+    let pin_presets = GtkBox::new(Orientation::Horizontal, 6);
+    pin_presets.add_css_class("pairing-pin-presets");
+    pin_presets.set_halign(gtk4::Align::Start);
+    pin_presets.set_visible(false);
+    for pin in LEGACY_PINS {
+        let button = Button::with_label(pin);
+        button.add_css_class("flat");
+        button.set_tooltip_text(Some("Use this PIN"));
+        if let Some(cursor) = gtk4::gdk::Cursor::from_name("pointer", None) {
+            button.set_cursor(Some(&cursor));
+        }
+        let entry = entry.clone();
+        button.connect_clicked(move |_| {
+            entry.set_text(pin);
+            entry.grab_focus();
+            entry.set_position(-1);
+        });
+        pin_presets.append(&button);
+    }
+
+    let cancel_button = section_button("Cancel", "cancel-button");
+    let confirm_button = section_button("Pair", "connect-button");
+
+    let section = build_inline_section(
+        "pairing-prompt",
+        &[
+            code.upcast_ref(),
+            entry.upcast_ref(),
+            pin_presets.upcast_ref(),
+        ],
+        &[&cancel_button, &confirm_button],
+    );
+    // Title label (updated with the device name)
+    section.title.set_wrap(true);
+    section.title.set_xalign(0.0);
+
+    PairingPrompt {
+        revealer: section.revealer,
+        title: section.title,
+        code,
+        entry,
+        pin_presets,
+        confirm_button,
+        cancel_button,
+    }
+}
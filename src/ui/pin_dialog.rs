@@ -0,0 +1,67 @@
+//! Small modal PIN prompt used to unlock restricted actions in guest mode.
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Button, Entry, InputPurpose, Label, Orientation, Window};
+
+/// Show a modal PIN prompt over `parent`.
+///
+/// `on_submit` receives the entered PIN when the user confirms; cancelling
+/// or closing the dialog calls nothing.
+pub fn show_pin_dialog(parent: &Window, action: &str, on_submit: impl Fn(String) + 'static) {
+    let dialog = Window::builder()
+        .modal(true)
+        .transient_for(parent)
+        .title("Guest mode")
+        .resizable(false)
+        .build();
+    dialog.add_css_class("pin-dialog");
+
+    let vbox = GtkBox::new(Orientation::Vertical, 8);
+    vbox.add_css_class("password-section");
+
+    let title = Label::new(Some(&format!("Enter PIN to {action}")));
+    title.add_css_class("password-title");
+    title.set_halign(gtk4::Align::Start);
+    vbox.append(&title);
+
+    let entry = Entry::new();
+    entry.add_css_class("password-entry");
+    entry.set_placeholder_text(Some("PIN"));
+    entry.set_visibility(false);
+    entry.set_input_purpose(InputPurpose::Pin);
+    vbox.append(&entry);
+
+    let button_box = GtkBox::new(Orientation::Horizontal, 8);
+    button_box.add_css_class("password-buttons");
+    button_box.set_halign(gtk4::Align::End);
+
+    let cancel_button = Button::with_label("Cancel");
+    cancel_button.add_css_class("cancel-button");
+    let unlock_button = Button::with_label("Unlock");
+    unlock_button.add_css_class("connect-button");
+
+    button_box.append(&cancel_button);
+    button_box.append(&unlock_button);
+    vbox.append(&button_box);
+    dialog.set_child(Some(&vbox));
+
+    {
+        let dialog = dialog.clone();
+        cancel_button.connect_clicked(move |_| dialog.close());
+    }
+    {
+        let dialog = dialog.clone();
+        let entry = entry.clone();
+        unlock_button.connect_clicked(move |_| {
+            on_submit(entry.text().to_string());
+            dialog.close();
+        });
+    }
+    {
+        let unlock_button = unlock_button.clone();
+        entry.connect_activate(move |_| unlock_button.emit_clicked());
+    }
+
+    dialog.present();
+    entry.grab_focus();
+}
@@ -0,0 +1,53 @@
+//! Quick toggles strip — compact on/off buttons at the top of the panel.
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Orientation, ToggleButton};
+
+/// All widgets produced by the quick toggles builder.
+pub struct QuickToggles {
+    pub container: GtkBox,
+    pub wifi: ToggleButton,
+    pub bluetooth: ToggleButton,
+    pub airplane: ToggleButton,
+    pub night_mode: ToggleButton,
+}
+
+/// Build one icon-only toggle button.
+fn build_toggle(icon: &str, tooltip: &str) -> ToggleButton {
+    let button = ToggleButton::new();
+    button.set_icon_name(icon);
+    button.set_tooltip_text(Some(tooltip));
+    button.add_css_class("quick-toggle");
+    button.set_hexpand(true);
+    if let Some(cursor) = gtk4::gdk::Cursor::from_name("pointer", None) {
+        button.set_cursor(Some(&cursor));
+    }
+    button
+}
+
+/// Build the quick toggles row: Wi-Fi, Bluetooth, Airplane, Night mode.
+pub fn build_quick_toggles() -> QuickToggles {
+    let container = GtkBox::new(Orientation::Horizontal, 6);
+    container.add_css_class("quick-toggles");
+
+    let wifi = build_toggle("network-wireless-symbolic", "Wi-Fi");
+    let bluetooth = build_toggle("bluetooth-active-symbolic", "Bluetooth");
+    let airplane = build_toggle("airplane-mode-symbolic", "Airplane mode");
+    let night_mode = build_toggle("night-light-symbolic", "Night mode");
+
+    // Hidden until a Bluetooth adapter is found
+    bluetooth.set_visible(false);
+
+    container.append(&wifi);
+    container.append(&bluetooth);
+    container.append(&airplane);
+    container.append(&night_mode);
+
+    QuickToggles {
+        container,
+        wifi,
+        bluetooth,
+        airplane,
+        night_mode,
+    }
+}
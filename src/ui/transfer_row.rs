@@ -0,0 +1,37 @@
+//! Progress row for a Bluetooth file transfer, shown under the device list.
+
+use gtk4::prelude::*;
+use gtk4::{Button, Label, ProgressBar, Revealer};
+
+use super::inline_section::{build_inline_section, section_button};
+
+/// All widgets produced by the transfer row builder.
+#[derive(Clone)]
+pub struct TransferRow {
+    pub revealer: Revealer,
+    pub title: Label,
+    pub progress: ProgressBar,
+    pub cancel_button: Button,
+}
+
+/// Build the transfer row; hidden until `revealer.set_reveal_child(true)`.
+pub fn build_transfer_row() -> TransferRow {
+    let progress = ProgressBar::new();
+    progress.add_css_class("transfer-progress");
+    progress.set_show_text(true);
+
+    let cancel_button = section_button("Cancel", "cancel-button");
+
+    let section = build_inline_section("transfer-row", &[progress.upcast_ref()], &[&cancel_button]);
+    // Title label (updated with the file and device name)
+    section
+        .title
+        .set_ellipsize(gtk4::pango::EllipsizeMode::Middle);
+
+    TransferRow {
+        revealer: section.revealer,
+        title: section.title,
+        progress,
+        cancel_button,
+    }
+}
@@ -4,6 +4,8 @@
 //! dialog into the panel. Uses a GtkStack to switch between Wi-Fi and
 //! Bluetooth views based on the header tab selection.
 
+use std::cell::RefCell;
+
 use gtk4::prelude::*;
 use gtk4::{
     Application, ApplicationWindow, Box as GtkBox, CssProvider, ListBox, Orientation, Stack,
@@ -11,8 +13,11 @@ use gtk4::{
 };
 use gtk4_layer_shell::{Edge, KeyboardMode, Layer, LayerShell};
 
-use super::{controls_panel, device_list, header, network_list, password_dialog, vpn_list};
-use crate::config::{Config, Position};
+use super::{
+    controls_panel, device_list, device_rename, header, network_details, network_list, open_warning,
+    pairing_prompt, password_dialog, quick_toggles, transfer_row, vpn_list,
+};
+use crate::config::{self, Config, PanelLayer, Position};
 
 /// Minimum pixel height for list boxes (shows ~3 items)
 pub const MIN_LIST_HEIGHT: i32 = 220;
@@ -30,20 +35,29 @@ pub struct PanelWidgets {
     pub title_label: gtk4::Label,
     pub status_label: gtk4::Label,
     pub scan_button: gtk4::Button,
+    pub search_button: gtk4::ToggleButton,
+    pub bt_search_button: gtk4::ToggleButton,
+    pub bt_filter_button: gtk4::ToggleButton,
+    pub bt_discoverable_button: gtk4::ToggleButton,
     pub wifi_tab: gtk4::ToggleButton,
     pub bt_tab: gtk4::ToggleButton,
     // Wi-Fi page
     pub wifi_networks_tab: ToggleButton,
     pub wifi_vpn_tab: ToggleButton,
     pub wifi_sub_stack: Stack,
+    pub search_bar: gtk4::SearchBar,
+    pub search_entry: gtk4::SearchEntry,
     pub network_list_box: ListBox,
     pub network_scroll: gtk4::ScrolledWindow,
+    pub scan_age_label: gtk4::Label,
     pub spinner: gtk4::Spinner,
     pub password_revealer: gtk4::Revealer,
     pub password_entry: gtk4::Entry,
     pub connect_button: gtk4::Button,
     pub cancel_button: gtk4::Button,
     pub error_label: gtk4::Label,
+    pub open_warning: open_warning::OpenWarning,
+    pub network_details: network_details::NetworkDetails,
     // VPN page (inside Wi-Fi tab)
     pub vpn_import_button: gtk4::Button,
     pub vpn_open_button: gtk4::Button,
@@ -51,13 +65,22 @@ pub struct PanelWidgets {
     pub vpn_scroll: gtk4::ScrolledWindow,
     pub vpn_spinner: gtk4::Spinner,
     // Bluetooth page
+    pub bt_adapter_dropdown: gtk4::DropDown,
+    pub bt_search_bar: gtk4::SearchBar,
+    pub bt_search_entry: gtk4::SearchEntry,
     pub bt_list_box: ListBox,
     pub bt_scroll: gtk4::ScrolledWindow,
     pub bt_spinner: gtk4::Spinner,
+    pub bt_pairing: pairing_prompt::PairingPrompt,
+    pub bt_transfer: transfer_row::TransferRow,
+    pub bt_rename: device_rename::DeviceRename,
+    pub bt_details: network_details::NetworkDetails,
     // Content stack
     pub content_stack: Stack,
     // Controls panel
     pub controls: controls_panel::ControlsPanel,
+    // Quick toggles strip
+    pub quick_toggles: quick_toggles::QuickToggles,
 }
 
 /// Build the main floating panel window with all UI components.
@@ -73,8 +96,9 @@ pub fn build_window(app: &Application) -> PanelWidgets {
     // Initialize layer shell
     window.init_layer_shell();
     window.set_namespace(Some("wifi-manager"));
-    window.set_layer(Layer::Top);
-    window.set_keyboard_mode(KeyboardMode::OnDemand);
+    apply_layer(&window, &config);
+    apply_output(&window, &config);
+    follow_output_changes(&window);
 
     // Apply position from config
     apply_position(&window, &config);
@@ -86,6 +110,10 @@ pub fn build_window(app: &Application) -> PanelWidgets {
     let main_box = GtkBox::new(Orientation::Vertical, 0);
     main_box.add_css_class("wifi-panel");
 
+    // Quick toggles
+    let quick_toggles = quick_toggles::build_quick_toggles();
+    main_box.append(&quick_toggles.container);
+
     // Header
     let header = header::build_header();
     main_box.append(&header.container);
@@ -153,14 +181,65 @@ pub fn build_window(app: &Application) -> PanelWidgets {
     spinner.set_margin_top(20);
     spinner.set_margin_bottom(20);
 
+    // Search bar (Ctrl+F or the header search button)
+    let search_entry = gtk4::SearchEntry::new();
+    search_entry.add_css_class("search-entry");
+    search_entry.set_placeholder_text(Some("Filter networks"));
+    search_entry.set_hexpand(true);
+    let search_bar = gtk4::SearchBar::new();
+    search_bar.add_css_class("search-bar");
+    search_bar.set_child(Some(&search_entry));
+    search_bar.connect_entry(&search_entry);
+    header
+        .search_button
+        .bind_property("active", &search_bar, "search-mode-enabled")
+        .bidirectional()
+        .build();
+    // Each tab has its own search button
+    header
+        .wifi_tab
+        .bind_property("active", &header.search_button, "visible")
+        .sync_create()
+        .build();
+    header
+        .bt_tab
+        .bind_property("active", &header.bt_search_button, "visible")
+        .sync_create()
+        .build();
+    header
+        .bt_tab
+        .bind_property("active", &header.bt_filter_button, "visible")
+        .sync_create()
+        .build();
+    header
+        .bt_tab
+        .bind_property("active", &header.bt_discoverable_button, "visible")
+        .sync_create()
+        .build();
+
+    wifi_networks_view.append(&search_bar);
     wifi_networks_view.append(&spinner);
     wifi_networks_view.append(&scrolled);
     scrolled.set_visible(false);
 
+    let scan_age_label = gtk4::Label::new(None);
+    scan_age_label.add_css_class("scan-age-label");
+    scan_age_label.set_halign(gtk4::Align::Start);
+    scan_age_label.set_margin_start(20);
+    scan_age_label.set_margin_bottom(4);
+    scan_age_label.set_visible(false);
+    wifi_networks_view.append(&scan_age_label);
+
     let (revealer, entry, connect_btn, cancel_btn, error_label) =
         password_dialog::build_password_section();
     wifi_networks_view.append(&revealer);
 
+    let open_warning = open_warning::build_open_warning();
+    wifi_networks_view.append(&open_warning.revealer);
+
+    let network_details = network_details::build_network_details();
+    wifi_networks_view.append(&network_details.revealer);
+
     wifi_sub_stack.add_named(&wifi_networks_view, Some("networks"));
 
     // VPN view
@@ -213,6 +292,29 @@ pub fn build_window(app: &Application) -> PanelWidgets {
     // ── Bluetooth page ─────────────────────────────────────────────
     let bt_page = GtkBox::new(Orientation::Vertical, 0);
 
+    // Adapter selector, shown only when there is more than one adapter
+    let bt_adapter_dropdown = gtk4::DropDown::from_strings(&[]);
+    bt_adapter_dropdown.add_css_class("bt-adapter-dropdown");
+    bt_adapter_dropdown.set_tooltip_text(Some("Bluetooth adapter"));
+    bt_adapter_dropdown.set_visible(false);
+    bt_page.append(&bt_adapter_dropdown);
+
+    // Device search bar (Ctrl+F on the BT tab or the header search button)
+    let bt_search_entry = gtk4::SearchEntry::new();
+    bt_search_entry.add_css_class("search-entry");
+    bt_search_entry.set_placeholder_text(Some("Filter devices"));
+    bt_search_entry.set_hexpand(true);
+    let bt_search_bar = gtk4::SearchBar::new();
+    bt_search_bar.add_css_class("search-bar");
+    bt_search_bar.set_child(Some(&bt_search_entry));
+    bt_search_bar.connect_entry(&bt_search_entry);
+    header
+        .bt_search_button
+        .bind_property("active", &bt_search_bar, "search-mode-enabled")
+        .bidirectional()
+        .build();
+    bt_page.append(&bt_search_bar);
+
     let (bt_scrolled, bt_list_box) = device_list::build_device_list();
 
     let bt_spinner = gtk4::Spinner::new();
@@ -228,6 +330,19 @@ pub fn build_window(app: &Application) -> PanelWidgets {
     bt_page.append(&bt_scrolled);
     bt_scrolled.set_visible(false);
 
+    let bt_pairing = pairing_prompt::build_pairing_prompt();
+    bt_page.append(&bt_pairing.revealer);
+
+    let bt_transfer = transfer_row::build_transfer_row();
+    bt_page.append(&bt_transfer.revealer);
+
+    let bt_rename = device_rename::build_device_rename();
+    bt_page.append(&bt_rename.revealer);
+
+    // Same key/value section the Wi-Fi page uses for network details
+    let bt_details = network_details::build_network_details();
+    bt_page.append(&bt_details.revealer);
+
     content_stack.add_named(&bt_page, Some("bluetooth"));
 
     // Start on Wi-Fi page
@@ -307,29 +422,46 @@ pub fn build_window(app: &Application) -> PanelWidgets {
         title_label: header.title_label,
         status_label: header.status_label,
         scan_button: header.scan_button,
+        search_button: header.search_button,
+        bt_search_button: header.bt_search_button,
+        bt_filter_button: header.bt_filter_button,
+        bt_discoverable_button: header.bt_discoverable_button,
         wifi_tab: header.wifi_tab,
         bt_tab: header.bt_tab,
         wifi_networks_tab,
         wifi_vpn_tab,
         wifi_sub_stack,
+        search_bar,
+        search_entry,
         network_list_box: list_box,
         network_scroll: scrolled,
+        scan_age_label,
         spinner,
         password_revealer: revealer,
         password_entry: entry,
         connect_button: connect_btn,
         cancel_button: cancel_btn,
         error_label,
+        open_warning,
+        network_details,
         vpn_import_button,
         vpn_open_button,
         vpn_list_box,
         vpn_scroll: vpn_scrolled,
         vpn_spinner,
+        bt_adapter_dropdown,
+        bt_search_bar,
+        bt_search_entry,
         bt_list_box,
         bt_scroll: bt_scrolled,
         bt_spinner,
+        bt_pairing,
+        bt_transfer,
+        bt_rename,
+        bt_details,
         content_stack,
         controls,
+        quick_toggles,
     }
 }
 
@@ -349,49 +481,137 @@ fn load_css() {
     log::info!("Default CSS theme loaded");
 
     // Load optional user theme override
-    if let Some(config_dir) = dirs_config_path() {
-        let user_css_path = config_dir.join("style.css");
-        if user_css_path.exists() {
-            let user_provider = CssProvider::new();
-            user_provider.load_from_path(user_css_path.to_str().unwrap_or_default());
-            gtk4::style_context_add_provider_for_display(
-                &display,
-                &user_provider,
-                gtk4::STYLE_PROVIDER_PRIORITY_USER,
-            );
-            log::info!("User CSS theme loaded from {:?}", user_css_path);
-        }
-    }
+    load_user_css(&display);
 }
 
-/// Reload user CSS (for --reload flag).
+/// Reload user CSS (for --reload flag and config file changes).
 pub fn reload_css() {
     let display = gdk::Display::default().expect("Could not get default display");
+    load_user_css(&display);
+}
 
-    // Reload optional user theme override
-    if let Some(config_dir) = dirs_config_path() {
-        let user_css_path = config_dir.join("style.css");
-        if user_css_path.exists() {
-            let user_provider = CssProvider::new();
-            user_provider.load_from_path(user_css_path.to_str().unwrap_or_default());
-            gtk4::style_context_add_provider_for_display(
-                &display,
-                &user_provider,
-                gtk4::STYLE_PROVIDER_PRIORITY_USER,
-            );
-            log::info!("User CSS reloaded from {:?}", user_css_path);
-        }
+thread_local! {
+    /// Provider holding the user's style.css, replaced on every reload.
+    static USER_CSS: RefCell<Option<CssProvider>> = const { RefCell::new(None) };
+}
+
+/// Load `style.css` from the config directory over the default theme.
+///
+/// The previous user provider is removed first, so reloads don't pile up
+/// and deleting the file goes back to the default theme.
+fn load_user_css(display: &gdk::Display) {
+    let user_provider = dirs_config_path()
+        .map(|config_dir| config_dir.join("style.css"))
+        .filter(|path| path.exists())
+        .map(|user_css_path| {
+            let provider = CssProvider::new();
+            provider.load_from_path(user_css_path.to_str().unwrap_or_default());
+            log::info!("User CSS theme loaded from {:?}", user_css_path);
+            provider
+        });
+
+    if let Some(old) = USER_CSS.with(|current| current.replace(user_provider.clone())) {
+        gtk4::style_context_remove_provider_for_display(display, &old);
+    }
+    if let Some(provider) = &user_provider {
+        gtk4::style_context_add_provider_for_display(
+            display,
+            provider,
+            gtk4::STYLE_PROVIDER_PRIORITY_USER,
+        );
     }
 }
 
-/// Get the config directory: ~/.config/wifi-manager/
+/// Re-apply layer, keyboard mode and position after the config changed.
+pub fn reapply_config(window: &ApplicationWindow) {
+    let config = Config::load();
+    apply_layer(window, &config);
+    apply_output(window, &config);
+    apply_position(window, &config);
+}
+
+/// Get the config directory: ~/.config/wifi-manager/, or the directory of
+/// the `--config` file
 fn dirs_config_path() -> Option<std::path::PathBuf> {
-    let home = std::env::var("HOME").ok()?;
-    Some(
-        std::path::PathBuf::from(home)
-            .join(".config")
-            .join("wifi-manager"),
-    )
+    crate::config::config_dir()
+}
+
+/// Toggle a state class (e.g. `.connected`) on the `.wifi-panel` container.
+///
+/// `widget` is any widget inside the panel; its window's child is the panel.
+pub fn set_panel_class(widget: &impl IsA<gtk4::Widget>, class: &str, enabled: bool) {
+    let Some(panel) = widget
+        .root()
+        .and_downcast::<gtk4::Window>()
+        .and_then(|window| window.child())
+    else {
+        return;
+    };
+    if enabled {
+        panel.add_css_class(class);
+    } else {
+        panel.remove_css_class(class);
+    }
+}
+
+/// Apply layer and keyboard mode from config to a layer-shell window.
+///
+/// Some compositors only give a layer surface keyboard focus with
+/// `Exclusive`, otherwise the password entry never receives input.
+fn apply_layer(window: &ApplicationWindow, config: &Config) {
+    window.set_layer(match config.layer {
+        PanelLayer::Top => Layer::Top,
+        PanelLayer::Overlay => Layer::Overlay,
+    });
+    window.set_keyboard_mode(match config.keyboard_mode {
+        config::KeyboardMode::Exclusive => KeyboardMode::Exclusive,
+        config::KeyboardMode::OnDemand => KeyboardMode::OnDemand,
+        config::KeyboardMode::None => KeyboardMode::None,
+    });
+
+    log::info!("Layer: {:?}, keyboard mode: {:?}", config.layer, config.keyboard_mode);
+}
+
+/// Put the layer surface on the configured output.
+///
+/// `None` (unset, "focused", or the output isn't connected) leaves the
+/// choice to the compositor.
+fn apply_output(window: &ApplicationWindow, config: &Config) {
+    let wanted = config
+        .output
+        .as_deref()
+        .filter(|name| !name.is_empty() && *name != "focused");
+    let monitor = wanted.and_then(|name| {
+        let monitor = find_monitor(name);
+        if monitor.is_none() {
+            log::warn!("Output {name} not connected, letting the compositor pick");
+        }
+        monitor
+    });
+    window.set_monitor(monitor.as_ref());
+    log::info!("Output: {}", wanted.unwrap_or("focused"));
+}
+
+/// The connected monitor whose connector is `name` (e.g. "DP-1").
+fn find_monitor(name: &str) -> Option<gdk::Monitor> {
+    let monitors = gdk::Display::default()?.monitors();
+    (0..monitors.n_items())
+        .filter_map(|i| monitors.item(i).and_downcast::<gdk::Monitor>())
+        .find(|monitor| monitor.connector().is_some_and(|c| c.as_str() == name))
+}
+
+/// Re-apply the output when monitors are plugged or unplugged, so the panel
+/// moves back to its configured output once it reappears.
+fn follow_output_changes(window: &ApplicationWindow) {
+    let Some(display) = gdk::Display::default() else {
+        return;
+    };
+    let window = window.downgrade();
+    display.monitors().connect_items_changed(move |_, _, _, _| {
+        if let Some(window) = window.upgrade() {
+            apply_output(&window, &Config::load());
+        }
+    });
 }
 
 /// Apply window position and margins from config to a layer-shell window.